@@ -1,11 +1,11 @@
 use serde::Deserialize;
 
-use async_odoors::odoo::{deserialize_odoo_nullable, Odoo};
+use async_odoors::odoo::{deserialize_odoo_optional, Odoo};
 
 #[derive(Deserialize, Debug)]
 struct ProductTemplate {
     name: String,
-    #[serde(deserialize_with = "deserialize_odoo_nullable")]
+    #[serde(deserialize_with = "deserialize_odoo_optional")]
     default_code: Option<String>,
 }
 
@@ -0,0 +1,127 @@
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// An operation checked against access rights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Read,
+    Write,
+    Create,
+    Unlink,
+}
+
+impl Operation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Operation::Read => "read",
+            Operation::Write => "write",
+            Operation::Create => "create",
+            Operation::Unlink => "unlink",
+        }
+    }
+
+    // field-level rights (`groups=`) only distinguish reading from writing
+    fn field_operation(self) -> &'static str {
+        match self {
+            Operation::Read => "read",
+            _ => "write",
+        }
+    }
+}
+
+impl Odoo {
+    /// Whether the user may perform `operation` on `model` at all (`ir.model.access`),
+    /// without raising.
+    pub async fn check_access_rights(
+        &self,
+        model: &str,
+        operation: Operation,
+    ) -> Result<bool, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("raise_exception".to_string(), Value::Bool(false));
+        Ok(self
+            .call_kw(model, "check_access_rights", (operation.as_str(),), kwargs)
+            .await?
+            .result)
+    }
+
+    /// Fields of `model` the user may read or write, given fields restricted to groups.
+    ///
+    /// Uses `check_field_access_rights` where the server exposes it (up to Odoo 16) and
+    /// falls back to `fields_get`, which leaves out fields the user can't access.
+    pub async fn accessible_fields(
+        &self,
+        model: &str,
+        operation: Operation,
+    ) -> Result<Vec<String>, Error> {
+        let result = self
+            .call(
+                model,
+                "check_field_access_rights",
+                (operation.field_operation(), Value::Null),
+            )
+            .await;
+        match result {
+            Ok(response) => Ok(response.result),
+            Err(e) if e.message().contains("check_field_access_rights") => {
+                let mut fields: Vec<String> =
+                    self.fields_get(model).await?.keys().cloned().collect();
+                fields.sort();
+                Ok(fields)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Splits `fields` into those the user may access for `operation` and those restricted,
+    /// so a sync can skip the latter instead of failing the whole batch with an access error.
+    pub async fn split_accessible_fields<'a>(
+        &self,
+        model: &str,
+        operation: Operation,
+        fields: &[&'a str],
+    ) -> Result<(Vec<&'a str>, Vec<&'a str>), Error> {
+        let accessible = self.accessible_fields(model, operation).await?;
+        Ok(fields
+            .iter()
+            .partition(|field| *field == &"id" || accessible.iter().any(|f| f == *field)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_split_accessible_fields() {
+        use serde_json::json;
+
+        use crate::access::Operation;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        server.insert(
+            "res.partner",
+            json!({"name": "Azure", "email": "azure@example.com"}),
+        );
+
+        // servers without `check_field_access_rights` fall back to `fields_get`
+        let (allowed, denied) = odoo
+            .split_accessible_fields("res.partner", Operation::Read, &["id", "name", "salary"])
+            .await
+            .unwrap();
+        assert_eq!((allowed, denied), (vec!["id", "name"], vec!["salary"]));
+
+        server.on_call("res.partner", "check_field_access_rights", |args, _| {
+            assert_eq!(args[0], "write");
+            Ok(json!(["name"]))
+        });
+        let (allowed, denied) = odoo
+            .split_accessible_fields("res.partner", Operation::Create, &["name", "email"])
+            .await
+            .unwrap();
+        assert_eq!((allowed, denied), (vec!["name"], vec!["email"]));
+    }
+}
@@ -0,0 +1,264 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+use crate::xmlid::RecordRef;
+
+/// Follow-up action returned by a button method or a server action.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// The method returned `True`, `False` or `None`: nothing to do.
+    None,
+    /// `ir.actions.act_window`, e.g. a wizard or the created record to open.
+    Window(WindowAction),
+    /// `ir.actions.report`
+    Report(ReportAction),
+    /// `ir.actions.act_url`
+    Url(UrlAction),
+    /// `ir.actions.client`, e.g. `reload` or `display_notification`.
+    Client(ClientAction),
+    /// Any other action type, as returned by the server.
+    Other(Map<String, Value>),
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WindowAction {
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub name: Option<String>,
+    pub res_model: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub res_id: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub view_mode: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub domain: Value,
+    #[serde(default)]
+    pub context: Value,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ReportAction {
+    pub report_name: String,
+    #[serde(default)]
+    pub report_type: String,
+    #[serde(default)]
+    pub data: Value,
+    #[serde(default)]
+    pub context: Value,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UrlAction {
+    pub url: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub target: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ClientAction {
+    pub tag: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl Action {
+    pub fn from_value(value: Value) -> Result<Action, Error> {
+        let action = match value {
+            Value::Object(action) => action,
+            _ => return Ok(Action::None),
+        };
+        let action_type = action
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let action = match action_type.as_str() {
+            "ir.actions.act_window" => parse(action).map(Action::Window),
+            "ir.actions.report" => parse(action).map(Action::Report),
+            "ir.actions.act_url" => parse(action).map(Action::Url),
+            "ir.actions.client" => parse(action).map(Action::Client),
+            _ => Ok(Action::Other(action)),
+        };
+        action.map_err(|e| Error::Decode {
+            path: None,
+            message: e.to_string(),
+        })
+    }
+}
+
+fn parse<T: DeserializeOwned>(action: Map<String, Value>) -> Result<T, serde_json::Error> {
+    serde_json::from_value(Value::Object(action))
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D: Deserializer<'de>>(data: D) -> Result<Action, D::Error> {
+        Action::from_value(Value::deserialize(data)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Odoo {
+    /// Calls a button method (`action_confirm`, `button_validate`, ...) on `ids` the way the
+    /// web client does, and returns the follow-up action.
+    pub async fn call_button(
+        &self,
+        model: &str,
+        method: &str,
+        ids: &[u32],
+        context: Option<Map<String, Value>>,
+    ) -> Result<Action, Error> {
+        let mut kwargs = Map::new();
+        if let Some(context) = context {
+            kwargs.insert("context".to_string(), Value::Object(context));
+        }
+        Ok(self.call_kw(model, method, (ids,), kwargs).await?.result)
+    }
+
+    /// Like `call_button`, through the web client's `/web/dataset/call_button` endpoint,
+    /// which cleans up the returned action as the UI sees it; needs a session from
+    /// `web_login`.
+    pub async fn web_call_button(
+        &self,
+        model: &str,
+        method: &str,
+        ids: &[u32],
+        context: Option<Map<String, Value>>,
+    ) -> Result<Action, Error> {
+        let params = json!({
+            "model": model,
+            "method": method,
+            "args": [ids],
+            "kwargs": {"context": context.unwrap_or_default()},
+        });
+        self.call_controller("web/dataset/call_button", params)
+            .await
+    }
+
+    /// Runs an `ir.actions.server` (by id or external id) on `active_ids` of `active_model`.
+    pub async fn run_server_action(
+        &self,
+        action: impl Into<RecordRef>,
+        active_model: &str,
+        active_ids: &[u32],
+    ) -> Result<Action, Error> {
+        let id = self.resolve("ir.actions.server", &action.into()).await?;
+        let mut context = Map::new();
+        context.insert("active_model".to_string(), Value::from(active_model));
+        context.insert("active_ids".to_string(), Value::from(active_ids));
+        if let Some(active_id) = active_ids.first() {
+            context.insert("active_id".to_string(), Value::from(*active_id));
+        }
+        let mut kwargs = Map::new();
+        kwargs.insert("context".to_string(), Value::Object(context));
+        Ok(self
+            .call_kw("ir.actions.server", "run", ([id],), kwargs)
+            .await?
+            .result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::action::Action;
+
+    #[test]
+    fn test_action_from_value() {
+        let action = Action::from_value(json!({
+            "type": "ir.actions.act_window",
+            "res_model": "stock.backorder.confirmation",
+            "views": [[false, "form"]],
+            "target": "new",
+            "context": {"default_pick_ids": [[4, 7]]},
+        }))
+        .unwrap();
+        match action {
+            Action::Window(window) => {
+                assert_eq!(window.res_model, "stock.backorder.confirmation");
+                assert_eq!(window.target.as_deref(), Some("new"));
+                assert_eq!(window.res_id, None);
+            }
+            action => panic!("unexpected action {:?}", action),
+        }
+
+        assert_eq!(Action::from_value(json!(true)).unwrap(), Action::None);
+        let action = Action::from_value(json!({"type": "ir.actions.act_url", "url": "/web"}));
+        assert!(matches!(action, Ok(Action::Url(_))));
+        let action = Action::from_value(json!({"type": "ir.actions.act_window_close"}));
+        assert!(matches!(action, Ok(Action::Other(_))));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_run_server_action() {
+        use crate::error::Error;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        server.insert(
+            "ir.model.data",
+            json!({"module": "sale", "name": "action_mark_sent", "model": "ir.actions.server", "res_id": 5}),
+        );
+        server.insert(
+            "ir.model.data",
+            json!({"module": "base", "name": "main_partner", "model": "res.partner", "res_id": 1}),
+        );
+        server.on_call("ir.actions.server", "run", |args, kwargs| {
+            assert_eq!(args[0], json!([5]));
+            assert_eq!(
+                kwargs["context"],
+                json!({"active_model": "sale.order", "active_ids": [7, 8], "active_id": 7})
+            );
+            Ok(json!({"type": "ir.actions.act_url", "url": "/web"}))
+        });
+        let odoo = server.odoo().await.unwrap();
+
+        let action = odoo
+            .run_server_action("sale.action_mark_sent", "sale.order", &[7, 8])
+            .await
+            .unwrap();
+        assert!(matches!(action, Action::Url(_)));
+        assert!(odoo
+            .run_server_action(5, "sale.order", &[7, 8])
+            .await
+            .is_ok());
+
+        let error = odoo
+            .run_server_action("base.main_partner", "sale.order", &[7])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::NotFound(_)));
+        let error = odoo
+            .run_server_action("sale.missing", "sale.order", &[7])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::NotFound(_)));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_web_call_button() {
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+
+        let server = MockServer::start().await.unwrap();
+        server.on_call("sale.order", "action_view_invoice", |args, _| {
+            Ok(json!({
+                "type": "ir.actions.act_window",
+                "res_model": "account.move",
+                "res_id": args[0][0],
+            }))
+        });
+        let mut web = Odoo::new(server.url(), "mock");
+        web.web_login("admin", "admin").await.unwrap();
+        let action = web
+            .web_call_button("sale.order", "action_view_invoice", &[7], None)
+            .await
+            .unwrap();
+        assert!(matches!(action, Action::Window(window) if window.res_id == Some(7)));
+    }
+}
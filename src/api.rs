@@ -37,3 +37,47 @@ impl<T> Request<T> {
         }
     }
 }
+
+#[derive(Serialize, Debug)]
+pub struct WebRequest<T> {
+    jsonrpc: String,
+    method: String,
+    id: u32,
+    params: T,
+}
+
+impl<T> WebRequest<T> {
+    pub fn new(params: T) -> WebRequest<T> {
+        let mut rng = thread_rng();
+        WebRequest {
+            jsonrpc: String::from("2.0"),
+            method: String::from("call"),
+            params,
+            id: rng.gen_range(1..10000),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<RpcErrorData>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RpcErrorData {
+    pub name: String,
+    pub message: String,
+    #[serde(default)]
+    pub debug: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct Envelope<T> {
+    #[serde(default)]
+    pub id: Option<u32>,
+    pub result: Option<T>,
+    #[serde(default)]
+    pub error: Option<RpcError>,
+}
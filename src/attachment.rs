@@ -0,0 +1,155 @@
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::checksum::sha1_hex;
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+use crate::query::SearchReadOptions;
+
+const ATTACHMENT_FIELDS: &[&str] = &[
+    "name",
+    "mimetype",
+    "file_size",
+    "checksum",
+    "res_model",
+    "res_id",
+];
+
+/// Metadata of an `ir.attachment`, without its content.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    pub id: u32,
+    pub name: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub mimetype: Option<String>,
+    /// In bytes.
+    #[serde(rename = "file_size", default)]
+    pub size: u64,
+    /// SHA-1 of the content; `None` for URL attachments.
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub checksum: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub res_model: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub res_id: Option<u32>,
+}
+
+impl Odoo {
+    /// The attachments of a record, oldest first. Attachments holding binary fields
+    /// (`res_field` set) aren't listed, as in the chatter.
+    pub async fn list_attachments(&self, model: &str, id: u32) -> Result<Vec<Attachment>, Error> {
+        let domain = json!([["res_model", "=", model], ["res_id", "=", id]]);
+        let options = SearchReadOptions::new()
+            .fields(ATTACHMENT_FIELDS)
+            .order("id");
+        Ok(self
+            .search_read_with("ir.attachment", domain, options)
+            .await?
+            .result)
+    }
+
+    /// Fetches an attachment's content through `/web/content` and checks it against its
+    /// checksum; requires `web_login`. Use `download_to` for files that don't fit in memory.
+    pub async fn download_attachment(&self, attachment_id: u32) -> Result<Vec<u8>, Error> {
+        let route = format!("web/content/{}", attachment_id);
+        let call = self.begin_call().await?;
+        let resp = self
+            .execute(self.http(reqwest::Method::GET, &route))
+            .await?;
+        if !resp.status().is_success() {
+            return Err(Error::NotFound(format!(
+                "Download of ir.attachment({}) failed: {}",
+                attachment_id,
+                resp.status()
+            )));
+        }
+        let content = self.read_body(resp).await?;
+        drop(call);
+        self.verify_checksum(attachment_id, &sha1_hex(&content))
+            .await?;
+        Ok(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::attachment::Attachment;
+
+    #[test]
+    fn test_attachment() {
+        let attachment: Attachment = serde_json::from_value(json!({
+            "id": 7,
+            "name": "contract.pdf",
+            "mimetype": "application/pdf",
+            "file_size": 52311,
+            "checksum": "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            "res_model": "res.partner",
+            "res_id": 3,
+        }))
+        .unwrap();
+        assert_eq!(attachment.size, 52311);
+        assert_eq!(attachment.res_id, Some(3));
+
+        // URL attachments
+        let attachment: Attachment = serde_json::from_value(json!({
+            "id": 8,
+            "name": "Website",
+            "mimetype": false,
+            "file_size": 0,
+            "checksum": false,
+            "res_model": false,
+            "res_id": 0,
+        }))
+        .unwrap();
+        assert_eq!(attachment.checksum, None);
+        assert_eq!(attachment.res_id, Some(0));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_list_and_download_attachments() {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+
+        use crate::checksum::sha1_hex;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let partner = server.insert("res.partner", json!({"name": "Azure"}));
+        for (name, content) in [("contract.pdf", "%PDF-1.4"), ("logo.png", "\u{89}PNG")] {
+            server.insert(
+                "ir.attachment",
+                json!({
+                    "name": name,
+                    "mimetype": "application/octet-stream",
+                    "res_model": "res.partner",
+                    "res_id": partner,
+                    "datas": STANDARD.encode(content),
+                }),
+            );
+        }
+        server.insert(
+            "ir.attachment",
+            json!({"name": "other.txt", "res_model": "res.users", "res_id": partner}),
+        );
+        let mut odoo = server.odoo().await.unwrap();
+
+        let attachments = odoo.list_attachments("res.partner", partner).await.unwrap();
+        let names: Vec<&str> = attachments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["contract.pdf", "logo.png"]);
+        assert_eq!(attachments[0].size, 8);
+        assert_eq!(
+            attachments[0].checksum.as_deref(),
+            Some(sha1_hex(b"%PDF-1.4").as_str())
+        );
+
+        // /web/content needs the session cookie
+        assert!(odoo.download_attachment(attachments[0].id).await.is_err());
+        odoo.web_login("admin", "admin").await.unwrap();
+        let content = odoo.download_attachment(attachments[0].id).await.unwrap();
+        assert_eq!(content, b"%PDF-1.4");
+        assert!(odoo.download_attachment(999).await.is_err());
+    }
+}
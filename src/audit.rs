@@ -0,0 +1,197 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use serde_json::{json, Value};
+
+use crate::cron::format_utc;
+
+const REDACTED: &str = "***";
+
+/// An RPC call handed to the sink set with `Odoo::with_audit_sink`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEvent {
+    pub timestamp: SystemTime,
+    pub uid: Option<u32>,
+    pub model: String,
+    pub method: String,
+    /// Records the call acted on: the ids passed first, or the ids returned by `create`.
+    pub ids: Vec<u32>,
+    /// `{"args": [...], "kwargs": {...}}`, with redacted fields replaced by `***`.
+    pub payload: Value,
+    pub outcome: AuditOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditOutcome {
+    Success,
+    /// The call failed, with the error message.
+    Failure(String),
+}
+
+impl AuditEvent {
+    /// The event as a JSON object, e.g. to append to a JSON-lines trail.
+    pub fn to_json(&self) -> Value {
+        let (outcome, error) = match &self.outcome {
+            AuditOutcome::Success => ("success", None),
+            AuditOutcome::Failure(error) => ("failure", Some(error)),
+        };
+        json!({
+            "timestamp": format_utc(self.timestamp),
+            "uid": self.uid,
+            "model": self.model,
+            "method": self.method,
+            "ids": self.ids,
+            "payload": self.payload,
+            "outcome": outcome,
+            "error": error,
+        })
+    }
+}
+
+/// Receives an `AuditEvent` for every call; implemented for closures.
+pub trait AuditSink: Send + Sync {
+    fn record(&self, event: &AuditEvent);
+}
+
+impl<F: Fn(&AuditEvent) + Send + Sync> AuditSink for F {
+    fn record(&self, event: &AuditEvent) {
+        self(event)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct AuditHook {
+    pub(crate) sink: Arc<dyn AuditSink>,
+    pub(crate) redacted: Arc<Vec<String>>,
+}
+
+impl fmt::Debug for AuditHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuditHook")
+    }
+}
+
+/// Replaces the values of `fields` anywhere in `value`.
+pub(crate) fn redact(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(values) => {
+            for (field, value) in values.iter_mut() {
+                if fields.contains(field) {
+                    *value = Value::from(REDACTED);
+                } else {
+                    redact(value, fields);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(|value| redact(value, fields)),
+        _ => {}
+    }
+}
+
+/// The ids a call acted on: its first argument, or the result of `create`.
+pub(crate) fn touched_ids(method: &str, args: &Value, result: Option<&Value>) -> Vec<u32> {
+    let ids = match (method, result) {
+        ("create", Some(result)) => result,
+        _ => match args.get(0) {
+            Some(ids) => ids,
+            None => return vec![],
+        },
+    };
+    match ids {
+        Value::Array(ids) => ids
+            .iter()
+            .map(Value::as_u64)
+            .collect::<Option<Vec<u64>>>()
+            .unwrap_or_default(),
+        id => id.as_u64().into_iter().collect(),
+    }
+    .into_iter()
+    .map(|id| id as u32)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::audit::{redact, touched_ids};
+
+    #[test]
+    fn test_redact() {
+        let mut payload = json!({
+            "args": [[7], {"name": "Joel", "password": "secret", "bank_ids": [[0, 0, {"acc_number": "ES12"}]]}],
+            "kwargs": {},
+        });
+        redact(
+            &mut payload,
+            &["password".to_string(), "acc_number".to_string()],
+        );
+        assert_eq!(
+            payload["args"][1],
+            json!({"name": "Joel", "password": "***", "bank_ids": [[0, 0, {"acc_number": "***"}]]})
+        );
+    }
+
+    #[test]
+    fn test_touched_ids() {
+        assert_eq!(touched_ids("write", &json!([[3, 4], {}]), None), vec![3, 4]);
+        assert_eq!(
+            touched_ids("create", &json!([{}]), Some(&json!(9))),
+            vec![9]
+        );
+        assert!(touched_ids("search", &json!([[["id", ">", 2]]]), None).is_empty());
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_audit_sink() {
+        use std::sync::{Arc, Mutex};
+
+        use serde_json::Value;
+
+        use crate::audit::{AuditEvent, AuditOutcome};
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let events: Arc<Mutex<Vec<AuditEvent>>> = Arc::default();
+        let sink = events.clone();
+        let odoo = server.odoo().await.unwrap().with_audit_sink(
+            move |event: &AuditEvent| sink.lock().unwrap().push(event.clone()),
+            &["password"],
+        );
+
+        let id: u32 = odoo
+            .call(
+                "res.users",
+                "create",
+                (json!({"login": "joel", "password": "secret"}),),
+            )
+            .await
+            .unwrap()
+            .result;
+        let _: bool = odoo
+            .call("res.users", "write", ([id], json!({"name": "Joel"})))
+            .await
+            .unwrap()
+            .result;
+        server.on_call("res.users", "action_reset_password", |_, _| {
+            Err("Access Denied".to_string())
+        });
+        assert!(odoo
+            .call::<_, Value>("res.users", "action_reset_password", ([id],))
+            .await
+            .is_err());
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].ids, vec![id]);
+        assert_eq!(events[0].payload["args"][0]["password"], "***");
+        assert_eq!(events[0].payload["args"][0]["login"], "joel");
+        assert_eq!(events[1].method, "write");
+        assert_eq!(events[1].ids, vec![id]);
+        assert_eq!(events[1].outcome, AuditOutcome::Success);
+        assert!(matches!(events[2].outcome, AuditOutcome::Failure(_)));
+        assert_eq!(events[2].to_json()["outcome"], "failure");
+    }
+}
@@ -0,0 +1,286 @@
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::{Map, Value};
+
+use async_odoors::domain::parse_literal;
+use async_odoors::error::Error;
+use async_odoors::odoo::Odoo;
+
+#[cfg(feature = "repl")]
+#[path = "odoors/repl.rs"]
+mod repl;
+
+/// Query an Odoo instance over JSON-RPC.
+#[derive(Parser)]
+#[command(name = "odoors", version)]
+struct Cli {
+    /// Server URL, e.g. https://erp.example.com
+    #[arg(short, long, env = "ODOO_URL")]
+    url: String,
+    #[arg(long, env = "ODOO_DB")]
+    db: String,
+    #[arg(long, env = "ODOO_USER")]
+    user: String,
+    #[arg(long, env = "ODOO_PASSWORD", hide_env_values = true)]
+    password: String,
+    #[arg(short, long, value_enum, default_value_t = Output::Json)]
+    output: Output,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Output {
+    Json,
+    Table,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Read the records matching a domain
+    Search {
+        model: String,
+        /// Domain, Python or JSON syntax: '[("id", ">", 2)]'
+        #[arg(short, long, default_value = "[]")]
+        domain: String,
+        #[arg(short, long = "field", value_delimiter = ',')]
+        fields: Vec<String>,
+        #[arg(long)]
+        limit: Option<u32>,
+        #[arg(long)]
+        offset: Option<u32>,
+        #[arg(long)]
+        order: Option<String>,
+    },
+    /// Read records by id
+    Read {
+        model: String,
+        #[arg(required = true)]
+        ids: Vec<u32>,
+        #[arg(short, long = "field", value_delimiter = ',')]
+        fields: Vec<String>,
+    },
+    /// Create a record and print its id
+    Create {
+        model: String,
+        /// Values, Python or JSON syntax: '{"name": "Test"}'
+        values: String,
+    },
+    /// Write values on records
+    Write {
+        model: String,
+        #[arg(required = true, value_delimiter = ',')]
+        ids: Vec<u32>,
+        #[arg(long)]
+        values: String,
+    },
+    /// Call any model method
+    Call {
+        model: String,
+        method: String,
+        /// Positional arguments as a list
+        #[arg(default_value = "[]")]
+        args: String,
+        /// Keyword arguments as a dict
+        #[arg(long, default_value = "{}")]
+        kwargs: String,
+    },
+    /// Start an interactive shell on the same session
+    #[cfg(feature = "repl")]
+    Shell,
+}
+
+impl Command {
+    #[cfg(feature = "repl")]
+    fn model(&self) -> Option<&str> {
+        match self {
+            Command::Search { model, .. }
+            | Command::Read { model, .. }
+            | Command::Create { model, .. }
+            | Command::Write { model, .. }
+            | Command::Call { model, .. } => Some(model),
+            Command::Shell => None,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let odoo = match Odoo::new_and_login(&cli.url, &cli.db, &cli.user, &cli.password).await {
+        Ok(odoo) => odoo,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    #[cfg(feature = "repl")]
+    if let Command::Shell = cli.command {
+        return match repl::run(&odoo, cli.output).await {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    match run(&odoo, cli.command).await {
+        Ok(value) => {
+            print(&value, cli.output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print(value: &Value, output: Output) {
+    match output {
+        Output::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(value).unwrap_or_default()
+        ),
+        Output::Table => print_table(value),
+    }
+}
+
+async fn run(odoo: &Odoo, command: Command) -> Result<Value, Error> {
+    match command {
+        Command::Search {
+            model,
+            domain,
+            fields,
+            limit,
+            offset,
+            order,
+        } => {
+            let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+            let mut query = odoo
+                .query(&model)
+                .domain(parse_literal(&domain)?)
+                .fields(&fields);
+            if let Some(limit) = limit {
+                query = query.limit(limit);
+            }
+            if let Some(offset) = offset {
+                query = query.offset(offset);
+            }
+            if let Some(order) = order {
+                query = query.order(&order);
+            }
+            Ok(Value::Array(query.all().await?))
+        }
+        Command::Read { model, ids, fields } => {
+            Ok(odoo.call(&model, "read", (ids, fields)).await?.result)
+        }
+        Command::Create { model, values } => Ok(odoo
+            .call(&model, "create", vec![parse_literal(&values)?])
+            .await?
+            .result),
+        Command::Write { model, ids, values } => Ok(odoo
+            .call(&model, "write", (ids, parse_literal(&values)?))
+            .await?
+            .result),
+        Command::Call {
+            model,
+            method,
+            args,
+            kwargs,
+        } => {
+            let kwargs = match parse_literal(&kwargs)? {
+                Value::Object(kwargs) => kwargs,
+                _ => return Err(Error::Request(String::from("kwargs must be a dict"))),
+            };
+            Ok(odoo
+                .call_kw(&model, &method, parse_literal(&args)?, kwargs)
+                .await?
+                .result)
+        }
+        #[cfg(feature = "repl")]
+        Command::Shell => Err(Error::Request(String::from("already in a shell"))),
+    }
+}
+
+fn print_table(value: &Value) {
+    let records: Vec<&Map<String, Value>> = match value {
+        Value::Array(items) => items.iter().filter_map(Value::as_object).collect(),
+        Value::Object(record) => vec![record],
+        _ => vec![],
+    };
+    if records.is_empty() {
+        println!("{}", cell(value));
+        return;
+    }
+
+    let mut columns: Vec<&String> = vec![];
+    for record in &records {
+        for key in record.keys() {
+            if !columns.contains(&key) {
+                columns.push(key);
+            }
+        }
+    }
+    if let Some(position) = columns.iter().position(|column| *column == "id") {
+        let id = columns.remove(position);
+        columns.insert(0, id);
+    }
+
+    let rows: Vec<Vec<String>> = records
+        .iter()
+        .map(|record| {
+            columns
+                .iter()
+                .map(|column| record.get(*column).map(cell).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([column.chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let line = |cells: Vec<&str>| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    };
+    line(columns.iter().map(|column| column.as_str()).collect());
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for row in &rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+// many2one pairs show their name, `false` shows empty
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::Bool(false) | Value::Null => String::new(),
+        Value::Array(pair) if pair.len() == 2 && pair[0].is_u64() && pair[1].is_string() => {
+            pair[1].as_str().unwrap_or_default().to_string()
+        }
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use serde_json::Value;
+
+use async_odoors::error::Error;
+use async_odoors::odoo::Odoo;
+
+use crate::{print, Command, Output};
+
+const BUILTINS: &[&str] = &["fields", "output", "exit", "quit", "help"];
+const COMMANDS: &[&str] = &["search", "read", "create", "write", "call"];
+
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct Line {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Completes commands, model names and the fields of models used so far.
+#[derive(Default)]
+struct ShellHelper {
+    models: Vec<String>,
+    fields: HashMap<String, Vec<String>>,
+}
+
+impl ShellHelper {
+    async fn load_fields(&mut self, odoo: &Odoo, model: &str) -> Result<(), Error> {
+        if !self.fields.contains_key(model) {
+            let mut fields: Vec<String> = odoo.fields_get(model).await?.keys().cloned().collect();
+            fields.sort();
+            self.fields.insert(model.to_string(), fields);
+        }
+        Ok(())
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .rfind(|c: char| c.is_whitespace() || "[](),'\"".contains(c))
+            .map_or(0, |i| i + 1);
+        let word = &line[start..pos];
+        let words = split_line(&line[..start]);
+        let candidates: Vec<&String> = match words.len() {
+            0 => {
+                return Ok((
+                    start,
+                    COMMANDS
+                        .iter()
+                        .chain(BUILTINS)
+                        .filter(|c| c.starts_with(word))
+                        .map(|c| c.to_string())
+                        .collect(),
+                ))
+            }
+            1 => self.models.iter().collect(),
+            _ => self
+                .fields
+                .get(&words[1])
+                .map(|fields| fields.iter().collect())
+                .unwrap_or_default(),
+        };
+        Ok((
+            start,
+            candidates
+                .into_iter()
+                .filter(|c| c.starts_with(word))
+                .cloned()
+                .collect(),
+        ))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// Runs the interactive shell until `exit` or end of input.
+pub async fn run(odoo: &Odoo, output: Output) -> Result<(), Error> {
+    let mut output = output;
+    let mut helper = ShellHelper::default();
+    let models: Vec<Value> = odoo.query("ir.model").fields(&["model"]).all().await?;
+    helper.models = models
+        .iter()
+        .filter_map(|model| model.get("model").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect();
+    helper.models.sort();
+
+    let mut editor = Editor::<ShellHelper>::new().map_err(|e| Error::Request(e.to_string()))?;
+    editor.set_helper(Some(helper));
+    let history = history_path();
+    if let Some(history) = &history {
+        let _ = editor.load_history(history);
+    }
+
+    loop {
+        let line = match editor.readline("odoo> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(Error::Request(e.to_string())),
+        };
+        let words = split_line(&line);
+        if words.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line.as_str());
+
+        match words[0].as_str() {
+            "exit" | "quit" => break,
+            "fields" => {
+                for model in &words[1..] {
+                    match odoo.fields_get(model).await {
+                        Ok(fields) => {
+                            let mut names: Vec<&String> = fields.keys().collect();
+                            names.sort();
+                            for name in names {
+                                println!("{:32} {}", name, fields[name].field_type);
+                            }
+                        }
+                        Err(e) => eprintln!("error: {}", e),
+                    }
+                }
+            }
+            "output" => match words.get(1).map(|o| Output::from_str(o, true)) {
+                Some(Ok(format)) => output = format,
+                _ => eprintln!("usage: output json|table"),
+            },
+            _ => match Line::try_parse_from(&words) {
+                Ok(Line { command }) => {
+                    if let (Some(model), Some(helper)) = (command.model(), editor.helper_mut()) {
+                        let _ = helper.load_fields(odoo, model).await;
+                    }
+                    match super::run(odoo, command).await {
+                        Ok(value) => print(&value, output),
+                        Err(e) => eprintln!("error: {}", e),
+                    }
+                }
+                Err(e) => eprintln!("{}", e),
+            },
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
+    }
+    Ok(())
+}
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".odoors_history"))
+}
+
+/// Splits a line on whitespace, keeping bracketed literals in one word and stripping top-level
+/// quotes, so `search res.partner -d [('name', 'ilike', 'a')]` needs no shell quoting.
+fn split_line(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => {
+                quote = None;
+                if depth > 0 {
+                    word.push(c);
+                }
+            }
+            Some(_) => word.push(c),
+            None => match c {
+                '\'' | '"' => {
+                    quote = Some(c);
+                    in_word = true;
+                    if depth > 0 {
+                        word.push(c);
+                    }
+                }
+                '[' | '(' | '{' => {
+                    depth += 1;
+                    in_word = true;
+                    word.push(c);
+                }
+                ']' | ')' | '}' => {
+                    depth = depth.saturating_sub(1);
+                    word.push(c);
+                }
+                c if c.is_whitespace() && depth == 0 => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                c => {
+                    in_word = true;
+                    word.push(c);
+                }
+            },
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_line;
+
+    #[test]
+    fn test_split_line() {
+        assert_eq!(
+            split_line(r#"search res.partner -d [("name", "ilike", "a b")] -f name,email"#),
+            vec![
+                "search",
+                "res.partner",
+                "-d",
+                r#"[("name", "ilike", "a b")]"#,
+                "-f",
+                "name,email",
+            ]
+        );
+        assert_eq!(
+            split_line(r#"create res.partner '{"name": "x"}'"#),
+            vec!["create", "res.partner", r#"{"name": "x"}"#]
+        );
+        assert_eq!(split_line("  "), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::dns::Resolve;
+use reqwest::redirect::Policy;
+use reqwest::ClientBuilder;
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// How HTTP redirects (e.g. `http` to `https`, or to a new hostname) are handled. A request
+/// body, which carries the credentials of RPC calls, is never re-sent to another origin or
+/// over plain `http`; such redirects fail with an error naming the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Don't follow redirects; calls fail with an error naming the target.
+    None,
+    /// Follow up to `n` redirects like browsers do: `301`/`302`/`303` turn the JSON-RPC POST
+    /// into a GET, which Odoo rejects.
+    Limited(usize),
+    /// Follow up to `n` redirects, re-sending the same request to the new location. The
+    /// session cookie is dropped when a request without body leaves the origin.
+    FollowSameOrigin(usize),
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> RedirectPolicy {
+        RedirectPolicy::Limited(10)
+    }
+}
+
+/// HTTP version used to talk to the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HttpVersion {
+    /// HTTP/1.1, or HTTP/2 when negotiated over TLS (requires the `http2` feature).
+    #[default]
+    Auto,
+    Http1Only,
+    /// HTTP/2 without negotiation, e.g. cleartext `h2c` to an internal endpoint.
+    Http2PriorKnowledge,
+}
+
+/// Builder for an `Odoo` client with custom transport settings.
+///
+/// ```ignore
+/// let odoo = Odoo::builder("https://erp.example.com", "prod")
+///     .redirect_policy(RedirectPolicy::FollowSameOrigin(5))
+///     .resolve("erp.example.com", "10.0.0.12:443".parse()?)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct OdooBuilder {
+    host: String,
+    database: String,
+    redirect_policy: RedirectPolicy,
+    resolve: HashMap<String, Vec<SocketAddr>>,
+    dns_resolver: Option<ClientOption>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    tcp_keepalive: Option<Duration>,
+    http_version: HttpVersion,
+    http2_keep_alive_interval: Option<Duration>,
+    #[cfg(all(unix, feature = "unix-socket"))]
+    unix_socket: Option<std::path::PathBuf>,
+}
+
+/// A setting applied to the reqwest client builder, for options that aren't `Debug`/`Clone`.
+#[derive(Clone)]
+struct ClientOption(Arc<dyn Fn(ClientBuilder) -> ClientBuilder + Send + Sync>);
+
+impl fmt::Debug for ClientOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ClientOption")
+    }
+}
+
+impl Odoo {
+    pub fn builder(host: &str, database: &str) -> OdooBuilder {
+        OdooBuilder {
+            host: host.to_string(),
+            database: database.to_string(),
+            redirect_policy: RedirectPolicy::default(),
+            resolve: HashMap::new(),
+            dns_resolver: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            tcp_keepalive: None,
+            http_version: HttpVersion::default(),
+            http2_keep_alive_interval: None,
+            #[cfg(all(unix, feature = "unix-socket"))]
+            unix_socket: None,
+        }
+    }
+}
+
+impl OdooBuilder {
+    pub fn redirect_policy(mut self, redirect_policy: RedirectPolicy) -> OdooBuilder {
+        self.redirect_policy = redirect_policy;
+        self
+    }
+
+    /// Connects to `addr` whenever `domain` is requested, bypassing DNS (split-horizon DNS,
+    /// pinning a node behind a load balancer). Can be called several times per domain.
+    /// The port of `addr` is ignored in favour of the one in the URL.
+    pub fn resolve(mut self, domain: &str, addr: SocketAddr) -> OdooBuilder {
+        self.resolve
+            .entry(domain.to_string())
+            .or_default()
+            .push(addr);
+        self
+    }
+
+    /// Resolves hostnames with a custom resolver; overrides from `resolve` still apply first.
+    pub fn dns_resolver<R: Resolve + 'static>(mut self, resolver: Arc<R>) -> OdooBuilder {
+        self.dns_resolver = Some(ClientOption(Arc::new(move |client: ClientBuilder| {
+            client.dns_resolver(resolver.clone())
+        })));
+        self
+    }
+
+    /// Idle connections kept per host; raise for high-throughput syncs, lower (or 0) for
+    /// mostly-idle daemons.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> OdooBuilder {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// How long idle connections are kept (90s by default); `None` keeps them forever.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> OdooBuilder {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables TCP keepalive probes at `interval`, so load balancers don't drop idle
+    /// connections silently.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> OdooBuilder {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// With HTTP/2, concurrent calls on clones of the client are multiplexed over one
+    /// connection instead of opening one connection each.
+    pub fn http_version(mut self, http_version: HttpVersion) -> OdooBuilder {
+        self.http_version = http_version;
+        self
+    }
+
+    /// Sends HTTP/2 pings at `interval` to keep the multiplexed connection alive.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> OdooBuilder {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Connects through a Unix socket (sidecar deployments, local proxy) instead of TCP. The
+    /// host URL is still used for the `Host` header and paths.
+    #[cfg(all(unix, feature = "unix-socket"))]
+    pub fn unix_socket(mut self, path: impl Into<std::path::PathBuf>) -> OdooBuilder {
+        self.unix_socket = Some(path.into());
+        self
+    }
+
+    pub fn build(self) -> Result<Odoo, Error> {
+        let client = self.client()?;
+        let odoo =
+            Odoo::try_new(&self.host, &self.database)?.with_client(client, self.redirect_policy);
+        #[cfg(all(unix, feature = "unix-socket"))]
+        let odoo = odoo.with_unix_socket(self.unix_socket.clone());
+        Ok(odoo.with_builder(self))
+    }
+
+    pub(crate) fn client(&self) -> Result<reqwest::Client, Error> {
+        // redirects are followed by `Odoo` itself
+        let mut client = reqwest::Client::builder().redirect(Policy::none());
+        for (domain, addrs) in &self.resolve {
+            client = client.resolve_to_addrs(domain, addrs);
+        }
+        if let Some(ClientOption(dns_resolver)) = &self.dns_resolver {
+            client = dns_resolver(client);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            client = client.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            client = client.tcp_keepalive(interval);
+        }
+        client = match self.http_version {
+            HttpVersion::Auto => client,
+            HttpVersion::Http1Only => client.http1_only(),
+            HttpVersion::Http2PriorKnowledge => client.http2_prior_knowledge(),
+        };
+        if let Some(interval) = self.http2_keep_alive_interval {
+            client = client
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(true);
+        }
+        client.build().map_err(|e| Error::Request(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_redirect_policy() {
+        use std::convert::Infallible;
+        use std::net::SocketAddr;
+        use std::sync::{Arc, Mutex};
+
+        use hyper::header::{HOST, LOCATION};
+        use hyper::service::{make_service_fn, service_fn};
+        use hyper::{Body, Request, Response, Server, StatusCode};
+
+        use crate::builder::RedirectPolicy;
+        use crate::odoo::Odoo;
+
+        // `/jsonrpc` answers, `/moved` and `/loop` redirect on the same origin and `/away`
+        // to another one (`localhost` instead of `127.0.0.1`)
+        let seen: Arc<Mutex<Vec<String>>> = Arc::default();
+        let log = seen.clone();
+        let make_service = make_service_fn(move |_| {
+            let log = log.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let host = req.headers()[HOST].to_str().unwrap().to_string();
+                    let port = host.rsplit(':').next().unwrap().to_string();
+                    let path = req.uri().path().to_string();
+                    log.lock()
+                        .unwrap()
+                        .push(format!("{} {}{}", req.method(), host, path));
+                    let redirect = |status, location: String| {
+                        Response::builder()
+                            .status(status)
+                            .header(LOCATION, location)
+                            .body(Body::empty())
+                    };
+                    let response = match path.as_str() {
+                        "/moved/jsonrpc" => {
+                            redirect(StatusCode::PERMANENT_REDIRECT, "/jsonrpc".into())
+                        }
+                        "/found/jsonrpc" => redirect(StatusCode::FOUND, "/jsonrpc".into()),
+                        "/loop/jsonrpc" => redirect(StatusCode::TEMPORARY_REDIRECT, path.clone()),
+                        "/away/jsonrpc" => redirect(
+                            StatusCode::TEMPORARY_REDIRECT,
+                            format!("http://localhost:{}/jsonrpc", port),
+                        ),
+                        _ => Response::builder()
+                            .header("Content-Type", "application/json")
+                            .body(Body::from(
+                                r#"{"jsonrpc": "2.0", "id": 1, "result": "16.0"}"#,
+                            )),
+                    };
+                    async move { Ok::<_, Infallible>(response.unwrap()) }
+                }))
+            }
+        });
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+
+        let odoo = |prefix: &str, policy| {
+            Odoo::builder(&format!("{}/{}", url, prefix), "demo")
+                .redirect_policy(policy)
+                .build()
+                .unwrap()
+        };
+        let take = || std::mem::take(&mut *seen.lock().unwrap());
+        let follow = RedirectPolicy::FollowSameOrigin(2);
+
+        let result = odoo("moved", follow)
+            .call_service("common", "version", ())
+            .await;
+        assert_eq!(result.unwrap(), "16.0");
+        assert_eq!(take().len(), 2);
+
+        // POST turned into GET
+        let result = odoo("found", RedirectPolicy::default())
+            .call_service("common", "version", ())
+            .await;
+        assert!(result.is_ok());
+        assert!(take()[1].starts_with("GET "));
+        let result = odoo("found", follow)
+            .call_service("common", "version", ())
+            .await;
+        assert!(result.is_ok());
+        assert!(take()[1].starts_with("POST "));
+
+        // one count for all the redirects of a request
+        let result = odoo("loop", follow)
+            .call_service("common", "version", ())
+            .await;
+        assert!(result.unwrap_err().to_string().contains("redirected (307"));
+        assert_eq!(take().len(), 3);
+
+        // the body with the credentials stays on the origin
+        for policy in [follow, RedirectPolicy::default()] {
+            let result = odoo("away", policy)
+                .call_service("common", "version", ())
+                .await;
+            assert!(result.unwrap_err().to_string().contains("Refused"));
+            assert_eq!(take().len(), 1);
+        }
+        let result = odoo("moved", RedirectPolicy::None)
+            .call_service("common", "version", ())
+            .await;
+        assert!(result.is_err());
+        assert_eq!(take().len(), 1);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_resolve_override() {
+        use std::net::SocketAddr;
+
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+
+        let server = MockServer::start().await.unwrap();
+        let port = server.url().rsplit(':').next().unwrap();
+        let mut odoo = Odoo::builder(&format!("http://odoo.invalid:{}", port), "mock")
+            .resolve("odoo.invalid", SocketAddr::from(([127, 0, 0, 1], 0)))
+            .build()
+            .unwrap();
+        assert_eq!(odoo.login("admin", "admin").await.unwrap(), 2);
+    }
+
+    #[cfg(all(unix, feature = "unix-socket", feature = "mock"))]
+    #[tokio::test]
+    async fn test_unix_socket() {
+        use tokio::net::{TcpStream, UnixListener};
+
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+
+        // forward a Unix socket to the mock server
+        let server = MockServer::start().await.unwrap();
+        let addr = server.url().trim_start_matches("http://").to_string();
+        let path = std::env::temp_dir().join(format!("odoors-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut unix, _)) = listener.accept().await {
+                let mut tcp = TcpStream::connect(&addr).await.unwrap();
+                tokio::spawn(async move {
+                    let _ = tokio::io::copy_bidirectional(&mut unix, &mut tcp).await;
+                });
+            }
+        });
+
+        let mut odoo = Odoo::builder("http://odoo.invalid", "mock")
+            .unix_socket(&path)
+            .build()
+            .unwrap();
+        assert_eq!(odoo.login("admin", "admin").await.unwrap(), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_transport_options() {
+        use std::time::Duration;
+
+        use crate::builder::HttpVersion;
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+
+        let server = MockServer::start().await.unwrap();
+        for (http_version, expected) in [
+            (HttpVersion::Http1Only, http::Version::HTTP_11),
+            (HttpVersion::Http2PriorKnowledge, http::Version::HTTP_2),
+        ] {
+            let mut odoo = Odoo::builder(server.url(), "mock")
+                .pool_max_idle_per_host(1)
+                .pool_idle_timeout(Some(Duration::from_secs(5)))
+                .tcp_keepalive(Duration::from_secs(30))
+                .http_version(http_version)
+                .http2_keep_alive_interval(Duration::from_secs(10))
+                .build()
+                .unwrap();
+            assert_eq!(odoo.login("admin", "admin").await.unwrap(), 2);
+            let resp = odoo
+                .execute(odoo.http(reqwest::Method::GET, "web/login"))
+                .await
+                .unwrap();
+            assert_eq!(resp.version(), expected);
+        }
+    }
+}
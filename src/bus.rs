@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// A message sent on the bus (`bus.bus._sendone`).
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct BusNotification {
+    pub id: u64,
+    pub channel: Value,
+    pub message: Value,
+}
+
+impl BusNotification {
+    /// Whether `text` appears anywhere in the message, e.g. a job uuid or a record reference.
+    pub fn mentions(&self, text: &str) -> bool {
+        fn search(value: &Value, text: &str) -> bool {
+            match value {
+                Value::String(value) => value == text,
+                Value::Array(values) => values.iter().any(|value| search(value, text)),
+                Value::Object(values) => values.values().any(|value| search(value, text)),
+                _ => false,
+            }
+        }
+        search(&self.message, text)
+    }
+}
+
+impl Odoo {
+    /// Long-polls the bus for notifications on `channels` after notification `last`, through
+    /// the `/longpolling/poll` route of Odoo 15 and older (Odoo 16+ only serves the bus over
+    /// websockets). The server answers as soon as there are notifications, or with none
+    /// after about 50 seconds, or fails at once when the client shuts down.
+    pub async fn bus_poll(
+        &self,
+        channels: &[&str],
+        last: u64,
+    ) -> Result<Vec<BusNotification>, Error> {
+        let poll = self.call_controller(
+            "longpolling/poll",
+            json!({"channels": channels, "last": last, "options": {}}),
+        );
+        tokio::select! {
+            notifications = poll => notifications,
+            _ = self.lifecycle.closed() => Err(Error::Request(String::from("Client is shut down"))),
+        }
+    }
+
+    /// Waits up to `timeout` for a notification on `channels` matching `predicate`, starting
+    /// after notification `last` (0 includes the ones the server still holds).
+    pub async fn wait_notification<F>(
+        &self,
+        channels: &[&str],
+        mut last: u64,
+        timeout: Duration,
+        predicate: F,
+    ) -> Result<BusNotification, Error>
+    where
+        F: Fn(&BusNotification) -> bool,
+    {
+        let wait = async {
+            loop {
+                for notification in self.bus_poll(channels, last).await? {
+                    last = last.max(notification.id);
+                    if predicate(&notification) {
+                        return Ok(notification);
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| Error::DeadlineExceeded)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::bus::BusNotification;
+
+    #[test]
+    fn test_mentions() {
+        let notification = BusNotification {
+            id: 12,
+            channel: json!("jobs"),
+            message: json!({"type": "job_done", "payload": {"uuid": "a1", "ids": [3]}}),
+        };
+        assert!(notification.mentions("a1"));
+        assert!(!notification.mentions("b2"));
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+type Records = HashMap<(String, u32), Map<String, Value>>;
+
+/// Identity map of records keyed by `(model, id)`, returned by `Odoo::record_cache`.
+///
+/// Repeated reads of the same records are served from memory, only fetching what is
+/// missing. Nothing expires: keep a cache for the duration of a task (e.g. walking a
+/// graph of records) and `invalidate` what the task changes. Clones share the records.
+#[derive(Debug, Clone)]
+pub struct RecordCache {
+    odoo: Odoo,
+    records: Arc<Mutex<Records>>,
+}
+
+impl Odoo {
+    pub fn record_cache(&self) -> RecordCache {
+        RecordCache {
+            odoo: self.clone(),
+            records: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl RecordCache {
+    /// Reads `fields` of the records `ids`, in the order of `ids`, fetching only the records
+    /// not cached with all of these fields yet. Missing records are left out.
+    ///
+    /// With no `fields`, records are always fetched with all their fields.
+    pub async fn read<U: DeserializeOwned>(
+        &self,
+        model: &str,
+        ids: &[u32],
+        fields: &[&str],
+    ) -> Result<Vec<U>, Error> {
+        let missing: Vec<u32> = {
+            let records = self.records.lock().unwrap();
+            ids.iter()
+                .copied()
+                .filter(|id| {
+                    fields.is_empty()
+                        || records
+                            .get(&(model.to_string(), *id))
+                            .is_none_or(|record| fields.iter().any(|f| !record.contains_key(*f)))
+                })
+                .collect()
+        };
+        if !missing.is_empty() {
+            let fetched: Vec<Map<String, Value>> = self.odoo.read(model, &missing, fields).await?;
+            let mut records = self.records.lock().unwrap();
+            for record in fetched {
+                let id = record.get("id").and_then(Value::as_u64).unwrap_or_default() as u32;
+                records
+                    .entry((model.to_string(), id))
+                    .or_default()
+                    .extend(record);
+            }
+        }
+
+        let records = self.records.lock().unwrap();
+        ids.iter()
+            .filter_map(|id| records.get(&(model.to_string(), *id)))
+            .map(|record| {
+                let mut values = Map::new();
+                values.insert("id".to_string(), record["id"].clone());
+                for (field, value) in record {
+                    if fields.is_empty() || fields.contains(&field.as_str()) {
+                        values.insert(field.clone(), value.clone());
+                    }
+                }
+                self.odoo.decode_value(Value::Object(values))
+            })
+            .collect()
+    }
+
+    /// Reads a single record, failing with `Error::NotFound` if it doesn't exist.
+    pub async fn get<U: DeserializeOwned>(
+        &self,
+        model: &str,
+        id: u32,
+        fields: &[&str],
+    ) -> Result<U, Error> {
+        self.read(model, &[id], fields)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("{}({}) does not exist", model, id)))
+    }
+
+    /// Drops the cached `ids` of `model`, to be read again from the server.
+    pub fn invalidate(&self, model: &str, ids: &[u32]) {
+        let mut records = self.records.lock().unwrap();
+        for id in ids {
+            records.remove(&(model.to_string(), *id));
+        }
+    }
+
+    pub fn invalidate_model(&self, model: &str) {
+        self.records
+            .lock()
+            .unwrap()
+            .retain(|(cached, _), _| cached != model);
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().unwrap().clear();
+    }
+
+    /// Number of cached records.
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The cached values of a record, without reading it.
+    pub fn peek(&self, model: &str, id: u32) -> Option<Value> {
+        self.records
+            .lock()
+            .unwrap()
+            .get(&(model.to_string(), id))
+            .map(|record| json!(record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_record_cache() {
+        use serde_json::{json, Value};
+
+        use crate::error::Error;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let azure = server.insert(
+            "res.partner",
+            json!({"name": "Azure", "email": "azure@example.com"}),
+        );
+        let deco = server.insert("res.partner", json!({"name": "Deco"}));
+
+        let cache = odoo.record_cache();
+        let partners: Vec<Value> = cache
+            .read("res.partner", &[deco, azure], &["name"])
+            .await
+            .unwrap();
+        assert_eq!(partners[0], json!({"id": deco, "name": "Deco"}));
+        assert_eq!(cache.len(), 2);
+
+        // served from the cache until invalidated
+        let _: bool = odoo
+            .call(
+                "res.partner",
+                "write",
+                ([azure], json!({"name": "Azure Interior"})),
+            )
+            .await
+            .unwrap()
+            .result;
+        let partner: Value = cache.get("res.partner", azure, &["name"]).await.unwrap();
+        assert_eq!(partner["name"], "Azure");
+
+        // fields not cached yet are fetched and merged
+        let partner: Value = cache
+            .get("res.partner", azure, &["name", "email"])
+            .await
+            .unwrap();
+        assert_eq!(partner["email"], "azure@example.com");
+        assert_eq!(partner["name"], "Azure Interior");
+
+        cache.invalidate("res.partner", &[azure]);
+        assert!(cache.peek("res.partner", azure).is_none());
+        cache.invalidate_model("res.partner");
+        assert!(cache.is_empty());
+        assert!(matches!(
+            cache.get::<Value>("res.partner", 99, &["name"]).await,
+            Err(Error::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use serde::Deserialize;
+use sha1_smol::Sha1;
+use tokio::io::AsyncReadExt;
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+/// SHA-1 hex digest of `data`.
+pub fn sha1_hex(data: &[u8]) -> String {
+    Sha1::from(data).digest().to_string()
+}
+
+/// SHA-1 hex digest of the file at `path`, read in chunks; compare it with
+/// `Odoo::attachment_checksum` to skip files the server already has.
+pub async fn file_sha1(path: impl AsRef<Path>) -> Result<String, Error> {
+    let path = path.as_ref();
+    let read_error =
+        |e: std::io::Error| Error::Request(format!("Can't read {}: {}", path.display(), e));
+    let mut file = tokio::fs::File::open(path).await.map_err(read_error)?;
+    let mut hasher = Sha1::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await.map_err(read_error)?;
+        if read == 0 {
+            return Ok(hasher.digest().to_string());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+#[derive(Deserialize)]
+struct Checksum {
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    checksum: Option<String>,
+}
+
+impl Odoo {
+    /// The SHA-1 Odoo computed for an attachment's content; `None` for URL attachments.
+    pub async fn attachment_checksum(&self, attachment_id: u32) -> Result<Option<String>, Error> {
+        let records: Vec<Checksum> = self
+            .call(
+                "ir.attachment",
+                "read",
+                (vec![attachment_id], vec!["checksum"]),
+            )
+            .await?
+            .result;
+        match records.into_iter().next() {
+            Some(record) => Ok(record.checksum),
+            None => Err(Error::NotFound(format!("ir.attachment({})", attachment_id))),
+        }
+    }
+
+    /// Checks a local copy of an attachment, e.g. after `download_to`, against its checksum
+    /// on the server and returns the digest; fails with `Error::ChecksumMismatch`.
+    pub async fn verify_attachment(
+        &self,
+        attachment_id: u32,
+        path: impl AsRef<Path>,
+    ) -> Result<String, Error> {
+        let digest = file_sha1(path).await?;
+        self.verify_checksum(attachment_id, &digest).await?;
+        Ok(digest)
+    }
+
+    pub(crate) async fn verify_checksum(
+        &self,
+        attachment_id: u32,
+        digest: &str,
+    ) -> Result<(), Error> {
+        match self.attachment_checksum(attachment_id).await? {
+            Some(checksum) if checksum != digest => Err(Error::ChecksumMismatch {
+                attachment_id,
+                expected: checksum,
+                actual: digest.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_verify_attachment() {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+        use serde_json::json;
+
+        use crate::checksum::sha1_hex;
+        use crate::error::Error;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let content = b"%PDF-1.4 invoice".to_vec();
+        let mut odoo = server.odoo().await.unwrap();
+        odoo.web_login("admin", "admin").await.unwrap();
+        let id: u32 = odoo
+            .call(
+                "ir.attachment",
+                "create",
+                vec![json!({"name": "invoice.pdf", "datas": STANDARD.encode(&content)})],
+            )
+            .await
+            .unwrap()
+            .result;
+
+        let path = std::env::temp_dir().join(format!("verify-{}.pdf", std::process::id()));
+        let size = odoo
+            .download_to(&format!("web/content/{}", id), &path)
+            .await
+            .unwrap();
+        assert_eq!(size, content.len() as u64);
+        let digest = odoo.verify_attachment(id, &path).await.unwrap();
+        assert_eq!(digest, sha1_hex(&content));
+        assert_eq!(
+            odoo.attachment_checksum(id).await.unwrap(),
+            Some(digest.clone())
+        );
+
+        std::fs::write(&path, b"%PDF-1.4 corrupted").unwrap();
+        let error = odoo.verify_attachment(id, &path).await.unwrap_err();
+        assert!(matches!(error, Error::ChecksumMismatch { expected, .. } if expected == digest));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
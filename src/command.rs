@@ -0,0 +1,89 @@
+use serde::ser::SerializeTuple;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+/// x2many write commands, serialized to Odoo's `(code, id, values)` triplets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// `(0, 0, values)`: create a new record and link it.
+    Create(Value),
+    /// `(1, id, values)`: update a linked record.
+    Update(u32, Value),
+    /// `(2, id, 0)`: remove and delete a linked record.
+    Delete(u32),
+    /// `(3, id, 0)`: remove the link, keeping the record.
+    Unlink(u32),
+    /// `(4, id, 0)`: link an existing record.
+    Link(u32),
+    /// `(5, 0, 0)`: remove all links.
+    Clear,
+    /// `(6, 0, ids)`: replace all links with `ids`.
+    Set(Vec<u32>),
+}
+
+impl Serialize for Command {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(3)?;
+        match self {
+            Command::Create(values) => {
+                tuple.serialize_element(&0)?;
+                tuple.serialize_element(&0)?;
+                tuple.serialize_element(values)?;
+            }
+            Command::Update(id, values) => {
+                tuple.serialize_element(&1)?;
+                tuple.serialize_element(id)?;
+                tuple.serialize_element(values)?;
+            }
+            Command::Delete(id) | Command::Unlink(id) | Command::Link(id) => {
+                let code = match self {
+                    Command::Delete(_) => 2,
+                    Command::Unlink(_) => 3,
+                    _ => 4,
+                };
+                tuple.serialize_element(&code)?;
+                tuple.serialize_element(id)?;
+                tuple.serialize_element(&0)?;
+            }
+            Command::Clear => {
+                tuple.serialize_element(&5)?;
+                tuple.serialize_element(&0)?;
+                tuple.serialize_element(&0)?;
+            }
+            Command::Set(ids) => {
+                tuple.serialize_element(&6)?;
+                tuple.serialize_element(&0)?;
+                tuple.serialize_element(ids)?;
+            }
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::command::Command;
+
+    #[test]
+    fn test_serialize_commands() {
+        let commands = vec![
+            Command::Create(json!({"name": "Line"})),
+            Command::Update(3, json!({"quantity": 2})),
+            Command::Link(7),
+            Command::Clear,
+            Command::Set(vec![1, 2]),
+        ];
+        assert_eq!(
+            serde_json::to_value(commands).unwrap(),
+            json!([
+                [0, 0, {"name": "Line"}],
+                [1, 3, {"quantity": 2}],
+                [4, 7, 0],
+                [5, 0, 0],
+                [6, 0, [1, 2]]
+            ])
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use rand::{thread_rng, Rng};
+
+use crate::odoo::Odoo;
+
+impl Odoo {
+    /// Sends a correlation id in the `header` (e.g. `X-Request-Id`) of every request, so
+    /// proxy and Odoo-side logs can be joined with ours. The id is the one set with
+    /// `with_correlation_id`, else the current span's with the `tracing` feature, else a
+    /// random one per request.
+    pub fn with_correlation_header(mut self, header: &str) -> Odoo {
+        self.correlation_header = Some(header.to_string());
+        self
+    }
+
+    /// Returns a handle on the same session sending `id` as correlation id, e.g. the id of
+    /// the incoming request being served. Needs `with_correlation_header`.
+    pub fn with_correlation_id(&self, id: &str) -> Odoo {
+        let mut odoo = self.clone();
+        odoo.correlation_id = Some(id.to_string());
+        odoo
+    }
+
+    pub(crate) fn correlation(&self) -> Option<(&str, String)> {
+        let header = self.correlation_header.as_deref()?;
+        let id = match &self.correlation_id {
+            Some(id) => id.clone(),
+            None => span_id().unwrap_or_else(|| format!("{:016x}", thread_rng().gen::<u64>())),
+        };
+        Some((header, id))
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn span_id() -> Option<String> {
+    tracing::Span::current()
+        .id()
+        .map(|id| format!("{:016x}", id.into_u64()))
+}
+
+#[cfg(not(feature = "tracing"))]
+fn span_id() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::odoo::Odoo;
+
+    #[test]
+    fn test_correlation() {
+        let odoo = Odoo::new("http://localhost:8069", "demo");
+        assert!(odoo.correlation().is_none());
+
+        let odoo = odoo.with_correlation_header("X-Request-Id");
+        let (header, first) = odoo.correlation().unwrap();
+        assert_eq!(header, "X-Request-Id");
+        assert_eq!(first.len(), 16);
+        assert_ne!(odoo.correlation().unwrap().1, first);
+
+        let scoped = odoo.with_correlation_id("req-42");
+        assert_eq!(scoped.correlation().unwrap().1, "req-42");
+        assert_ne!(odoo.correlation().unwrap().1, "req-42");
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_correlation_header() {
+        use std::sync::{Arc, Mutex};
+
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let curls: Arc<Mutex<Vec<String>>> = Arc::default();
+        let dumped = curls.clone();
+        let odoo = server
+            .odoo()
+            .await
+            .unwrap()
+            .with_correlation_header("X-Request-Id")
+            .with_request_dump(move |dump| dumped.lock().unwrap().push(dump.curl.clone()))
+            .with_correlation_id("req-42");
+        let _: Vec<Value> = odoo
+            .call("res.partner", "search", (json!([]),))
+            .await
+            .unwrap()
+            .result;
+        assert!(curls.lock().unwrap()[0].contains("'x-request-id: req-42'"));
+    }
+}
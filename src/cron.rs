@@ -0,0 +1,161 @@
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::date::OdooDateTime;
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+use crate::xmlid::RecordRef;
+
+#[derive(Deserialize)]
+struct CronState {
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    lastcall: Option<String>,
+}
+
+impl Odoo {
+    /// Runs a scheduled action now through `method_direct_trigger`; returns once it is done.
+    pub async fn run_cron(&self, cron: impl Into<RecordRef>) -> Result<(), Error> {
+        let id = self.resolve("ir.cron", &cron.into()).await?;
+        let _: Value = self
+            .call_kw("ir.cron", "method_direct_trigger", ([id],), Map::new())
+            .await?
+            .result;
+        Ok(())
+    }
+
+    /// Asks the cron workers to run a scheduled action as soon as possible by moving its
+    /// `nextcall` to now, and returns its current `lastcall` to pass to `wait_cron`.
+    pub async fn schedule_cron(&self, cron: impl Into<RecordRef>) -> Result<Option<String>, Error> {
+        let id = self.resolve("ir.cron", &cron.into()).await?;
+        let lastcall = self.cron_lastcall(id).await?;
+        let _: bool = self
+            .call(
+                "ir.cron",
+                "write",
+                ([id], json!({ "nextcall": format_utc(SystemTime::now()) })),
+            )
+            .await?
+            .result;
+        Ok(lastcall)
+    }
+
+    /// Polls a scheduled action until its `lastcall` moves past `since`, bounded by the
+    /// client deadline if one is set.
+    pub async fn wait_cron(
+        &self,
+        cron: impl Into<RecordRef>,
+        since: Option<String>,
+        poll_interval: Duration,
+    ) -> Result<(), Error> {
+        let id = self.resolve("ir.cron", &cron.into()).await?;
+        loop {
+            if self.cron_lastcall(id).await? != since {
+                return Ok(());
+            }
+            self.sleep(poll_interval).await?;
+        }
+    }
+
+    async fn cron_lastcall(&self, id: u32) -> Result<Option<String>, Error> {
+        let crons: Vec<CronState> = self
+            .call("ir.cron", "read", ([id], ["lastcall"]))
+            .await?
+            .result;
+        crons
+            .into_iter()
+            .next()
+            .map(|cron| cron.lastcall)
+            .ok_or_else(|| Error::NotFound(format!("ir.cron {} not found", id)))
+    }
+}
+
+/// Formats a time as an Odoo UTC datetime (`%Y-%m-%d %H:%M:%S`).
+pub(crate) fn format_utc(time: SystemTime) -> String {
+    OdooDateTime::from(time).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::cron::format_utc;
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01 00:00:00");
+        let time = UNIX_EPOCH + Duration::from_secs(1709251199);
+        assert_eq!(format_utc(time), "2024-02-29 23:59:59");
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_cron() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let cron = server.insert(
+            "ir.cron",
+            json!({"name": "Mail: Email Queue Manager", "lastcall": "2024-03-01 10:00:00"}),
+        );
+        server.insert(
+            "ir.model.data",
+            json!({"module": "mail", "name": "ir_cron_mail_scheduler_action", "model": "ir.cron", "res_id": cron}),
+        );
+        let triggered = Arc::new(AtomicUsize::new(0));
+        let count = triggered.clone();
+        server.on_call("ir.cron", "method_direct_trigger", move |args, _| {
+            assert_eq!(args[0], json!([cron]));
+            count.fetch_add(1, Ordering::SeqCst);
+            Ok(Value::Bool(true))
+        });
+        let odoo = server.odoo().await.unwrap();
+
+        odoo.run_cron("mail.ir_cron_mail_scheduler_action")
+            .await
+            .unwrap();
+        odoo.run_cron(cron).await.unwrap();
+        assert_eq!(triggered.load(Ordering::SeqCst), 2);
+        assert!(odoo.run_cron("mail.missing").await.is_err());
+
+        let before = format_utc(std::time::SystemTime::now());
+        let since = odoo
+            .schedule_cron("mail.ir_cron_mail_scheduler_action")
+            .await
+            .unwrap();
+        assert_eq!(since.as_deref(), Some("2024-03-01 10:00:00"));
+        let nextcall = server.records("ir.cron")[0]["nextcall"].clone();
+        assert!(nextcall.as_str().unwrap() >= before.as_str());
+
+        // a worker picks it up while we poll
+        let worker = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let _: bool = odoo
+                .call(
+                    "ir.cron",
+                    "write",
+                    ([cron], json!({"lastcall": "2024-03-01 10:05:00"})),
+                )
+                .await
+                .unwrap()
+                .result;
+        };
+        let wait = odoo.wait_cron(cron, since, Duration::from_millis(10));
+        let (waited, ()) = tokio::join!(wait, worker);
+        waited.unwrap();
+
+        // no run since then: bounded by the deadline
+        let since = Some("2024-03-01 10:05:00".to_string());
+        let result = odoo
+            .with_timeout(Duration::from_millis(100))
+            .wait_cron(cron, since, Duration::from_millis(10))
+            .await;
+        assert!(matches!(result, Err(crate::error::Error::DeadlineExceeded)));
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+use crate::types::Ref;
+
+/// A record of `res.currency`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Currency {
+    pub id: u32,
+    /// ISO code, e.g. `EUR`.
+    pub name: String,
+    /// Smallest amount, e.g. `0.01`.
+    pub rounding: f64,
+}
+
+#[derive(Deserialize)]
+struct CurrencyRate {
+    currency_id: Ref<Currency>,
+    /// The date the rate applies from.
+    name: String,
+    rate: f64,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    company_id: Option<Ref<()>>,
+}
+
+/// Exchange rates read once with `Odoo::currency_rates`, converting amounts client-side
+/// the way Odoo does: rates are units of the currency per unit of the company currency,
+/// taken from the latest rate on or before the date, 1 when there is none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyRates {
+    currencies: HashMap<u32, Currency>,
+    /// `(date, rate)` per currency, by date.
+    rates: HashMap<u32, Vec<(String, f64)>>,
+}
+
+impl Odoo {
+    /// Reads the currencies and their rates, the ones of `company` taking precedence over
+    /// rates shared by all companies.
+    pub async fn currency_rates(&self, company: Option<u32>) -> Result<CurrencyRates, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(["name", "rounding"]));
+        kwargs.insert("context".to_string(), json!({"active_test": false}));
+        let currencies: Vec<Currency> = self
+            .call_kw("res.currency", "search_read", (json!([]),), kwargs)
+            .await?
+            .result;
+
+        let domain = match company {
+            Some(company) => json!([["company_id", "in", [company, false]]]),
+            None => json!([]),
+        };
+        let mut kwargs = Map::new();
+        kwargs.insert(
+            "fields".to_string(),
+            json!(["currency_id", "name", "rate", "company_id"]),
+        );
+        kwargs.insert("load".to_string(), Value::from(""));
+        let rates: Vec<CurrencyRate> = self
+            .call_kw("res.currency.rate", "search_read", (domain,), kwargs)
+            .await?
+            .result;
+        Ok(CurrencyRates::new(currencies, rates))
+    }
+}
+
+impl CurrencyRates {
+    fn new(currencies: Vec<Currency>, rates: Vec<CurrencyRate>) -> CurrencyRates {
+        let mut by_currency: HashMap<u32, Vec<(String, f64, bool)>> = HashMap::new();
+        for rate in rates {
+            by_currency.entry(rate.currency_id.id).or_default().push((
+                rate.name,
+                rate.rate,
+                rate.company_id.is_some(),
+            ));
+        }
+        let rates = by_currency
+            .into_iter()
+            .map(|(currency, mut rates)| {
+                // on the same date, the company rate sorts last and wins
+                rates.sort_by(|a, b| a.0.cmp(&b.0).then(a.2.cmp(&b.2)));
+                let rates = rates.into_iter().map(|(date, rate, _)| (date, rate));
+                (currency, rates.collect())
+            })
+            .collect();
+        CurrencyRates {
+            currencies: currencies
+                .into_iter()
+                .map(|currency| (currency.id, currency))
+                .collect(),
+            rates,
+        }
+    }
+
+    /// The currency with ISO code `name`.
+    pub fn currency(&self, name: &str) -> Option<&Currency> {
+        self.currencies
+            .values()
+            .find(|currency| currency.name == name)
+    }
+
+    /// The rate of `currency` on `date` (`YYYY-MM-DD`).
+    pub fn rate(&self, currency: u32, date: &str) -> f64 {
+        self.rates
+            .get(&currency)
+            .and_then(|rates| rates.iter().rev().find(|(from, _)| from.as_str() <= date))
+            .map_or(1.0, |(_, rate)| *rate)
+    }
+
+    /// Converts `amount` from currency `from` to currency `to` at the rates of `date`,
+    /// rounded to the precision of `to`.
+    pub fn convert(&self, amount: f64, from: u32, to: u32, date: &str) -> Result<f64, Error> {
+        let target = self
+            .currencies
+            .get(&to)
+            .ok_or_else(|| Error::NotFound(format!("res.currency {} not found", to)))?;
+        if !self.currencies.contains_key(&from) {
+            return Err(Error::NotFound(format!("res.currency {} not found", from)));
+        }
+        let converted = if from == to {
+            amount
+        } else {
+            amount * self.rate(to, date) / self.rate(from, date)
+        };
+        Ok(round(converted, target.rounding))
+    }
+}
+
+fn round(amount: f64, rounding: f64) -> f64 {
+    if rounding <= 0.0 {
+        return amount;
+    }
+    let rounded = (amount / rounding).round() * rounding;
+    // drop float noise such as 12.340000000000002
+    let decimals = (-rounding.log10()).ceil().max(0.0) as i32;
+    let factor = 10f64.powi(decimals);
+    (rounded * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::currency::{round, Currency, CurrencyRate, CurrencyRates};
+
+    #[test]
+    fn test_convert() {
+        let currencies: Vec<Currency> = serde_json::from_value(json!([
+            {"id": 1, "name": "EUR", "rounding": 0.01},
+            {"id": 2, "name": "USD", "rounding": 0.01},
+            {"id": 3, "name": "JPY", "rounding": 1.0},
+        ]))
+        .unwrap();
+        let rates: Vec<CurrencyRate> = serde_json::from_value(json!([
+            {"currency_id": 2, "name": "2024-01-01", "rate": 1.10, "company_id": false},
+            {"currency_id": [2, "USD"], "name": "2024-02-01", "rate": 1.08, "company_id": false},
+            {"currency_id": 2, "name": "2024-02-01", "rate": 1.09, "company_id": 1},
+            {"currency_id": 3, "name": "2024-01-01", "rate": 160.0, "company_id": false},
+        ]))
+        .unwrap();
+        let rates = CurrencyRates::new(currencies, rates);
+
+        let usd = rates.currency("USD").unwrap().id;
+        assert_eq!(rates.rate(usd, "2023-12-31"), 1.0);
+        assert_eq!(rates.rate(usd, "2024-01-15"), 1.10);
+        assert_eq!(rates.rate(usd, "2024-03-01"), 1.09);
+        assert_eq!(rates.convert(100.0, 1, 2, "2024-01-15").unwrap(), 110.0);
+        assert_eq!(rates.convert(110.0, 2, 1, "2024-01-15").unwrap(), 100.0);
+        assert_eq!(rates.convert(10.0, 2, 3, "2024-01-15").unwrap(), 1455.0);
+        assert!(rates.convert(1.0, 1, 9, "2024-01-15").is_err());
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(round(12.345_678, 0.01), 12.35);
+        assert_eq!(round(1454.545, 1.0), 1455.0);
+        assert_eq!(round(0.1 + 0.2, 0.05), 0.3);
+    }
+}
@@ -0,0 +1,419 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::Error;
+
+/// A `date` field value, `YYYY-MM-DD` on the wire. Use `OdooOption<OdooDate>` for dates that
+/// may be empty (`false`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OdooDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+/// A `datetime` field value, `YYYY-MM-DD HH:MM:SS` in UTC on the wire. Use
+/// `OdooOption<OdooDateTime>` for datetimes that may be empty (`false`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OdooDateTime {
+    date: OdooDate,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl OdooDate {
+    /// `None` if the date doesn't exist or the year is outside `1..=9999`.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<OdooDate> {
+        let valid = (1..=9999).contains(&year)
+            && (1..=12).contains(&month)
+            && day >= 1
+            && day <= days_in_month(year, month);
+        valid.then_some(OdooDate { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The datetime at `hour:minute:second` UTC on this date.
+    pub fn and_hms(self, hour: u8, minute: u8, second: u8) -> Option<OdooDateTime> {
+        OdooDateTime::new(self, hour, minute, second)
+    }
+
+    /// Days since 1970-01-01.
+    fn days(&self) -> i64 {
+        // days-from-civil, Howard Hinnant's algorithm
+        let (month, day) = (i64::from(self.month), i64::from(self.day));
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    fn from_days(days: i64) -> OdooDate {
+        // civil-from-days
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        OdooDate {
+            year: year.clamp(1, 9999) as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl OdooDateTime {
+    pub fn new(date: OdooDate, hour: u8, minute: u8, second: u8) -> Option<OdooDateTime> {
+        (hour < 24 && minute < 60 && second < 60).then_some(OdooDateTime {
+            date,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    pub fn now() -> OdooDateTime {
+        OdooDateTime::from(SystemTime::now())
+    }
+
+    pub fn date(&self) -> OdooDate {
+        self.date
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Seconds since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        self.date.days() * 86400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+    }
+
+    pub fn to_system_time(&self) -> SystemTime {
+        let secs = self.timestamp();
+        match u64::try_from(secs) {
+            Ok(secs) => UNIX_EPOCH + Duration::from_secs(secs),
+            Err(_) => UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs()),
+        }
+    }
+}
+
+impl From<SystemTime> for OdooDateTime {
+    /// Truncates to the second.
+    fn from(time: SystemTime) -> OdooDateTime {
+        let secs = match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs_f64().ceil() as i64),
+        };
+        let (days, rem) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+        OdooDateTime {
+            date: OdooDate::from_days(days),
+            hour: (rem / 3600) as u8,
+            minute: (rem % 3600 / 60) as u8,
+            second: (rem % 60) as u8,
+        }
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for OdooDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl fmt::Display for OdooDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:02}:{:02}:{:02}",
+            self.date, self.hour, self.minute, self.second
+        )
+    }
+}
+
+fn invalid(kind: &str, value: &str) -> Error {
+    Error::Decode {
+        path: None,
+        message: format!("Invalid {} \"{}\"", kind, value),
+    }
+}
+
+/// Parses the digits of `value` split by `separator` into `N` numbers.
+fn numbers<const N: usize>(value: &str, separator: char) -> Option<[u16; N]> {
+    let mut numbers = [0; N];
+    let mut parts = value.split(separator);
+    for number in numbers.iter_mut() {
+        let part = parts.next()?;
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        *number = part.parse().ok()?;
+    }
+    parts.next().is_none().then_some(numbers)
+}
+
+impl FromStr for OdooDate {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<OdooDate, Error> {
+        numbers::<3>(value, '-')
+            .and_then(|[year, month, day]| {
+                OdooDate::new(year, u8::try_from(month).ok()?, u8::try_from(day).ok()?)
+            })
+            .ok_or_else(|| invalid("date", value))
+    }
+}
+
+impl FromStr for OdooDateTime {
+    type Err = Error;
+
+    /// Also accepts an ISO `T` separator and fractional seconds, which are dropped.
+    fn from_str(value: &str) -> Result<OdooDateTime, Error> {
+        let (date, time) = value
+            .split_once([' ', 'T'])
+            .ok_or_else(|| invalid("datetime", value))?;
+        let time = time.split_once('.').map_or(time, |(time, _)| time);
+        let date: OdooDate = date.parse().map_err(|_| invalid("datetime", value))?;
+        numbers::<3>(time, ':')
+            .filter(|time| time.iter().all(|n| *n < 60))
+            .and_then(|[hour, minute, second]| {
+                OdooDateTime::new(date, hour as u8, minute as u8, second as u8)
+            })
+            .ok_or_else(|| invalid("datetime", value))
+    }
+}
+
+impl<'de> Deserialize<'de> for OdooDate {
+    fn deserialize<D: Deserializer<'de>>(data: D) -> Result<OdooDate, D::Error> {
+        String::deserialize(data)?.parse().map_err(D::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for OdooDateTime {
+    fn deserialize<D: Deserializer<'de>>(data: D) -> Result<OdooDateTime, D::Error> {
+        String::deserialize(data)?.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for OdooDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for OdooDateTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+    use crate::date::{OdooDate, OdooDateTime};
+
+    impl From<OdooDate> for NaiveDate {
+        fn from(date: OdooDate) -> NaiveDate {
+            NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+                .expect("OdooDate is always a valid date")
+        }
+    }
+
+    impl From<OdooDateTime> for NaiveDateTime {
+        fn from(datetime: OdooDateTime) -> NaiveDateTime {
+            NaiveDate::from(datetime.date)
+                .and_hms_opt(
+                    datetime.hour.into(),
+                    datetime.minute.into(),
+                    datetime.second.into(),
+                )
+                .expect("OdooDateTime is always a valid datetime")
+        }
+    }
+
+    impl TryFrom<NaiveDate> for OdooDate {
+        type Error = crate::error::Error;
+
+        /// Fails for years outside `1..=9999`.
+        fn try_from(date: NaiveDate) -> Result<OdooDate, Self::Error> {
+            u16::try_from(date.year())
+                .ok()
+                .and_then(|year| OdooDate::new(year, date.month() as u8, date.day() as u8))
+                .ok_or_else(|| crate::date::invalid("date", &date.to_string()))
+        }
+    }
+
+    impl TryFrom<NaiveDateTime> for OdooDateTime {
+        type Error = crate::error::Error;
+
+        /// Truncates to the second; fails for years outside `1..=9999`.
+        fn try_from(datetime: NaiveDateTime) -> Result<OdooDateTime, Self::Error> {
+            let date = OdooDate::try_from(datetime.date())?;
+            Ok(OdooDateTime {
+                date,
+                hour: datetime.hour() as u8,
+                minute: datetime.minute() as u8,
+                // leap seconds are folded into the last second
+                second: datetime.second().min(59) as u8,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    use crate::date::{OdooDate, OdooDateTime};
+
+    impl From<OdooDate> for Date {
+        fn from(date: OdooDate) -> Date {
+            let month = Month::try_from(date.month).expect("OdooDate has a valid month");
+            Date::from_calendar_date(date.year.into(), month, date.day)
+                .expect("OdooDate is always a valid date")
+        }
+    }
+
+    impl From<OdooDateTime> for PrimitiveDateTime {
+        fn from(datetime: OdooDateTime) -> PrimitiveDateTime {
+            let time = Time::from_hms(datetime.hour, datetime.minute, datetime.second)
+                .expect("OdooDateTime is always a valid time");
+            PrimitiveDateTime::new(datetime.date.into(), time)
+        }
+    }
+
+    impl TryFrom<Date> for OdooDate {
+        type Error = crate::error::Error;
+
+        /// Fails for years outside `1..=9999`.
+        fn try_from(date: Date) -> Result<OdooDate, Self::Error> {
+            u16::try_from(date.year())
+                .ok()
+                .and_then(|year| OdooDate::new(year, date.month().into(), date.day()))
+                .ok_or_else(|| crate::date::invalid("date", &date.to_string()))
+        }
+    }
+
+    impl TryFrom<PrimitiveDateTime> for OdooDateTime {
+        type Error = crate::error::Error;
+
+        /// Truncates to the second; fails for years outside `1..=9999`.
+        fn try_from(datetime: PrimitiveDateTime) -> Result<OdooDateTime, Self::Error> {
+            Ok(OdooDateTime {
+                date: OdooDate::try_from(datetime.date())?,
+                hour: datetime.hour(),
+                minute: datetime.minute(),
+                second: datetime.second(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use crate::date::{OdooDate, OdooDateTime};
+    use crate::types::OdooOption;
+
+    #[test]
+    fn test_parse_and_display() {
+        let date: OdooDate = "2024-02-29".parse().unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert!("2023-02-29".parse::<OdooDate>().is_err());
+        assert!("2024-2-30".parse::<OdooDate>().is_err());
+        assert!("24-01-01x".parse::<OdooDate>().is_err());
+
+        let datetime: OdooDateTime = "2024-05-01 08:30:00".parse().unwrap();
+        assert_eq!(datetime.to_string(), "2024-05-01 08:30:00");
+        let iso: OdooDateTime = "2024-05-01T08:30:00.123456".parse().unwrap();
+        assert_eq!(iso, datetime);
+        assert!("2024-05-01 24:00:00".parse::<OdooDateTime>().is_err());
+        assert!("2024-05-01".parse::<OdooDateTime>().is_err());
+        assert!(datetime.date() < "2024-05-02".parse().unwrap());
+    }
+
+    #[test]
+    fn test_system_time() {
+        let time = UNIX_EPOCH + Duration::from_secs(1709251199);
+        let datetime = OdooDateTime::from(time);
+        assert_eq!(datetime.to_string(), "2024-02-29 23:59:59");
+        assert_eq!(datetime.to_system_time(), time);
+        let before = OdooDateTime::from(UNIX_EPOCH - Duration::from_secs(86400));
+        assert_eq!(before.to_string(), "1969-12-31 00:00:00");
+        assert_eq!(before.timestamp(), -86400);
+    }
+
+    #[test]
+    fn test_serde() {
+        #[derive(Serialize, Deserialize)]
+        struct Invoice {
+            invoice_date: OdooDate,
+            date_due: OdooOption<OdooDate>,
+            create_date: OdooDateTime,
+        }
+
+        let invoice: Invoice = serde_json::from_value(json!({
+            "invoice_date": "2024-05-01",
+            "date_due": false,
+            "create_date": "2024-05-01 08:30:00",
+        }))
+        .unwrap();
+        assert_eq!(invoice.invoice_date.day(), 1);
+        assert_eq!(*invoice.date_due, None);
+        assert_eq!(
+            serde_json::to_value(&invoice).unwrap(),
+            json!({"invoice_date": "2024-05-01", "date_due": false, "create_date": "2024-05-01 08:30:00"})
+        );
+        assert!(serde_json::from_value::<OdooDate>(json!(false)).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use serde_json::{Map, Number, Value};
+
+use crate::error::Error;
+
+pub const DEFAULT_IN_CHUNK_SIZE: usize = 5000;
+
+/// Splits the largest `(field, "in", [...])` leaf of `domain` holding more than `chunk_size`
+/// values into one domain per chunk. The union of their results equals the original
+/// result, which only holds while no `!` operator is involved.
+pub fn chunk_in_clause(domain: &Value, chunk_size: usize) -> Option<Vec<Value>> {
+    if contains_negation(domain) {
+        return None;
+    }
+    let pointer = largest_in_clause(domain, String::new())
+        .filter(|(len, _)| *len > chunk_size)
+        .map(|(_, pointer)| pointer)?;
+    let values = domain.pointer(&pointer)?.as_array()?;
+    Some(
+        values
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let mut domain = domain.clone();
+                *domain.pointer_mut(&pointer).unwrap() = Value::Array(chunk.to_vec());
+                domain
+            })
+            .collect(),
+    )
+}
+
+fn contains_negation(domain: &Value) -> bool {
+    match domain {
+        Value::String(operator) => operator == "!",
+        Value::Array(items) => match items.as_slice() {
+            [Value::String(_), Value::String(_), _] => false,
+            _ => items.iter().any(contains_negation),
+        },
+        _ => false,
+    }
+}
+
+// returns the length and JSON pointer of the largest `in` list
+fn largest_in_clause(domain: &Value, pointer: String) -> Option<(usize, String)> {
+    let items = domain.as_array()?;
+    match items.as_slice() {
+        [Value::String(_), Value::String(operator), Value::Array(values)] if operator == "in" => {
+            Some((values.len(), format!("{}/2", pointer)))
+        }
+        [Value::String(_), Value::String(_), _] => None,
+        _ => items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| largest_in_clause(item, format!("{}/{}", pointer, i)))
+            .max_by_key(|(len, _)| *len),
+    }
+}
+
+/// Parses a Python-style literal as written in Odoo code and shells, e.g.
+/// `[("id", ">", 2), ("active", "=", True)]`, into JSON. JSON itself is accepted too.
+pub fn parse_literal(input: &str) -> Result<Value, Error> {
+    let mut parser = LiteralParser {
+        chars: input.chars().collect(),
+        position: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position < parser.chars.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+struct LiteralParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl LiteralParser {
+    fn error(&self, message: &str) -> Error {
+        Error::Request(format!(
+            "Invalid literal at position {}: {}",
+            self.position, message
+        ))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.sequence(']'),
+            Some('(') => self.sequence(')'),
+            Some('{') => self.dict(),
+            Some(quote @ ('"' | '\'')) => self.string(quote).map(Value::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) if c.is_ascii_alphabetic() => self.keyword(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn sequence(&mut self, close: char) -> Result<Value, Error> {
+        self.position += 1;
+        let mut items = vec![];
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                self.position += 1;
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(c) if c == close => {}
+                _ => return Err(self.error("expected ',' or end of sequence")),
+            }
+        }
+    }
+
+    fn dict(&mut self) -> Result<Value, Error> {
+        self.position += 1;
+        let mut map = Map::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(Value::Object(map));
+                }
+                Some(quote @ ('"' | '\'')) => {
+                    let key = self.string(quote)?;
+                    self.skip_whitespace();
+                    if self.peek() != Some(':') {
+                        return Err(self.error("expected ':'"));
+                    }
+                    self.position += 1;
+                    map.insert(key, self.value()?);
+                }
+                _ => return Err(self.error("expected a string key")),
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {}
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, Error> {
+        self.position += 1;
+        let mut string = String::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.position += 1;
+            match c {
+                '\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += 1;
+                    string.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                }
+                c if c == quote => return Ok(string),
+                c => string.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, Error> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || "-+.eE".contains(c))
+        {
+            self.position += 1;
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        if let Ok(integer) = text.parse::<i64>() {
+            return Ok(Value::Number(integer.into()));
+        }
+        text.parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+
+    fn keyword(&mut self) -> Result<Value, Error> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric()) {
+            self.position += 1;
+        }
+        let word: String = self.chars[start..self.position].iter().collect();
+        match word.as_str() {
+            "True" | "true" => Ok(Value::Bool(true)),
+            "False" | "false" => Ok(Value::Bool(false)),
+            "None" | "null" => Ok(Value::Null),
+            _ => {
+                self.position = start;
+                Err(self.error("unknown identifier"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::domain::{chunk_in_clause, parse_literal};
+
+    #[test]
+    fn test_chunk_in_clause() {
+        let ids: Vec<u32> = (1..=5).collect();
+        let domain = json!(["|", ["active", "=", false], ["id", "in", ids]]);
+        let domains = chunk_in_clause(&domain, 2).unwrap();
+        assert_eq!(domains.len(), 3);
+        assert_eq!(
+            domains[0],
+            json!(["|", ["active", "=", false], ["id", "in", [1, 2]]])
+        );
+        assert_eq!(
+            domains[2],
+            json!(["|", ["active", "=", false], ["id", "in", [5]]])
+        );
+
+        assert_eq!(chunk_in_clause(&domain, 10), None);
+        let negated = json!(["!", ["id", "in", [1, 2, 3]]]);
+        assert_eq!(chunk_in_clause(&negated, 2), None);
+        assert_eq!(chunk_in_clause(&Value::Array(vec![]), 2), None);
+    }
+
+    #[test]
+    fn test_parse_literal() {
+        assert_eq!(
+            parse_literal(r#"[("id", ">", 2), ('name', 'ilike', 'O\'Neil'), "|"]"#).unwrap(),
+            json!([["id", ">", 2], ["name", "ilike", "O'Neil"], "|"])
+        );
+        assert_eq!(
+            parse_literal("{'active': False, 'rate': -1.5, 'tags': [(6, 0, [1, 2],)]}").unwrap(),
+            json!({"active": false, "rate": -1.5, "tags": [[6, 0, [1, 2]]]})
+        );
+        assert_eq!(parse_literal(r#"{"x": null}"#).unwrap(), json!({"x": null}));
+        assert!(parse_literal("[('id', '=', 1)").is_err());
+        assert!(parse_literal("[foo]").is_err());
+    }
+}
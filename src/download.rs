@@ -0,0 +1,348 @@
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+
+use reqwest::header::{
+    HeaderMap, HeaderValue, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE,
+};
+use reqwest::StatusCode;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// Route of a binary field's content, e.g. `web/content/ir.attachment/42/datas`.
+pub fn content_route(model: &str, id: u32, field: &str) -> String {
+    format!("web/content/{}/{}/{}", model, id, field)
+}
+
+impl Odoo {
+    /// Downloads a binary route such as `web/content/42` (an attachment) or a `content_route`
+    /// into `path`, for files too large to fetch again from the start after a failure.
+    ///
+    /// The `ETag` (or `Last-Modified`) of the content is kept in `<path>.validator` while the
+    /// file is incomplete. An existing file is taken as a partial download and continued with
+    /// a `Range` request conditioned on it (`If-Range`); a transfer cut off midway resumes
+    /// the same way, up to `max_retries` times in a row (see `with_retry_policy`). The file
+    /// restarts from zero when there is no validator to check, or the content changed in
+    /// between. Failures to write the file aren't retried. Returns the size of the file.
+    pub async fn download_to(&self, route: &str, path: impl AsRef<Path>) -> Result<u64, Error> {
+        let path = path.as_ref();
+        let _call = self.begin_call().await?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(path)
+            .await
+            .map_err(|e| io_error(path, e))?;
+        let mut offset = file.metadata().await.map_err(|e| io_error(path, e))?.len();
+        file.seek(SeekFrom::End(0))
+            .await
+            .map_err(|e| io_error(path, e))?;
+
+        let mut validator = match offset {
+            0 => None,
+            _ => tokio::fs::read_to_string(validator_path(path))
+                .await
+                .ok()
+                .and_then(|validator| HeaderValue::from_str(validator.trim()).ok()),
+        };
+        let mut attempt = 0;
+        loop {
+            let started = offset;
+            let error = match self
+                .download_range(route, path, &mut file, &mut offset, &mut validator)
+                .await
+            {
+                Ok(()) => {
+                    remove_validator(path).await?;
+                    return Ok(offset);
+                }
+                Err(Failure::Transfer(
+                    e @ (Error::Request(_) | Error::NotSent(_) | Error::ServerUnavailable { .. }),
+                )) => e,
+                Err(Failure::Transfer(e) | Failure::File(e)) => return Err(e),
+            };
+            // only failures without progress count towards the limit
+            if offset > started {
+                attempt = 0;
+            }
+            if attempt >= self.retry_policy.max_retries {
+                return Err(error);
+            }
+            self.sleep(self.retry_policy.backoff(attempt)).await?;
+            self.lifecycle.check(self.draining)?;
+            attempt += 1;
+        }
+    }
+
+    async fn download_range(
+        &self,
+        route: &str,
+        path: &Path,
+        file: &mut File,
+        offset: &mut u64,
+        validator: &mut Option<HeaderValue>,
+    ) -> Result<(), Failure> {
+        let mut builder = self.http(reqwest::Method::GET, route);
+        match validator.as_ref() {
+            Some(validator) if *offset > 0 => {
+                builder = builder
+                    .header(RANGE, format!("bytes={}-", offset))
+                    .header(IF_RANGE, validator);
+            }
+            // without a validator, the bytes on disk may be of another version
+            _ => restart(path, file, offset).await?,
+        }
+        let mut resp = self.execute(builder).await.map_err(Failure::Transfer)?;
+        match resp.status() {
+            StatusCode::PARTIAL_CONTENT => match content_range(resp.headers()) {
+                Some((start, _)) if start == *offset => {}
+                _ => {
+                    return Err(Failure::Transfer(Error::Request(format!(
+                        "Unexpected Content-Range for bytes {}-",
+                        offset
+                    ))))
+                }
+            },
+            StatusCode::OK => restart(path, file, offset).await?,
+            // the file was already complete, unless the content shrank in between
+            StatusCode::RANGE_NOT_SATISFIABLE => {
+                return match content_range(resp.headers()) {
+                    Some((_, Some(total))) if total == *offset => Ok(()),
+                    _ => {
+                        restart(path, file, offset).await?;
+                        *validator = None;
+                        Err(Failure::Transfer(Error::Request(String::from(
+                            "Content changed during the download",
+                        ))))
+                    }
+                };
+            }
+            status if status.is_server_error() => {
+                return Err(Failure::Transfer(Error::ServerUnavailable {
+                    status: status.as_u16(),
+                    retry_after: None,
+                }))
+            }
+            status => {
+                return Err(Failure::Transfer(Error::NotFound(format!(
+                    "Download of {} failed: {}",
+                    route, status
+                ))))
+            }
+        }
+        *validator = resp
+            .headers()
+            .get(ETAG)
+            .or_else(|| resp.headers().get(LAST_MODIFIED))
+            .cloned();
+        match validator
+            .as_ref()
+            .and_then(|validator| validator.to_str().ok())
+        {
+            Some(validator) => tokio::fs::write(validator_path(path), validator)
+                .await
+                .map_err(|e| Failure::File(io_error(path, e)))?,
+            None => remove_validator(path).await.map_err(Failure::File)?,
+        }
+
+        // what was received is kept for the next attempt
+        let received = loop {
+            match resp.chunk().await {
+                Ok(Some(chunk)) => {
+                    file.write_all(&chunk)
+                        .await
+                        .map_err(|e| Failure::File(io_error(path, e)))?;
+                    *offset += chunk.len() as u64;
+                }
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(Failure::Transfer(self.map_transport_error(e))),
+            }
+        };
+        file.flush()
+            .await
+            .map_err(|e| Failure::File(io_error(path, e)))?;
+        received
+    }
+}
+
+// a failed attempt: the transfer may be resumed, not a file that can't be written
+enum Failure {
+    Transfer(Error),
+    File(Error),
+}
+
+async fn restart(path: &Path, file: &mut File, offset: &mut u64) -> Result<(), Failure> {
+    file.set_len(0)
+        .await
+        .map_err(|e| Failure::File(io_error(path, e)))?;
+    file.seek(SeekFrom::Start(0))
+        .await
+        .map_err(|e| Failure::File(io_error(path, e)))?;
+    *offset = 0;
+    Ok(())
+}
+
+// `backup.zip` -> `backup.zip.validator`
+fn validator_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".validator");
+    PathBuf::from(name)
+}
+
+async fn remove_validator(path: &Path) -> Result<(), Error> {
+    match tokio::fs::remove_file(validator_path(path)).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error(path, e)),
+        _ => Ok(()),
+    }
+}
+
+// `bytes 100-999/1000` -> (100, Some(1000)), `bytes */1000` -> (0, Some(1000))
+fn content_range(headers: &HeaderMap) -> Option<(u64, Option<u64>)> {
+    let value = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let total = total.parse().ok();
+    let start = match range {
+        "*" => 0,
+        range => range.split_once('-')?.0.parse().ok()?,
+    };
+    Some((start, total))
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> Error {
+    Error::Request(format!("Can't write {}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue, CONTENT_RANGE};
+
+    use crate::download::{content_range, content_route};
+
+    #[test]
+    fn test_content_range() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_range(&headers), None);
+        headers.insert(
+            CONTENT_RANGE,
+            HeaderValue::from_static("bytes 100-999/1000"),
+        );
+        assert_eq!(content_range(&headers), Some((100, Some(1000))));
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes 0-9/*"));
+        assert_eq!(content_range(&headers), Some((0, None)));
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes */1000"));
+        assert_eq!(content_range(&headers), Some((0, Some(1000))));
+        assert_eq!(
+            content_route("ir.attachment", 42, "datas"),
+            "web/content/ir.attachment/42/datas"
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_download_to() {
+        use std::convert::Infallible;
+        use std::net::SocketAddr;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use hyper::header::{CONTENT_LENGTH, ETAG, IF_RANGE, RANGE};
+        use hyper::service::{make_service_fn, service_fn};
+        use hyper::{Body, Response, Server, StatusCode};
+
+        use crate::download::validator_path;
+        use crate::odoo::Odoo;
+        use crate::retry::RetryPolicy;
+
+        // drops the connection after 300 bytes, once per request without a range; a range is
+        // only served for the current `ETag`
+        let content: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = (content.clone(), requests.clone());
+        let make_service = make_service_fn(move |_| {
+            let (content, requests) = served.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: hyper::Request<Body>| {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    let current = req
+                        .headers()
+                        .get(IF_RANGE)
+                        .is_some_and(|tag| tag == "\"v1\"");
+                    let start: usize = req
+                        .headers()
+                        .get(RANGE)
+                        .and_then(|range| range.to_str().ok())
+                        .and_then(|range| range.strip_prefix("bytes="))
+                        .and_then(|range| range.trim_end_matches('-').parse().ok())
+                        .filter(|_| current)
+                        .unwrap_or(0);
+                    let response = Response::builder()
+                        .header(ETAG, "\"v1\"")
+                        .header(CONTENT_LENGTH, content.len() - start);
+                    let response = match start {
+                        0 => {
+                            let (mut sender, body) = Body::channel();
+                            let head = content[..300].to_vec();
+                            tokio::spawn(async move {
+                                let _ = sender.send_data(head.into()).await;
+                                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                                sender.abort();
+                            });
+                            response.body(body)
+                        }
+                        start => response
+                            .status(StatusCode::PARTIAL_CONTENT)
+                            .header(
+                                "Content-Range",
+                                format!("bytes {}-{}/{}", start, content.len() - 1, content.len()),
+                            )
+                            .body(Body::from(content[start..].to_vec())),
+                    };
+                    async move { Ok::<_, Infallible>(response.unwrap()) }
+                }))
+            }
+        });
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+
+        let path = std::env::temp_dir().join(format!("download-{}.bin", std::process::id()));
+        let validator = validator_path(&path);
+        let _ = std::fs::remove_file(&path);
+        let odoo = Odoo::new(&url, "mock");
+        let size = odoo.download_to("web/content/1", &path).await.unwrap();
+        assert_eq!(size, 1000);
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert!(!validator.exists());
+
+        // a partial file is resumed
+        std::fs::write(&path, &content[..600]).unwrap();
+        std::fs::write(&validator, "\"v1\"").unwrap();
+        odoo.download_to("web/content/1", &path).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        // unless it can't be checked against the current content
+        for stale in [None, Some("\"v0\"")] {
+            std::fs::write(&path, vec![0; 600]).unwrap();
+            if let Some(stale) = stale {
+                std::fs::write(&validator, stale).unwrap();
+            }
+            requests.store(0, Ordering::SeqCst);
+            odoo.download_to("web/content/1", &path).await.unwrap();
+            assert_eq!(std::fs::read(&path).unwrap(), content);
+            assert_eq!(requests.load(Ordering::SeqCst), 2);
+        }
+
+        let odoo = odoo.with_retry_policy(RetryPolicy::none());
+        std::fs::remove_file(&path).unwrap();
+        assert!(odoo.download_to("web/content/1", &path).await.is_err());
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 300);
+        assert_eq!(std::fs::read_to_string(&validator).unwrap(), "\"v1\"");
+        std::fs::remove_file(&validator).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::header::{AUTHORIZATION, COOKIE};
+use serde_json::Value;
+
+/// An HTTP exchange handed to the hook set with `Odoo::with_request_dump`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestDump {
+    /// The request as a curl command, with the credentials and session cookie redacted.
+    pub curl: String,
+    pub status: u16,
+    /// The raw response body, lossily decoded as UTF-8; empty for redirects.
+    pub response_body: String,
+}
+
+impl fmt::Display for RequestDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\n< {}\n{}",
+            self.curl, self.status, self.response_body
+        )
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct DumpHook(pub(crate) Arc<dyn Fn(&RequestDump) + Send + Sync>);
+
+impl fmt::Debug for DumpHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DumpHook")
+    }
+}
+
+const REDACTED: &str = "***";
+
+pub(crate) fn curl_command(request: &reqwest::Request) -> String {
+    let mut curl = format!(
+        "curl -X {} {}",
+        request.method(),
+        quote(request.url().as_str())
+    );
+    for (name, value) in request.headers() {
+        let value = if name == COOKIE || name == AUTHORIZATION {
+            REDACTED
+        } else {
+            value.to_str().unwrap_or(REDACTED)
+        };
+        curl.push_str(&format!(" -H {}", quote(&format!("{}: {}", name, value))));
+    }
+    match request.body().map(|body| body.as_bytes()) {
+        Some(Some(body)) => {
+            let body = match serde_json::from_slice::<Value>(body) {
+                Ok(mut json) => {
+                    redact(&mut json);
+                    json.to_string()
+                }
+                Err(_) => String::from_utf8_lossy(body).into_owned(),
+            };
+            curl.push_str(&format!(" --data-raw {}", quote(&body)));
+        }
+        Some(None) => curl.push_str(" --data-binary '<stream>'"),
+        None => {}
+    }
+    curl
+}
+
+// credentials travel as `/jsonrpc` arguments, by position for each service, or as
+// `login`/`password` for web logins
+pub(crate) fn redact(json: &mut Value) {
+    let params = match json.get_mut("params") {
+        Some(Value::Object(params)) => params,
+        _ => return,
+    };
+    for field in ["login", "password"] {
+        if let Some(value) = params.get_mut(field) {
+            *value = Value::from(REDACTED);
+        }
+    }
+    let service = params.get("service").and_then(Value::as_str);
+    let method = params.get("method").and_then(Value::as_str);
+    let secrets: &[usize] = match (service, method) {
+        (Some("common"), _) => &[1, 2],
+        (
+            Some("db"),
+            Some("list" | "db_exist" | "server_version" | "list_lang" | "list_countries"),
+        ) => &[],
+        // the master password, then the admin password of the new database
+        (Some("db"), Some("create_database")) => &[0, 4],
+        (Some("db"), Some("change_admin_password")) => &[0, 1],
+        (Some("db"), _) => &[0],
+        _ => &[2],
+    };
+    if let Some(Value::Array(args)) = params.get_mut("args") {
+        for &index in secrets {
+            if let Some(secret) = args.get_mut(index).filter(|secret| secret.is_string()) {
+                *secret = Value::from(REDACTED);
+            }
+        }
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::COOKIE;
+    use serde_json::json;
+
+    use crate::dump::{curl_command, redact, REDACTED};
+
+    #[test]
+    fn test_curl_command() {
+        let request = reqwest::Client::new()
+            .post("http://localhost:8069/jsonrpc")
+            .header(COOKIE, "session_id=abc")
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "method": "call",
+                "params": {
+                    "service": "object",
+                    "method": "execute_kw",
+                    "args": ["db", 2, "secret", "res.partner", "search", [[["name", "=", "O'Neil"]]]],
+                },
+            }))
+            .build()
+            .unwrap();
+        let curl = curl_command(&request);
+        assert!(curl.starts_with("curl -X POST 'http://localhost:8069/jsonrpc'"));
+        assert!(curl.contains("-H 'cookie: ***'"));
+        assert!(!curl.contains("secret"));
+        assert!(curl.contains(r#""O'\''Neil""#));
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut login = json!({"params": {
+            "service": "common",
+            "method": "authenticate",
+            "args": ["prod", "admin@example.com", "secret", {}],
+        }});
+        redact(&mut login);
+        assert_eq!(
+            login["params"]["args"],
+            json!(["prod", REDACTED, REDACTED, {}])
+        );
+
+        let mut drop =
+            json!({"params": {"service": "db", "method": "drop", "args": ["master", "prod"]}});
+        redact(&mut drop);
+        assert_eq!(drop["params"]["args"], json!([REDACTED, "prod"]));
+        let mut create = json!({"params": {
+            "service": "db",
+            "method": "create_database",
+            "args": ["master", "new", false, "en_US", "admin-secret", "admin"],
+        }});
+        redact(&mut create);
+        assert_eq!(
+            create["params"]["args"],
+            json!([REDACTED, "new", false, "en_US", REDACTED, "admin"])
+        );
+        let mut exists =
+            json!({"params": {"service": "db", "method": "db_exist", "args": ["prod"]}});
+        redact(&mut exists);
+        assert_eq!(exists["params"]["args"], json!(["prod"]));
+
+        let mut web = json!({"params": {"db": "prod", "login": "admin", "password": "secret"}});
+        redact(&mut web);
+        assert_eq!(
+            web["params"],
+            json!({"db": "prod", "login": REDACTED, "password": REDACTED})
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_request_dump() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let dumps = Arc::new(Mutex::new(vec![]));
+        let sink = dumps.clone();
+        let odoo = server
+            .odoo()
+            .await
+            .unwrap()
+            .with_request_dump(move |dump| sink.lock().unwrap().push(dump.clone()));
+        assert_eq!(odoo.query("res.partner").count().await.unwrap(), 0);
+
+        let dumps = dumps.lock().unwrap();
+        assert_eq!(dumps.len(), 1);
+        assert!(dumps[0].curl.contains("search_count"));
+        assert!(!dumps[0].curl.contains("\"admin\""));
+        assert_eq!(dumps[0].status, 200);
+        assert!(dumps[0].response_body.contains("\"result\":0"));
+    }
+}
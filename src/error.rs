@@ -1,8 +1,289 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::api::RpcError;
+
 #[derive(Debug, Clone)]
-pub struct Error(pub String);
+#[non_exhaustive]
+pub enum Error {
+    /// Transport, protocol or decoding failure on the client side.
+    Request(String),
+    /// The request was never sent: the connection failed, or the client is shut down.
+    NotSent(String),
+    /// Fault raised by the Odoo server, other than the exceptions mapped below.
+    Rpc(RpcError),
+    /// `odoo.exceptions.ValidationError`: a constraint failed; the message is meant for users.
+    Validation(RpcError),
+    /// `odoo.exceptions.AccessError`: the user lacks the rights for the operation.
+    Access(RpcError),
+    /// `odoo.exceptions.UserError`: a business rule refused the operation.
+    User(RpcError),
+    /// `odoo.exceptions.MissingError`: the records were deleted or never existed.
+    Missing(RpcError),
+    /// The deadline attached to the client expired before the call completed.
+    DeadlineExceeded,
+    /// The response did not match the expected type; `path` is filled in strict
+    /// (`path-to-error`) mode.
+    Decode {
+        path: Option<String>,
+        message: String,
+    },
+    /// A record looked up by the client (e.g. by external id) does not exist.
+    NotFound(String),
+    /// A field name or domain path was rejected by client-side validation.
+    InvalidField(String),
+    /// The host URL given to the client is malformed.
+    InvalidUrl(String),
+    /// The response body exceeded the configured maximum size, in bytes.
+    ResponseTooLarge(usize),
+    /// The server or a proxy answered HTTP 429; holds the `Retry-After` delay when given.
+    RateLimited(Option<Duration>),
+    /// The record changed since it was read; `write_date` is its current one.
+    Conflict {
+        model: String,
+        id: u32,
+        write_date: String,
+    },
+    /// A non-JSON 5xx response, e.g. the maintenance page served while Odoo.sh rebuilds.
+    ServerUnavailable {
+        status: u16,
+        retry_after: Option<Duration>,
+    },
+    /// A transferred file doesn't match the SHA-1 of `ir.attachment.checksum`.
+    ChecksumMismatch {
+        attachment_id: u32,
+        expected: String,
+        actual: String,
+    },
+}
 
 impl Error {
-    pub fn message(&self) -> &String {
-        &self.0
+    pub fn message(&self) -> &str {
+        match self {
+            Error::Request(message) | Error::NotSent(message) => message,
+            Error::Rpc(error)
+            | Error::Validation(error)
+            | Error::Access(error)
+            | Error::User(error)
+            | Error::Missing(error) => match &error.data {
+                Some(data) => &data.message,
+                None => &error.message,
+            },
+            Error::DeadlineExceeded => "Deadline exceeded",
+            Error::Decode { message, .. } => message,
+            Error::NotFound(message) => message,
+            Error::InvalidField(message) => message,
+            Error::InvalidUrl(message) => message,
+            Error::ResponseTooLarge(_) => "Response exceeds the maximum size",
+            Error::RateLimited(_) => "Too many requests",
+            Error::ServerUnavailable { .. } => "Server unavailable",
+            Error::Conflict { .. } => "Record was modified concurrently",
+            Error::ChecksumMismatch { .. } => "Checksum mismatch",
+        }
+    }
+
+    /// The server fault, whichever exception class it was mapped to.
+    pub fn rpc(&self) -> Option<&RpcError> {
+        match self {
+            Error::Rpc(error)
+            | Error::Validation(error)
+            | Error::Access(error)
+            | Error::User(error)
+            | Error::Missing(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The server-side Python traceback, when kept with `Odoo::with_tracebacks`.
+    pub fn traceback(&self) -> Option<&str> {
+        self.rpc()?
+            .data
+            .as_ref()
+            .and_then(|data| data.debug.as_deref())
+            .filter(|debug| !debug.is_empty())
+    }
+
+    /// How long to wait before retrying, when the server said so.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::RateLimited(delay) => *delay,
+            Error::ServerUnavailable { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Whether the server could not be reached or is temporarily down, as opposed to a call it
+    /// rejected. Client-side request failures are reported as transport errors too.
+    pub fn is_unreachable(&self) -> bool {
+        matches!(
+            self,
+            Error::Request(_)
+                | Error::NotSent(_)
+                | Error::DeadlineExceeded
+                | Error::RateLimited(_)
+                | Error::ServerUnavailable { .. }
+        )
+    }
+
+    /// Whether the request certainly never reached Odoo: it wasn't sent, or a proxy answered
+    /// with HTTP 429 or a maintenance page (503). Unlike after a timeout, resending
+    /// such a call can't apply it twice.
+    pub fn is_unsent(&self) -> bool {
+        matches!(
+            self,
+            Error::NotSent(_)
+                | Error::RateLimited(_)
+                | Error::ServerUnavailable { status: 503, .. }
+        )
+    }
+
+    /// Whether the server rolled the transaction back because of a concurrent update,
+    /// in which case the call is safe to retry.
+    pub fn is_serialization_failure(&self) -> bool {
+        match self {
+            Error::Rpc(error) => {
+                let name = error.data.as_ref().map(|data| data.name.as_str());
+                matches!(
+                    name,
+                    Some("psycopg2.errors.SerializationFailure")
+                        | Some("psycopg2.extensions.TransactionRollbackError")
+                ) || self.message().contains("could not serialize access")
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<RpcError> for Error {
+    fn from(error: RpcError) -> Error {
+        let name = error.data.as_ref().map(|data| data.name.as_str());
+        match name {
+            Some("odoo.exceptions.ValidationError") => Error::Validation(error),
+            Some("odoo.exceptions.AccessError") => Error::Access(error),
+            Some("odoo.exceptions.UserError") => Error::User(error),
+            Some("odoo.exceptions.MissingError") => Error::Missing(error),
+            _ => Error::Rpc(error),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(RpcError {
+            data: Some(data), ..
+        }) = self.rpc()
+        {
+            write!(f, "{}: {}", data.name, data.message)?;
+            return match self.traceback() {
+                Some(traceback) if f.alternate() => write!(f, "\n{}", traceback),
+                _ => Ok(()),
+            };
+        }
+        match self {
+            Error::Decode {
+                path: Some(path),
+                message,
+            } => write!(f, "{} at {}", message, path),
+            Error::ResponseTooLarge(limit) => {
+                write!(f, "Response exceeds the maximum size of {} bytes", limit)
+            }
+            Error::RateLimited(Some(delay)) => {
+                write!(f, "Too many requests, retry after {}s", delay.as_secs())
+            }
+            Error::ServerUnavailable { status, .. } => {
+                write!(f, "Server unavailable (HTTP {})", status)
+            }
+            Error::Conflict {
+                model,
+                id,
+                write_date,
+            } => write!(
+                f,
+                "{}({}) was modified concurrently at {}",
+                model, id, write_date
+            ),
+            Error::ChecksumMismatch {
+                attachment_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum mismatch for ir.attachment({}): expected {}, got {}",
+                attachment_id, expected, actual
+            ),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{RpcError, RpcErrorData};
+    use crate::error::Error;
+
+    fn rpc_error(name: &str, message: &str) -> Error {
+        RpcError {
+            code: 200,
+            message: String::from("Odoo Server Error"),
+            data: Some(RpcErrorData {
+                name: name.to_string(),
+                message: message.to_string(),
+                debug: None,
+            }),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_exception_classes() {
+        let error = rpc_error("odoo.exceptions.ValidationError", "Invalid email");
+        assert!(matches!(error, Error::Validation(_)));
+        assert_eq!(error.message(), "Invalid email");
+        assert!(matches!(
+            rpc_error("odoo.exceptions.AccessError", "Denied"),
+            Error::Access(_)
+        ));
+        assert!(matches!(
+            rpc_error("odoo.exceptions.MissingError", "Gone"),
+            Error::Missing(_)
+        ));
+        assert!(matches!(
+            rpc_error("builtins.ValueError", "Oops"),
+            Error::Rpc(_)
+        ));
+        assert!(Error::DeadlineExceeded.rpc().is_none());
+    }
+
+    #[test]
+    fn test_traceback() {
+        let mut error = rpc_error("odoo.exceptions.UserError", "Nope");
+        assert_eq!(error.traceback(), None);
+        assert_eq!(format!("{:#}", error), "odoo.exceptions.UserError: Nope");
+
+        if let Error::User(RpcError {
+            data: Some(data), ..
+        }) = &mut error
+        {
+            data.debug = Some(String::from("Traceback (most recent call last):"));
+        }
+        assert_eq!(
+            format!("{:#}", error),
+            "odoo.exceptions.UserError: Nope\nTraceback (most recent call last):"
+        );
+        assert_eq!(error.to_string(), "odoo.exceptions.UserError: Nope");
+    }
+
+    #[test]
+    fn test_is_serialization_failure() {
+        let error = rpc_error(
+            "psycopg2.errors.SerializationFailure",
+            "could not serialize access due to concurrent update",
+        );
+        assert!(error.is_serialization_failure());
+        let error = rpc_error("odoo.exceptions.ValidationError", "Invalid email");
+        assert!(!error.is_serialization_failure());
+        assert!(!Error::DeadlineExceeded.is_serialization_failure());
     }
 }
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::schema::Fields;
+
+const EXPORT_BATCH_SIZE: usize = 1000;
+
+#[derive(Deserialize)]
+struct ExportData {
+    datas: Vec<Vec<Value>>,
+}
+
+/// Column headers as the import wizard expects them: `id` (the external id) first, and
+/// relational fields given by name exported as external ids (`partner_id/id`), so the file
+/// loads into another database. Paths such as `partner_id/name` are kept as given.
+pub fn import_headers(fields: &[&str], model_fields: &Fields) -> Vec<String> {
+    let mut headers = vec![String::from("id")];
+    for field in fields {
+        let relational = model_fields
+            .get(*field)
+            .is_some_and(|info| matches!(info.field_type.as_str(), "many2one" | "many2many"));
+        let header = match *field {
+            "id" | ".id" => continue,
+            field if relational => format!("{}/id", field),
+            field => field.to_string(),
+        };
+        headers.push(header);
+    }
+    headers
+}
+
+impl Odoo {
+    /// Exports the records matching `domain` as CSV with import-compatible headers (see
+    /// `import_headers`), using the server's `export_data` so one2many paths expand to extra
+    /// lines like in the web client. Records without an external id get an `__export__` one.
+    pub async fn export_csv<T: Serialize>(
+        &self,
+        model: &str,
+        domain: T,
+        fields: &[&str],
+    ) -> Result<String, Error> {
+        let model_fields = self.fields_get(model).await?;
+        let headers = import_headers(fields, &model_fields);
+        let mut csv = csv_line(headers.iter().map(String::as_str));
+
+        let mut kwargs = Map::new();
+        kwargs.insert("order".to_string(), Value::from("id"));
+        kwargs.insert("context".to_string(), json!({"active_test": false}));
+        let ids: Vec<u32> = self
+            .call_kw(model, "search", (domain,), kwargs)
+            .await?
+            .result;
+        for ids in ids.chunks(EXPORT_BATCH_SIZE) {
+            let data: ExportData = self
+                .call(model, "export_data", (ids, &headers))
+                .await?
+                .result;
+            for row in data.datas {
+                let cells: Vec<String> = row.iter().map(cell).collect();
+                csv.push_str(&csv_line(cells.iter().map(String::as_str)));
+            }
+        }
+        Ok(csv)
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null | Value::Bool(false) => String::new(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn csv_line<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let mut line = cells
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::export::{cell, csv_line, import_headers};
+    use crate::schema::Fields;
+
+    #[test]
+    fn test_import_headers() {
+        let fields: Fields = serde_json::from_value(json!({
+            "name": {"type": "char"},
+            "parent_id": {"type": "many2one", "relation": "res.partner"},
+            "category_id": {"type": "many2many", "relation": "res.partner.category"},
+            "child_ids": {"type": "one2many", "relation": "res.partner"},
+        }))
+        .unwrap();
+        assert_eq!(
+            import_headers(
+                &[
+                    "id",
+                    "name",
+                    "parent_id",
+                    "category_id",
+                    "child_ids/name",
+                    "parent_id/name"
+                ],
+                &fields
+            ),
+            vec![
+                "id",
+                "name",
+                "parent_id/id",
+                "category_id/id",
+                "child_ids/name",
+                "parent_id/name"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_line() {
+        let cells = [
+            json!("Azure, Inc."),
+            json!(false),
+            json!(12.5),
+            json!("say \"hi\""),
+        ];
+        let cells: Vec<String> = cells.iter().map(cell).collect();
+        assert_eq!(
+            csv_line(cells.iter().map(String::as_str)),
+            "\"Azure, Inc.\",,12.5,\"say \"\"hi\"\"\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+impl Odoo {
+    /// Reads only `fields` of `model` when a `read`/`search_read` doesn't list any, instead of
+    /// every field (`message_ids`, images...). Can be called once per model.
+    pub fn with_default_fields(mut self, model: &str, fields: &[&str]) -> Odoo {
+        Arc::make_mut(&mut self.default_fields).insert(
+            model.to_string(),
+            fields.iter().map(|field| field.to_string()).collect(),
+        );
+        self
+    }
+
+    /// Leaves `fields` (e.g. `image_1920`, `message_follower_ids`) out of every
+    /// `read`/`search_read` that doesn't list its fields and out of the default fields, to
+    /// avoid multi-MB payloads by accident. Calls listing them still get them.
+    pub fn with_field_blacklist(mut self, fields: &[&str]) -> Odoo {
+        Arc::make_mut(&mut self.field_blacklist).extend(fields.iter().map(|f| f.to_string()));
+        self
+    }
+
+    pub(crate) fn has_field_rules(&self, model: &str, method: &str) -> bool {
+        matches!(method, "read" | "search_read")
+            && (self.default_fields.contains_key(model) || !self.field_blacklist.is_empty())
+    }
+
+    /// Fills in the field list of a `read`/`search_read` which doesn't have one, with the
+    /// default fields of `model`, else all its fields but the blacklisted ones.
+    pub(crate) async fn apply_field_rules(
+        &self,
+        model: &str,
+        method: &str,
+        args: &mut Value,
+        kwargs: &mut Option<Map<String, Value>>,
+    ) -> Result<(), Error> {
+        if !self.has_field_rules(model, method) || has_fields(args, kwargs) {
+            return Ok(());
+        }
+        let blacklisted = |field: &String| self.field_blacklist.contains(field);
+        let fields: Vec<String> = match self.default_fields.get(model) {
+            Some(fields) => fields.iter().filter(|f| !blacklisted(f)).cloned().collect(),
+            None => {
+                let all = self.fields_get(model).await?;
+                if !all.keys().any(blacklisted) {
+                    return Ok(());
+                }
+                let mut fields: Vec<String> =
+                    all.keys().filter(|f| !blacklisted(f)).cloned().collect();
+                fields.sort();
+                fields
+            }
+        };
+        apply_default_fields(args, kwargs, &fields);
+        Ok(())
+    }
+}
+
+fn is_unset(fields: Option<&Value>) -> bool {
+    match fields {
+        None | Some(Value::Null) | Some(Value::Bool(false)) => true,
+        Some(Value::Array(fields)) => fields.is_empty(),
+        _ => false,
+    }
+}
+
+/// Whether a `read`/`search_read` call lists its fields, positionally or as keyword.
+fn has_fields(args: &Value, kwargs: &Option<Map<String, Value>>) -> bool {
+    !is_unset(args.get(1))
+        || kwargs
+            .as_ref()
+            .is_some_and(|kwargs| !is_unset(kwargs.get("fields")))
+}
+
+/// Sets `fields` as the field list of a `read`/`search_read` call which has none (or an
+/// empty one), positionally (`(ids, fields)`, `(domain, fields, ...)`) or as keyword.
+fn apply_default_fields(
+    args: &mut Value,
+    kwargs: &mut Option<Map<String, Value>>,
+    fields: &[String],
+) {
+    let args = match args {
+        Value::Array(args) => args,
+        _ => return,
+    };
+    if args.len() > 1 {
+        if is_unset(args.get(1)) {
+            args[1] = Value::from(fields);
+        }
+        return;
+    }
+    match kwargs {
+        Some(kwargs) => {
+            if is_unset(kwargs.get("fields")) {
+                kwargs.insert("fields".to_string(), Value::from(fields));
+            }
+        }
+        None => {
+            // `search_read` without a domain
+            if args.is_empty() {
+                args.push(Value::Array(vec![]));
+            }
+            args.push(Value::from(fields));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map};
+
+    use crate::field_rules::apply_default_fields;
+
+    #[test]
+    fn test_apply_default_fields() {
+        let fields = vec!["name".to_string(), "email".to_string()];
+
+        let mut args = json!([[1, 2]]);
+        let mut kwargs = None;
+        apply_default_fields(&mut args, &mut kwargs, &fields);
+        assert_eq!(args, json!([[1, 2], ["name", "email"]]));
+
+        let mut args = json!([[["is_company", "=", true]]]);
+        let mut kwargs = Some(Map::new());
+        apply_default_fields(&mut args, &mut kwargs, &fields);
+        assert_eq!(kwargs.unwrap()["fields"], json!(["name", "email"]));
+
+        let mut args = json!([[1], ["phone"]]);
+        apply_default_fields(&mut args, &mut None, &fields);
+        assert_eq!(args, json!([[1], ["phone"]]));
+
+        let mut args = json!([[1]]);
+        let mut kwargs = Some(json!({"fields": ["phone"]}).as_object().cloned().unwrap());
+        apply_default_fields(&mut args, &mut kwargs, &fields);
+        assert_eq!(kwargs.unwrap()["fields"], json!(["phone"]));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_default_fields() {
+        use serde_json::Value;
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        server.insert(
+            "res.partner",
+            json!({"name": "Azure Interior", "email": "azure@example.com", "message_ids": [1, 2]}),
+        );
+        let odoo = server
+            .odoo()
+            .await
+            .unwrap()
+            .with_default_fields("res.partner", &["name"]);
+
+        let records: Vec<Map<String, Value>> = odoo
+            .call("res.partner", "search_read", (json!([]),))
+            .await
+            .unwrap()
+            .result;
+        assert_eq!(records[0].keys().collect::<Vec<_>>(), vec!["id", "name"]);
+
+        let records: Vec<Map<String, Value>> =
+            odoo.read("res.partner", &[1], &["email"]).await.unwrap();
+        assert_eq!(records[0]["email"], "azure@example.com");
+        assert!(!records[0].contains_key("name"));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_field_blacklist() {
+        use serde_json::Value;
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        server.insert(
+            "product.product",
+            json!({"name": "Desk", "image_1920": "iVBORw0KGgo=", "list_price": 120.0}),
+        );
+        let odoo = server
+            .odoo()
+            .await
+            .unwrap()
+            .with_field_blacklist(&["image_1920"]);
+
+        let records: Vec<Map<String, Value>> = odoo
+            .call_kw("product.product", "search_read", (json!([]),), Map::new())
+            .await
+            .unwrap()
+            .result;
+        assert!(records[0].contains_key("list_price"));
+        assert!(!records[0].contains_key("image_1920"));
+
+        let records: Vec<Map<String, Value>> = odoo
+            .read("product.product", &[1], &["image_1920"])
+            .await
+            .unwrap();
+        assert_eq!(records[0]["image_1920"], "iVBORw0KGgo=");
+
+        let odoo = odoo.with_default_fields("product.product", &["name", "image_1920"]);
+        let records: Vec<Map<String, Value>> =
+            odoo.read("product.product", &[1], &[]).await.unwrap();
+        assert_eq!(records[0].keys().collect::<Vec<_>>(), vec!["id", "name"]);
+    }
+}
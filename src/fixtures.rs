@@ -0,0 +1,354 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::command::Command;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// One record to create, as read from a fixture file:
+///
+/// ```yaml
+/// - model: res.partner
+///   xmlid: test.azure
+///   values: {name: Azure Interior, is_company: true}
+/// - model: res.partner
+///   xmlid: test.brandon
+///   values: {name: Brandon Freeman}
+///   refs: {parent_id: test.azure, category_id: [base.res_partner_category_0]}
+/// ```
+///
+/// `refs` map relational fields to external ids: a single xmlid for many2one fields, a list for
+/// x2many fields. Xmlids not defined in the fixtures are resolved on the server.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Fixture {
+    pub model: String,
+    pub xmlid: String,
+    #[serde(default)]
+    pub values: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub refs: HashMap<String, FixtureRef>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum FixtureRef {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl FixtureRef {
+    fn xmlids(&self) -> &[String] {
+        match self {
+            FixtureRef::One(xmlid) => std::slice::from_ref(xmlid),
+            FixtureRef::Many(xmlids) => xmlids,
+        }
+    }
+}
+
+/// A set of fixtures, created by `Odoo::load_fixtures` or `TestRecords::load_fixtures`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Fixtures(pub Vec<Fixture>);
+
+impl Fixtures {
+    pub fn from_json(data: &str) -> Result<Fixtures, Error> {
+        serde_json::from_str(data)
+            .map(Fixtures)
+            .map_err(|e| Error::Decode {
+                path: None,
+                message: e.to_string(),
+            })
+    }
+
+    #[cfg(feature = "fixtures-yaml")]
+    pub fn from_yaml(data: &str) -> Result<Fixtures, Error> {
+        serde_yaml::from_str(data)
+            .map(Fixtures)
+            .map_err(|e| Error::Decode {
+                path: None,
+                message: e.to_string(),
+            })
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(&self.0).map_err(|e| Error::Request(e.to_string()))
+    }
+
+    /// Reads a `.json` file, or a `.yaml`/`.yml` file with the `fixtures-yaml` feature.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Fixtures, Error> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| Error::Request(format!("{}: {}", path.display(), e)))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            #[cfg(feature = "fixtures-yaml")]
+            Some("yaml" | "yml") => Fixtures::from_yaml(&data),
+            _ => Fixtures::from_json(&data),
+        }
+    }
+
+    /// Fixtures ordered so that each one comes after the fixtures it refers to.
+    pub fn sorted(&self) -> Result<Vec<&Fixture>, Error> {
+        let defined: HashSet<&str> = self.0.iter().map(|f| f.xmlid.as_str()).collect();
+        let mut done: HashSet<&str> = HashSet::new();
+        let mut sorted = vec![];
+        while sorted.len() < self.0.len() {
+            let before = sorted.len();
+            for fixture in &self.0 {
+                if done.contains(fixture.xmlid.as_str()) {
+                    continue;
+                }
+                let ready = fixture
+                    .refs
+                    .values()
+                    .flat_map(FixtureRef::xmlids)
+                    .all(|xmlid| {
+                        !defined.contains(xmlid.as_str()) || done.contains(xmlid.as_str())
+                    });
+                if ready {
+                    done.insert(&fixture.xmlid);
+                    sorted.push(fixture);
+                }
+            }
+            if sorted.len() == before {
+                let pending: Vec<&str> = self
+                    .0
+                    .iter()
+                    .map(|f| f.xmlid.as_str())
+                    .filter(|xmlid| !done.contains(xmlid))
+                    .collect();
+                return Err(Error::Decode {
+                    path: None,
+                    message: format!("circular references between fixtures {:?}", pending),
+                });
+            }
+        }
+        Ok(sorted)
+    }
+}
+
+impl Odoo {
+    /// Creates the fixtures in dependency order and returns the created ids by xmlid.
+    pub async fn load_fixtures(&self, fixtures: &Fixtures) -> Result<HashMap<String, u32>, Error> {
+        self.create_fixtures(fixtures, &mut vec![]).await
+    }
+
+    async fn create_fixtures(
+        &self,
+        fixtures: &Fixtures,
+        created: &mut Vec<(String, u32)>,
+    ) -> Result<HashMap<String, u32>, Error> {
+        let mut ids: HashMap<String, u32> = HashMap::new();
+        for fixture in fixtures.sorted()? {
+            let mut values = fixture.values.clone();
+            for (field, reference) in &fixture.refs {
+                let mut ref_ids = vec![];
+                for xmlid in reference.xmlids() {
+                    let id = match ids.get(xmlid) {
+                        Some(id) => *id,
+                        None => self.resolve_xmlid(xmlid).await?.1,
+                    };
+                    ref_ids.push(id);
+                }
+                let value = match reference {
+                    FixtureRef::One(_) => Value::from(ref_ids[0]),
+                    FixtureRef::Many(_) => serde_json::to_value([Command::Set(ref_ids)])
+                        .map_err(|e| Error::Request(e.to_string()))?,
+                };
+                values.insert(field.clone(), value);
+            }
+            let id: u32 = self
+                .call(&fixture.model, "create", vec![Value::Object(values)])
+                .await?
+                .result;
+            created.push((fixture.model.clone(), id));
+            ids.insert(fixture.xmlid.clone(), id);
+        }
+        Ok(ids)
+    }
+}
+
+/// Guard deleting the records created through it, so tests against shared databases don't
+/// leak data.
+///
+/// Records are unlinked in reverse creation order, dependants first. Call `cleanup` at the end
+/// of a test to see errors; otherwise cleanup happens on drop, blocking on a separate thread.
+#[derive(Debug)]
+pub struct TestRecords {
+    odoo: Odoo,
+    created: Vec<(String, u32)>,
+}
+
+impl TestRecords {
+    pub fn new(odoo: &Odoo) -> TestRecords {
+        TestRecords {
+            odoo: odoo.clone(),
+            created: vec![],
+        }
+    }
+
+    pub async fn create<T: Serialize>(&mut self, model: &str, values: T) -> Result<u32, Error> {
+        let id: u32 = self.odoo.call(model, "create", vec![values]).await?.result;
+        self.track(model, id);
+        Ok(id)
+    }
+
+    pub async fn load_fixtures(
+        &mut self,
+        fixtures: &Fixtures,
+    ) -> Result<HashMap<String, u32>, Error> {
+        self.odoo.create_fixtures(fixtures, &mut self.created).await
+    }
+
+    /// Registers a record created by other means, e.g. by a button, for deletion.
+    pub fn track(&mut self, model: &str, id: u32) {
+        self.created.push((model.to_string(), id));
+    }
+
+    pub fn created(&self) -> &[(String, u32)] {
+        &self.created
+    }
+
+    /// Deletes the tracked records, skipping those already gone.
+    pub async fn cleanup(&mut self) -> Result<(), Error> {
+        let mut result = Ok(());
+        while let Some((model, id)) = self.created.pop() {
+            // unlink consecutive records of the same model together
+            let mut ids = vec![id];
+            while let Some((_, id)) = self.created.last().filter(|(m, _)| *m == model) {
+                ids.push(*id);
+                self.created.pop();
+            }
+            if let Err(e) = self.unlink(&model, ids).await {
+                result = result.and(Err(e));
+            }
+        }
+        result
+    }
+
+    async fn unlink(&self, model: &str, ids: Vec<u32>) -> Result<(), Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("context".to_string(), json!({"active_test": false}));
+        let existing: Vec<u32> = self
+            .odoo
+            .call_kw(model, "search", (vec![("id", "in", ids)],), kwargs)
+            .await?
+            .result;
+        if !existing.is_empty() {
+            self.odoo
+                .call::<_, bool>(model, "unlink", (existing,))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TestRecords {
+    fn drop(&mut self) {
+        if self.created.is_empty() {
+            return;
+        }
+        let odoo = match self.odoo.with_new_connections() {
+            Ok(odoo) => odoo,
+            Err(_) => return,
+        };
+        let mut records = TestRecords {
+            odoo,
+            created: std::mem::take(&mut self.created),
+        };
+        // a fresh runtime on its own thread, with connections of its own since the current
+        // runtime (e.g. a `#[tokio::test]` one) is blocked until the cleanup is over
+        let _ = std::thread::spawn(move || {
+            if let Ok(runtime) = tokio::runtime::Runtime::new() {
+                let _ = runtime.block_on(records.cleanup());
+            }
+        })
+        .join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{Fixtures, TestRecords};
+    use crate::odoo::Odoo;
+
+    #[test]
+    fn test_fixtures_sorted() {
+        let fixtures = Fixtures::from_json(
+            r#"[
+                {"model": "res.partner", "xmlid": "test.contact",
+                 "values": {"name": "Brandon"}, "refs": {"parent_id": "test.company"}},
+                {"model": "res.partner", "xmlid": "test.company",
+                 "values": {"name": "Azure"}, "refs": {"category_id": ["base.category"]}}
+            ]"#,
+        )
+        .unwrap();
+        let sorted: Vec<&str> = fixtures
+            .sorted()
+            .unwrap()
+            .iter()
+            .map(|f| f.xmlid.as_str())
+            .collect();
+        assert_eq!(sorted, vec!["test.company", "test.contact"]);
+
+        let cycle = Fixtures::from_json(
+            r#"[
+                {"model": "a", "xmlid": "test.a", "refs": {"b_id": "test.b"}},
+                {"model": "b", "xmlid": "test.b", "refs": {"a_id": "test.a"}}
+            ]"#,
+        )
+        .unwrap();
+        assert!(cycle.sorted().is_err());
+    }
+
+    #[test]
+    fn test_records_track() {
+        let odoo = Odoo::new("http://localhost:8069", "odoo");
+        let mut records = TestRecords::new(&odoo);
+        records.track("res.partner", 1);
+        records.track("res.partner", 2);
+        assert_eq!(records.created().len(), 2);
+        // no server to unlink from on drop
+        records.created.clear();
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_records_drop() {
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+
+        // the server runs apart, as a real one would, while this runtime blocks on drop
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async {
+                sender.send(MockServer::start().await.unwrap()).unwrap();
+                std::future::pending::<()>().await;
+            });
+        });
+        let server = receiver.recv().unwrap();
+        server.insert("res.partner", json!({"name": "Kept"}));
+        let odoo = server.odoo().await.unwrap();
+
+        let mut records = TestRecords::new(&odoo);
+        records
+            .create("res.partner", json!({"name": "Azure"}))
+            .await
+            .unwrap();
+        records
+            .create("res.partner", json!({"name": "Deco"}))
+            .await
+            .unwrap();
+        assert_eq!(server.records("res.partner").len(), 3);
+        drop(records);
+        let names: Vec<_> = server
+            .records("res.partner")
+            .iter()
+            .map(|record| record["name"].clone())
+            .collect();
+        assert_eq!(names, vec![json!("Kept")]);
+    }
+}
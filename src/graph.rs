@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::fixtures::{Fixture, FixtureRef, Fixtures};
+use crate::odoo::Odoo;
+use crate::schema::Fields;
+
+/// Fields maintained by the ORM, never exported.
+const MAGIC_FIELDS: [&str; 7] = [
+    "id",
+    "display_name",
+    "create_uid",
+    "create_date",
+    "write_uid",
+    "write_date",
+    "__last_update",
+];
+
+#[derive(Deserialize)]
+struct ModelData {
+    module: String,
+    name: String,
+    res_id: u32,
+}
+
+/// A relational value of a record, pointing to `ids` of `relation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Link {
+    field: String,
+    relation: String,
+    ids: Vec<u32>,
+    kind: LinkKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinkKind {
+    Many2one,
+    Many2many,
+    /// Children, which point back to the record through their inverse many2one.
+    One2many,
+}
+
+struct Pending {
+    model: String,
+    id: u32,
+    values: Map<String, Value>,
+    links: Vec<Link>,
+}
+
+impl Odoo {
+    /// Exports records with the records they link to, following many2one, many2many and
+    /// one2many fields up to `depth` hops, as fixtures that `load_fixtures` replays in
+    /// dependency order on another database.
+    ///
+    /// Records that already have an external id (module data such as countries, currencies
+    /// or the main company) are referred to by it instead of being exported, so they must
+    /// exist on the target. Links to records beyond `depth` without an external id are left
+    /// out. Exported records are named `__export__.<model>_<id>`.
+    pub async fn export_graph(
+        &self,
+        model: &str,
+        ids: &[u32],
+        depth: usize,
+    ) -> Result<Fixtures, Error> {
+        let mut xmlids: HashMap<(String, u32), String> = HashMap::new();
+        let mut visited: HashSet<(String, u32)> = HashSet::new();
+        let mut pending: Vec<Pending> = vec![];
+        let mut frontier: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        frontier.insert(model.to_string(), ids.to_vec());
+
+        for level in 0..=depth {
+            let mut next: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+            for (model, ids) in frontier {
+                let ids: Vec<u32> = ids
+                    .into_iter()
+                    .filter(|id| visited.insert((model.clone(), *id)))
+                    .collect();
+                if ids.is_empty() {
+                    continue;
+                }
+                let existing = self.existing_xmlids(&model, &ids).await?;
+                let ids: Vec<u32> = ids
+                    .into_iter()
+                    .filter(|id| !existing.contains_key(id))
+                    .collect();
+                xmlids.extend(
+                    existing
+                        .into_iter()
+                        .map(|(id, xmlid)| ((model.clone(), id), xmlid)),
+                );
+                if ids.is_empty() {
+                    continue;
+                }
+
+                let fields = self.fields_get(&model).await?;
+                let names = exported_fields(&fields);
+                let records: Vec<Map<String, Value>> =
+                    self.call(&model, "read", (&ids, &names)).await?.result;
+                for record in records {
+                    let id = record.get("id").and_then(Value::as_u64).unwrap_or_default() as u32;
+                    let links = links(&fields, &record);
+                    if level < depth {
+                        for link in &links {
+                            next.entry(link.relation.clone())
+                                .or_default()
+                                .extend(&link.ids);
+                        }
+                    }
+                    let values = record
+                        .into_iter()
+                        .filter(|(field, _)| {
+                            !MAGIC_FIELDS.contains(&field.as_str())
+                                && !links.iter().any(|link| &link.field == field)
+                        })
+                        .collect();
+                    xmlids.insert(
+                        (model.clone(), id),
+                        format!("__export__.{}_{}", model.replace('.', "_"), id),
+                    );
+                    pending.push(Pending {
+                        model: model.clone(),
+                        id,
+                        values,
+                        links,
+                    });
+                }
+            }
+            frontier = next;
+        }
+
+        // links leaving the graph can still point to records with an external id
+        let mut outside: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for record in &pending {
+            for link in record.links.iter().filter(|l| l.kind != LinkKind::One2many) {
+                for id in &link.ids {
+                    if !xmlids.contains_key(&(link.relation.clone(), *id)) {
+                        outside.entry(link.relation.clone()).or_default().push(*id);
+                    }
+                }
+            }
+        }
+        for (model, ids) in outside {
+            for (id, xmlid) in self.existing_xmlids(&model, &ids).await? {
+                xmlids.insert((model.clone(), id), xmlid);
+            }
+        }
+
+        let fixtures = pending
+            .into_iter()
+            .map(|record| {
+                let mut refs = HashMap::new();
+                for link in record.links {
+                    let targets: Vec<String> = link
+                        .ids
+                        .iter()
+                        .filter_map(|id| xmlids.get(&(link.relation.clone(), *id)).cloned())
+                        .collect();
+                    match link.kind {
+                        LinkKind::Many2one => {
+                            if let Some(xmlid) = targets.into_iter().next() {
+                                refs.insert(link.field, FixtureRef::One(xmlid));
+                            }
+                        }
+                        LinkKind::Many2many => {
+                            refs.insert(link.field, FixtureRef::Many(targets));
+                        }
+                        LinkKind::One2many => {}
+                    }
+                }
+                Fixture {
+                    xmlid: xmlids[&(record.model.clone(), record.id)].clone(),
+                    model: record.model,
+                    values: record.values,
+                    refs,
+                }
+            })
+            .collect();
+        Ok(Fixtures(fixtures))
+    }
+
+    async fn existing_xmlids(
+        &self,
+        model: &str,
+        ids: &[u32],
+    ) -> Result<HashMap<u32, String>, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(["module", "name", "res_id"]));
+        let data: Vec<ModelData> = self
+            .call_kw(
+                "ir.model.data",
+                "search_read",
+                (json!([
+                    ["model", "=", model],
+                    ["res_id", "in", ids],
+                    ["module", "!=", "__export__"],
+                ]),),
+                kwargs,
+            )
+            .await?
+            .result;
+        Ok(data
+            .into_iter()
+            .map(|data| (data.res_id, format!("{}.{}", data.module, data.name)))
+            .collect())
+    }
+}
+
+fn exported_fields(fields: &Fields) -> Vec<&str> {
+    let mut names: Vec<&str> = fields
+        .iter()
+        .filter(|(name, info)| {
+            (info.store || info.field_type == "one2many") && !MAGIC_FIELDS.contains(&name.as_str())
+        })
+        .map(|(name, _)| name.as_str())
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+fn links(fields: &Fields, record: &Map<String, Value>) -> Vec<Link> {
+    let mut links = vec![];
+    for (field, value) in record {
+        let info = match fields.get(field) {
+            Some(info) => info,
+            None => continue,
+        };
+        let (relation, kind) = match (&info.relation, info.field_type.as_str()) {
+            (Some(relation), "many2one") => (relation, LinkKind::Many2one),
+            (Some(relation), "many2many") => (relation, LinkKind::Many2many),
+            (Some(relation), "one2many") => (relation, LinkKind::One2many),
+            _ => continue,
+        };
+        // many2one values are read as `[id, display_name]`
+        let ids = match (kind, value) {
+            (LinkKind::Many2one, Value::Array(pair)) => {
+                pair.first().and_then(Value::as_u64).into_iter().collect()
+            }
+            (LinkKind::Many2one, value) => value.as_u64().into_iter().collect(),
+            (_, Value::Array(ids)) => ids.iter().filter_map(Value::as_u64).collect(),
+            _ => vec![],
+        };
+        links.push(Link {
+            field: field.clone(),
+            relation: relation.clone(),
+            ids: ids.into_iter().map(|id| id as u32).collect(),
+            kind,
+        });
+    }
+    links.sort_by(|a, b| a.field.cmp(&b.field));
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::graph::{exported_fields, links, Link, LinkKind};
+    use crate::schema::Fields;
+
+    #[test]
+    fn test_links() {
+        let fields: Fields = serde_json::from_value(json!({
+            "name": {"type": "char"},
+            "display_name": {"type": "char", "store": false},
+            "parent_id": {"type": "many2one", "relation": "res.partner"},
+            "category_id": {"type": "many2many", "relation": "res.partner.category"},
+            "child_ids": {"type": "one2many", "relation": "res.partner", "store": false},
+            "user_id": {"type": "many2one", "relation": "res.users"},
+        }))
+        .unwrap();
+        assert_eq!(
+            exported_fields(&fields),
+            vec!["category_id", "child_ids", "name", "parent_id", "user_id"]
+        );
+
+        let record = json!({
+            "id": 7,
+            "name": "Azure",
+            "parent_id": [1, "Parent"],
+            "category_id": [3, 4],
+            "child_ids": [8],
+            "user_id": false,
+        });
+        let link = |field: &str, relation: &str, ids: Vec<u32>, kind| Link {
+            field: field.to_string(),
+            relation: relation.to_string(),
+            ids,
+            kind,
+        };
+        assert_eq!(
+            links(&fields, record.as_object().unwrap()),
+            vec![
+                link(
+                    "category_id",
+                    "res.partner.category",
+                    vec![3, 4],
+                    LinkKind::Many2many
+                ),
+                link("child_ids", "res.partner", vec![8], LinkKind::One2many),
+                link("parent_id", "res.partner", vec![1], LinkKind::Many2one),
+                link("user_id", "res.users", vec![], LinkKind::Many2one),
+            ]
+        );
+    }
+}
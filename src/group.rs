@@ -0,0 +1,350 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::query::Query;
+
+/// One group returned by `read_group`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// The `__domain` selecting the records of this group.
+    pub domain: Value,
+    /// Number of records in the group (`__count` or `<groupby>_count` on lazy grouping).
+    pub count: u64,
+    /// Groupby values and aggregates, keyed by field name.
+    pub values: Map<String, Value>,
+    /// Records of the group, when expanded by `web_read_group`.
+    pub records: Option<Vec<Value>>,
+}
+
+/// Result of `web_read_group`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebReadGroup {
+    pub groups: Vec<Group>,
+    /// Total number of groups, regardless of `limit`.
+    pub length: u64,
+}
+
+/// Optional arguments of `web_read_group`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebReadGroupOptions {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub orderby: Option<String>,
+    pub lazy: bool,
+    /// Also return the records of each group (up to `expand_limit`), like kanban views.
+    pub expand: bool,
+    pub expand_limit: Option<u32>,
+    pub expand_orderby: Option<String>,
+}
+
+/// Totals computed by `Odoo::aggregate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregates {
+    /// Number of matching records.
+    pub count: u64,
+    values: Map<String, Value>,
+}
+
+impl Aggregates {
+    fn from_group(group: Group, aggregates: &[(&str, &str)]) -> Aggregates {
+        let mut values = Map::new();
+        for (field, function) in aggregates {
+            let alias = aggregate_alias(field, function);
+            let value = group.values.get(&alias).cloned().unwrap_or(Value::Null);
+            values.insert(alias, value);
+        }
+        Aggregates {
+            count: group.count,
+            values,
+        }
+    }
+
+    /// The numeric result of `function` over `field`, `None` when there were no records.
+    pub fn get(&self, field: &str, function: &str) -> Option<f64> {
+        self.value(field, function).and_then(Value::as_f64)
+    }
+
+    /// The raw result, e.g. the date string for `min`/`max` over a date field.
+    pub fn value(&self, field: &str, function: &str) -> Option<&Value> {
+        self.values
+            .get(&aggregate_alias(field, function))
+            .filter(|value| !matches!(value, Value::Null | Value::Bool(false)))
+    }
+}
+
+impl WebReadGroupOptions {
+    fn kwargs(&self) -> Map<String, Value> {
+        let mut kwargs = Map::new();
+        kwargs.insert("lazy".to_string(), Value::Bool(self.lazy));
+        kwargs.insert("expand".to_string(), Value::Bool(self.expand));
+        if let Some(limit) = self.limit {
+            kwargs.insert("limit".to_string(), Value::from(limit));
+        }
+        if let Some(offset) = self.offset {
+            kwargs.insert("offset".to_string(), Value::from(offset));
+        }
+        if let Some(orderby) = &self.orderby {
+            kwargs.insert("orderby".to_string(), Value::from(orderby.as_str()));
+        }
+        if let Some(expand_limit) = self.expand_limit {
+            kwargs.insert("expand_limit".to_string(), Value::from(expand_limit));
+        }
+        if let Some(expand_orderby) = &self.expand_orderby {
+            kwargs.insert(
+                "expand_orderby".to_string(),
+                Value::from(expand_orderby.as_str()),
+            );
+        }
+        kwargs
+    }
+}
+
+impl Group {
+    pub(crate) fn from_map(mut values: Map<String, Value>, groupby: &[&str]) -> Group {
+        let domain = values.remove("__domain").unwrap_or(Value::Array(vec![]));
+        let count_key = groupby
+            .first()
+            .map(|field| format!("{}_count", field.split(':').next().unwrap_or(field)));
+        let count = values
+            .remove("__count")
+            .or_else(|| count_key.and_then(|key| values.remove(&key)))
+            .and_then(|count| count.as_u64())
+            .unwrap_or_default();
+        values.remove("__context");
+        let records = values
+            .remove("__data")
+            .and_then(|mut data| match data.get_mut("records") {
+                Some(Value::Array(records)) => Some(std::mem::take(records)),
+                _ => None,
+            });
+        Group {
+            domain,
+            count,
+            values,
+            records,
+        }
+    }
+
+    /// Drill-down: a query over the records of this group.
+    pub fn records(&self, odoo: &Odoo, model: &str) -> Query {
+        odoo.query(model).domain(&self.domain)
+    }
+}
+
+impl Odoo {
+    pub async fn read_group<T: Serialize>(
+        &self,
+        model: &str,
+        domain: T,
+        fields: &[&str],
+        groupby: &[&str],
+        lazy: bool,
+    ) -> Result<Vec<Group>, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("lazy".to_string(), Value::Bool(lazy));
+        let groups: Vec<Map<String, Value>> = self
+            .call_kw(model, "read_group", (domain, fields, groupby), kwargs)
+            .await?
+            .result;
+        Ok(groups
+            .into_iter()
+            .map(|group| Group::from_map(group, groupby))
+            .collect())
+    }
+
+    /// Computes aggregates over the records matching `domain` with `read_group`, e.g.
+    /// `[("amount_total", "sum"), ("amount_total", "max")]`. Functions are the ones of
+    /// `read_group` (`sum`, `avg`, `min`, `max`, `count`, `count_distinct`, ...).
+    pub async fn aggregate<T: Serialize>(
+        &self,
+        model: &str,
+        domain: T,
+        aggregates: &[(&str, &str)],
+    ) -> Result<Aggregates, Error> {
+        let specs: Vec<String> = aggregates
+            .iter()
+            .map(|(field, function)| {
+                format!(
+                    "{}:{}({})",
+                    aggregate_alias(field, function),
+                    function,
+                    field
+                )
+            })
+            .collect();
+        let specs: Vec<&str> = specs.iter().map(String::as_str).collect();
+        let group = self
+            .read_group(model, domain, &specs, &[], false)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or(Group {
+                domain: Value::Array(vec![]),
+                count: 0,
+                values: Map::new(),
+                records: None,
+            });
+        Ok(Aggregates::from_group(group, aggregates))
+    }
+
+    /// The grouping call used by list/kanban views (Odoo 13 to 17 signature).
+    pub async fn web_read_group<T: Serialize>(
+        &self,
+        model: &str,
+        domain: T,
+        fields: &[&str],
+        groupby: &[&str],
+        options: WebReadGroupOptions,
+    ) -> Result<WebReadGroup, Error> {
+        let mut result: Map<String, Value> = self
+            .call_kw(
+                model,
+                "web_read_group",
+                (domain, fields, groupby),
+                options.kwargs(),
+            )
+            .await?
+            .result;
+        let groups = match result.remove("groups") {
+            Some(Value::Array(groups)) => groups,
+            _ => vec![],
+        };
+        Ok(WebReadGroup {
+            groups: groups
+                .into_iter()
+                .filter_map(|group| match group {
+                    Value::Object(group) => Some(Group::from_map(group, groupby)),
+                    _ => None,
+                })
+                .collect(),
+            length: result
+                .get("length")
+                .and_then(Value::as_u64)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+// the same field can be aggregated several ways, so each result gets its own name
+fn aggregate_alias(field: &str, function: &str) -> String {
+    format!("{}_{}", field, function)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::group::{Aggregates, Group};
+
+    #[test]
+    fn test_group_from_map() {
+        let group = json!({
+            "state": "sale",
+            "state_count": 4,
+            "amount_total": 1250.5,
+            "__domain": [["state", "=", "sale"]],
+            "__context": {"group_by": []},
+        });
+        let group = Group::from_map(group.as_object().unwrap().clone(), &["state"]);
+        assert_eq!(group.count, 4);
+        assert_eq!(group.domain, json!([["state", "=", "sale"]]));
+        assert_eq!(group.values.len(), 2);
+
+        let group = json!({
+            "date_order:month": "May 2024",
+            "__count": 2,
+            "__domain": [],
+            "__data": {"length": 2, "records": [{"id": 1}, {"id": 2}]},
+        });
+        let group = Group::from_map(group.as_object().unwrap().clone(), &["date_order:month"]);
+        assert_eq!(group.count, 2);
+        assert_eq!(group.records.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let group = json!({
+            "__count": 3,
+            "amount_total_sum": 1250.5,
+            "amount_total_max": 900,
+            "date_order_min": "2024-05-01 08:00:00",
+            "__domain": [["state", "=", "sale"]],
+        });
+        let group = Group::from_map(group.as_object().unwrap().clone(), &[]);
+        let aggregates = Aggregates::from_group(
+            group,
+            &[
+                ("amount_total", "sum"),
+                ("amount_total", "max"),
+                ("date_order", "min"),
+                ("margin", "avg"),
+            ],
+        );
+        assert_eq!(aggregates.count, 3);
+        assert_eq!(aggregates.get("amount_total", "sum"), Some(1250.5));
+        assert_eq!(aggregates.get("amount_total", "max"), Some(900.0));
+        assert_eq!(aggregates.get("date_order", "min"), None);
+        assert_eq!(
+            aggregates.value("date_order", "min"),
+            Some(&json!("2024-05-01 08:00:00"))
+        );
+        assert_eq!(aggregates.value("margin", "avg"), None);
+        assert_eq!(aggregates.get("amount_total", "avg"), None);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_web_read_group() {
+        use crate::group::WebReadGroupOptions;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        server.on_call("sale.order", "web_read_group", |args, kwargs| {
+            assert_eq!(args[2], json!(["state"]));
+            assert_eq!(kwargs["expand"], true);
+            assert_eq!(kwargs["expand_limit"], 1);
+            assert_eq!(kwargs["limit"], 2);
+            Ok(json!({
+                "groups": [
+                    {
+                        "state": "sale",
+                        "state_count": 3,
+                        "__domain": [["state", "=", "sale"]],
+                        "__data": {"length": 3, "records": [{"id": 7, "name": "S00007"}]},
+                    },
+                    {"state": "draft", "state_count": 1, "__domain": [["state", "=", "draft"]]},
+                ],
+                "length": 5,
+            }))
+        });
+        let odoo = server.odoo().await.unwrap();
+
+        let options = WebReadGroupOptions {
+            limit: Some(2),
+            expand: true,
+            expand_limit: Some(1),
+            ..Default::default()
+        };
+        let result = odoo
+            .web_read_group(
+                "sale.order",
+                json!([]),
+                &["amount_total"],
+                &["state"],
+                options,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.length, 5);
+        assert_eq!(result.groups.len(), 2);
+        assert_eq!(result.groups[0].count, 3);
+        assert_eq!(
+            result.groups[0].records,
+            Some(vec![json!({"id": 7, "name": "S00007"})])
+        );
+        assert_eq!(result.groups[1].records, None);
+        assert_eq!(result.groups[1].values["state"], "draft");
+    }
+}
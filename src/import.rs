@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+/// Outcome of `Odoo::load`. The import is all-or-nothing: when any row fails, `ids` is empty
+/// and nothing was saved.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ImportResult {
+    #[serde(default, deserialize_with = "deserialize_ids")]
+    pub ids: Vec<u32>,
+    #[serde(default)]
+    pub messages: Vec<ImportMessage>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMessageType {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ImportMessage {
+    #[serde(rename = "type")]
+    pub message_type: ImportMessageType,
+    pub message: String,
+    /// Index of the record the message is about.
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub record: Option<usize>,
+    /// Input rows of that record (several when it has one2many lines).
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub rows: Option<RowRange>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub field: Option<String>,
+}
+
+/// Inclusive range of input rows.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowRange {
+    pub from: usize,
+    pub to: usize,
+}
+
+fn deserialize_ids<'de, D>(data: D) -> Result<Vec<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(deserialize_odoo_optional(data)?.unwrap_or_default())
+}
+
+impl ImportResult {
+    pub fn is_success(&self) -> bool {
+        !self.ids.is_empty() && self.errors().next().is_none()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ImportMessage> {
+        self.messages
+            .iter()
+            .filter(|message| message.message_type == ImportMessageType::Error)
+    }
+
+    /// Indices of the input rows that caused errors, in order.
+    pub fn failed_rows(&self) -> Vec<usize> {
+        let mut rows = BTreeSet::new();
+        for error in self.errors() {
+            match (error.rows, error.record) {
+                (Some(range), _) => rows.extend(range.from..=range.to),
+                (None, Some(record)) => {
+                    rows.insert(record);
+                }
+                (None, None) => {}
+            }
+        }
+        rows.into_iter().collect()
+    }
+
+    /// Splits `rows` into those that imported cleanly and those that failed, so the former
+    /// can be loaded again on their own while the latter are fixed.
+    pub fn split_rows<T: Clone>(&self, rows: &[T]) -> (Vec<T>, Vec<T>) {
+        let failed = self.failed_rows();
+        let (failed, ok): (Vec<_>, Vec<_>) = rows
+            .iter()
+            .enumerate()
+            .partition(|(index, _)| failed.binary_search(index).is_ok());
+        (
+            ok.into_iter().map(|(_, row)| row.clone()).collect(),
+            failed.into_iter().map(|(_, row)| row.clone()).collect(),
+        )
+    }
+}
+
+impl Odoo {
+    /// Imports `rows` like the import wizard, with `fields` as column headers (`id`,
+    /// `partner_id/id`, `line_ids/name`, ...).
+    pub async fn load(
+        &self,
+        model: &str,
+        fields: &[&str],
+        rows: &[Vec<String>],
+    ) -> Result<ImportResult, Error> {
+        Ok(self.call(model, "load", (fields, rows)).await?.result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::import::{ImportMessageType, ImportResult};
+
+    #[test]
+    fn test_import_result() {
+        let result: ImportResult = serde_json::from_value(json!({
+            "ids": false,
+            "messages": [
+                {
+                    "type": "error",
+                    "message": "No matching record found for name 'Nope'",
+                    "record": 1,
+                    "rows": {"from": 1, "to": 2},
+                    "field": "partner_id",
+                },
+                {"type": "warning", "message": "Unknown column", "field": false},
+                {"type": "error", "message": "Missing required value", "record": 4},
+            ],
+            "nextrow": false,
+        }))
+        .unwrap();
+        assert!(!result.is_success());
+        assert_eq!(result.messages[1].message_type, ImportMessageType::Warning);
+        assert_eq!(result.messages[1].field, None);
+        assert_eq!(result.failed_rows(), vec![1, 2, 4]);
+
+        let rows = vec!["a", "b", "c", "d", "e", "f"];
+        assert_eq!(
+            result.split_rows(&rows),
+            (vec!["a", "d", "f"], vec!["b", "c", "e"])
+        );
+
+        let result: ImportResult =
+            serde_json::from_value(json!({"ids": [7, 8], "messages": []})).unwrap();
+        assert!(result.is_success());
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::VecDeque;
+
+/// A piece of a JSON-RPC response found by `ResultScanner`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Scanned {
+    /// One element of a `result` array.
+    Element(Vec<u8>),
+    /// A `result` that is not an array.
+    Result(Vec<u8>),
+    /// The `error` object.
+    Error(Vec<u8>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Container,
+    String,
+    Scalar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Element,
+    Result,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Capture {
+    start: usize,
+    depth: usize,
+    kind: Kind,
+    target: Target,
+}
+
+/// Splits a JSON-RPC response body, fed chunk by chunk, into the elements of its `result`
+/// array without buffering more than the element being read.
+#[derive(Debug, Default)]
+pub(crate) struct ResultScanner {
+    buf: Vec<u8>,
+    pos: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    key_start: usize,
+    key: Vec<u8>,
+    after_colon: bool,
+    in_result_array: bool,
+    capture: Option<Capture>,
+    ready: VecDeque<Scanned>,
+}
+
+impl ResultScanner {
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+        while self.pos < self.buf.len() {
+            let i = self.pos;
+            self.pos += 1;
+            self.scan(i);
+        }
+
+        // drop what was scanned, keeping the element or key being read
+        let keep = match (self.capture, self.in_string && self.depth == 1) {
+            (Some(capture), _) => capture.start,
+            (None, true) => self.key_start,
+            (None, false) => self.pos,
+        };
+        self.buf.drain(..keep);
+        self.pos -= keep;
+        self.key_start = self.key_start.saturating_sub(keep);
+        if let Some(capture) = &mut self.capture {
+            capture.start -= keep;
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Scanned> {
+        self.ready.pop_front()
+    }
+
+    fn scan(&mut self, i: usize) {
+        let b = self.buf[i];
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if b == b'\\' {
+                self.escaped = true;
+            } else if b == b'"' {
+                self.in_string = false;
+                if self.depth == 1 && self.capture.is_none() {
+                    self.key = self.buf[self.key_start + 1..i].to_vec();
+                }
+                if let Some(capture) = self.capture {
+                    if capture.kind == Kind::String && capture.depth == self.depth {
+                        self.finish(i + 1);
+                    }
+                }
+            }
+            return;
+        }
+
+        if let Some(capture) = self.capture {
+            if capture.kind == Kind::Scalar
+                && (b == b',' || b == b'}' || b == b']' || b.is_ascii_whitespace())
+            {
+                self.finish(i);
+            }
+        }
+        if b.is_ascii_whitespace() {
+            return;
+        }
+
+        let value_start = (self.depth == 1 && self.after_colon)
+            || (self.in_result_array
+                && self.depth == 2
+                && self.capture.is_none()
+                && b != b','
+                && b != b']');
+        if value_start && self.capture.is_none() {
+            let target = if self.depth == 1 {
+                self.after_colon = false;
+                match self.key.as_slice() {
+                    b"result" if b == b'[' => {
+                        self.in_result_array = true;
+                        self.depth += 1;
+                        return;
+                    }
+                    b"result" => Some(Target::Result),
+                    b"error" => Some(Target::Error),
+                    _ => None,
+                }
+            } else {
+                Some(Target::Element)
+            };
+            if let Some(target) = target {
+                let kind = match b {
+                    b'{' | b'[' => Kind::Container,
+                    b'"' => Kind::String,
+                    _ => Kind::Scalar,
+                };
+                self.capture = Some(Capture {
+                    start: i,
+                    depth: self.depth,
+                    kind,
+                    target,
+                });
+            }
+        }
+
+        match b {
+            b'"' => {
+                self.in_string = true;
+                self.key_start = i;
+            }
+            b'{' | b'[' => self.depth += 1,
+            b']' if self.in_result_array && self.depth == 2 => {
+                self.in_result_array = false;
+                self.depth -= 1;
+            }
+            b'}' | b']' => {
+                self.depth = self.depth.saturating_sub(1);
+                if let Some(capture) = self.capture {
+                    if capture.kind == Kind::Container && capture.depth == self.depth {
+                        self.finish(i + 1);
+                    }
+                }
+            }
+            b':' if self.depth == 1 => self.after_colon = true,
+            _ => {}
+        }
+    }
+
+    fn finish(&mut self, end: usize) {
+        if let Some(capture) = self.capture.take() {
+            let bytes = self.buf[capture.start..end].to_vec();
+            self.ready.push_back(match capture.target {
+                Target::Element => Scanned::Element(bytes),
+                Target::Result => Scanned::Result(bytes),
+                Target::Error => Scanned::Error(bytes),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::incremental::{ResultScanner, Scanned};
+
+    fn scan(body: &str, chunk_size: usize) -> Vec<Scanned> {
+        let mut scanner = ResultScanner::default();
+        let mut scanned = vec![];
+        for chunk in body.as_bytes().chunks(chunk_size) {
+            scanner.push(chunk);
+            while let Some(item) = scanner.next() {
+                scanned.push(item);
+            }
+        }
+        scanned
+    }
+
+    fn element(json: &str) -> Scanned {
+        Scanned::Element(json.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_result_scanner() {
+        let body = r#"{"jsonrpc": "2.0", "id": 7, "result": [
+            {"id": 1, "name": "A \"quoted\" [name]", "tag_ids": [1, 2]},
+            {"id": 2, "name": "B", "parent_id": [1, "A"]},
+            3, "four", null
+        ]}"#;
+        let expected = vec![
+            element(r#"{"id": 1, "name": "A \"quoted\" [name]", "tag_ids": [1, 2]}"#),
+            element(r#"{"id": 2, "name": "B", "parent_id": [1, "A"]}"#),
+            element("3"),
+            element(r#""four""#),
+            element("null"),
+        ];
+        for chunk_size in [1, 3, 16, body.len()] {
+            assert_eq!(scan(body, chunk_size), expected);
+        }
+
+        let body = r#"{"id": 7, "jsonrpc": "2.0", "error": {"code": 200, "data": {}}}"#;
+        assert_eq!(
+            scan(body, 5),
+            vec![Scanned::Error(br#"{"code": 200, "data": {}}"#.to_vec())]
+        );
+        assert_eq!(
+            scan(r#"{"result": 42}"#, 2),
+            vec![Scanned::Result(b"42".to_vec())]
+        );
+    }
+}
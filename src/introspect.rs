@@ -0,0 +1,181 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+const MODEL_FIELDS: [&str; 5] = ["model", "name", "transient", "state", "info"];
+
+const FIELD_FIELDS: [&str; 11] = [
+    "name",
+    "model",
+    "field_description",
+    "ttype",
+    "relation",
+    "relation_field",
+    "required",
+    "readonly",
+    "store",
+    "state",
+    "help",
+];
+
+/// A record of `ir.model`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct IrModel {
+    pub id: u32,
+    /// Technical name, e.g. `res.partner`.
+    pub model: String,
+    /// Label, e.g. `Contact`.
+    pub name: String,
+    #[serde(default)]
+    pub transient: bool,
+    /// `base` for models defined in code, `manual` for custom ones.
+    pub state: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub info: Option<String>,
+}
+
+/// A record of `ir.model.fields`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct IrModelField {
+    pub id: u32,
+    pub name: String,
+    pub model: String,
+    pub field_description: String,
+    /// Field type, e.g. `many2one`.
+    pub ttype: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub relation: Option<String>,
+    /// Inverse many2one of a one2many field.
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub relation_field: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub readonly: bool,
+    #[serde(default)]
+    pub store: bool,
+    /// `base` for fields defined in code, `manual` for custom (`x_`) ones.
+    pub state: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub help: Option<String>,
+}
+
+impl Odoo {
+    /// Installed models, ordered by technical name.
+    pub async fn list_models(&self) -> Result<Vec<IrModel>, Error> {
+        self.introspect("ir.model", json!([]), &MODEL_FIELDS, "model")
+            .await
+    }
+
+    pub async fn model_info(&self, model: &str) -> Result<IrModel, Error> {
+        self.introspect(
+            "ir.model",
+            json!([["model", "=", model]]),
+            &MODEL_FIELDS,
+            "model",
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::NotFound(format!("Model {} not found", model)))
+    }
+
+    /// Fields of `model` as registered in `ir.model.fields`, ordered by name.
+    pub async fn model_fields(&self, model: &str) -> Result<Vec<IrModelField>, Error> {
+        self.introspect(
+            "ir.model.fields",
+            json!([["model", "=", model]]),
+            &FIELD_FIELDS,
+            "name",
+        )
+        .await
+    }
+
+    /// Relational fields of any model pointing to `model`.
+    pub async fn relations_to(&self, model: &str) -> Result<Vec<IrModelField>, Error> {
+        self.introspect(
+            "ir.model.fields",
+            json!([["relation", "=", model]]),
+            &FIELD_FIELDS,
+            "model, name",
+        )
+        .await
+    }
+
+    async fn introspect<U: DeserializeOwned>(
+        &self,
+        model: &str,
+        domain: Value,
+        fields: &[&str],
+        order: &str,
+    ) -> Result<Vec<U>, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(fields));
+        kwargs.insert("order".to_string(), Value::from(order));
+        Ok(self
+            .call_kw(model, "search_read", (domain,), kwargs)
+            .await?
+            .result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::introspect::IrModelField;
+
+    #[test]
+    fn test_ir_model_field() {
+        let field: IrModelField = serde_json::from_value(json!({
+            "id": 42,
+            "name": "child_ids",
+            "model": "res.partner",
+            "field_description": "Contact",
+            "ttype": "one2many",
+            "relation": "res.partner",
+            "relation_field": "parent_id",
+            "required": false,
+            "readonly": false,
+            "store": true,
+            "state": "base",
+            "help": false,
+        }))
+        .unwrap();
+        assert_eq!(field.relation_field.as_deref(), Some("parent_id"));
+        assert_eq!(field.help, None);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_model_info() {
+        use crate::error::Error;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        server.insert(
+            "ir.model",
+            json!({"model": "res.partner", "name": "Contact", "state": "base"}),
+        );
+        server.insert(
+            "ir.model",
+            json!({"model": "res.company", "name": "Companies", "state": "base"}),
+        );
+
+        let models = odoo.list_models().await.unwrap();
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].model, "res.company");
+        assert_eq!(
+            odoo.model_info("res.partner").await.unwrap().name,
+            "Contact"
+        );
+        assert!(matches!(
+            odoo.model_info("res.nope").await,
+            Err(Error::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+const JOB_FIELDS: [&str; 9] = [
+    "uuid",
+    "name",
+    "state",
+    "model_name",
+    "method_name",
+    "result",
+    "exc_info",
+    "date_created",
+    "date_done",
+];
+
+/// State of an OCA `queue.job`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    WaitDependencies,
+    Pending,
+    Enqueued,
+    Started,
+    Done,
+    Cancelled,
+    Failed,
+    #[serde(other)]
+    Unknown,
+}
+
+impl JobState {
+    /// Whether the job won't run anymore without being requeued.
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            JobState::Done | JobState::Cancelled | JobState::Failed
+        )
+    }
+}
+
+/// A record of `queue.job` (OCA `queue_job` module).
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct QueueJob {
+    pub id: u32,
+    pub uuid: String,
+    pub name: String,
+    pub state: JobState,
+    pub model_name: String,
+    pub method_name: String,
+    /// What the job method returned, as text.
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub result: Option<String>,
+    /// The traceback of a failed job.
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub exc_info: Option<String>,
+    pub date_created: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub date_done: Option<String>,
+}
+
+impl Odoo {
+    /// Calls a server method that delays work with `with_delay()` and returns the uuid of
+    /// the job (`return self.with_delay().heavy_work().uuid`), since the job itself can't
+    /// be sent over RPC.
+    pub async fn enqueue_job<T: Serialize>(
+        &self,
+        model: &str,
+        method: &str,
+        args: T,
+    ) -> Result<String, Error> {
+        Ok(self.call(model, method, args).await?.result)
+    }
+
+    pub async fn queue_job(&self, uuid: &str) -> Result<QueueJob, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(JOB_FIELDS));
+        let jobs: Vec<QueueJob> = self
+            .call_kw("queue.job", "search_read", ([("uuid", "=", uuid)],), kwargs)
+            .await?
+            .result;
+        jobs.into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("queue.job {} not found", uuid)))
+    }
+
+    /// Polls a job until it is done, bounded by the client deadline if one is set. A failed
+    /// or cancelled job is an error carrying its traceback.
+    pub async fn wait_job(&self, uuid: &str, poll_interval: Duration) -> Result<QueueJob, Error> {
+        loop {
+            let job = self.queue_job(uuid).await?;
+            match job.state {
+                JobState::Done => return Ok(job),
+                JobState::Failed | JobState::Cancelled => return Err(job_error(&job)),
+                _ => self.sleep(poll_interval).await?,
+            }
+        }
+    }
+
+    /// Like `wait_job`, waiting for bus notifications on `channels` mentioning the job's uuid
+    /// instead of polling, up to `timeout`. `queue_job` doesn't notify by itself: the job
+    /// has to send one (`self.env["bus.bus"]._sendone(channel, "job_done", {"uuid": ...})`).
+    pub async fn wait_job_notified(
+        &self,
+        uuid: &str,
+        channels: &[&str],
+        timeout: Duration,
+    ) -> Result<QueueJob, Error> {
+        let started = Instant::now();
+        let mut last = 0;
+        loop {
+            // the job may be over before we listen, or only have notified its start
+            let job = self.queue_job(uuid).await?;
+            match job.state {
+                JobState::Done => return Ok(job),
+                JobState::Failed | JobState::Cancelled => return Err(job_error(&job)),
+                _ => {}
+            }
+            let remaining = timeout
+                .checked_sub(started.elapsed())
+                .ok_or(Error::DeadlineExceeded)?;
+            last = self
+                .wait_notification(channels, last, remaining, |n| n.mentions(uuid))
+                .await?
+                .id;
+        }
+    }
+
+    /// Like `wait_job`, decoding the job's result, which `queue_job` stores as the text of
+    /// the returned value (JSON if the method returns `json.dumps(...)`).
+    pub async fn wait_job_result<U: DeserializeOwned>(
+        &self,
+        uuid: &str,
+        poll_interval: Duration,
+    ) -> Result<U, Error> {
+        let job = self.wait_job(uuid, poll_interval).await?;
+        let result = job.result.unwrap_or_else(|| String::from("null"));
+        serde_json::from_str(&result).map_err(|e| Error::Decode {
+            path: None,
+            message: e.to_string(),
+        })
+    }
+
+    /// Puts failed or cancelled jobs back in the queue.
+    pub async fn requeue_jobs(&self, uuids: &[&str]) -> Result<(), Error> {
+        self.job_button(uuids, "requeue").await
+    }
+
+    pub async fn cancel_jobs(&self, uuids: &[&str]) -> Result<(), Error> {
+        self.job_button(uuids, "button_cancelled").await
+    }
+
+    async fn job_button(&self, uuids: &[&str], method: &str) -> Result<(), Error> {
+        let ids: Vec<u32> = self
+            .call_kw(
+                "queue.job",
+                "search",
+                ([("uuid", "in", uuids)],),
+                Map::new(),
+            )
+            .await?
+            .result;
+        let _: Value = self.call("queue.job", method, (ids,)).await?.result;
+        Ok(())
+    }
+}
+
+fn job_error(job: &QueueJob) -> Error {
+    let reason = job
+        .exc_info
+        .as_deref()
+        .and_then(|exc_info| exc_info.trim_end().lines().last())
+        .unwrap_or("no details");
+    let state = match job.state {
+        JobState::Cancelled => "was cancelled",
+        _ => "failed",
+    };
+    Error::Request(format!(
+        "Job {} ({}) {}: {}",
+        job.uuid, job.name, state, reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_wait_job() {
+        use std::time::Duration;
+
+        use serde_json::{json, Value};
+
+        use crate::job::JobState;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let job = |uuid: &str, state: &str, result: Value, exc_info: Value| {
+            json!({
+                "uuid": uuid,
+                "name": "Export partners",
+                "state": state,
+                "model_name": "res.partner",
+                "method_name": "export",
+                "result": result,
+                "exc_info": exc_info,
+                "date_created": "2024-05-01 08:00:00",
+                "date_done": false,
+            })
+        };
+        server.insert(
+            "queue.job",
+            job("a1", "done", json!("{\"count\": 3}"), json!(false)),
+        );
+        server.insert(
+            "queue.job",
+            job(
+                "b2",
+                "failed",
+                json!(false),
+                json!("Traceback (most recent call last):\nValueError: bad input\n"),
+            ),
+        );
+        server.on_call("res.partner", "export_delayed", |_, _| Ok(json!("a1")));
+
+        let uuid = odoo
+            .enqueue_job("res.partner", "export_delayed", ((),))
+            .await
+            .unwrap();
+        let job = odoo
+            .wait_job(&uuid, Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(job.state, JobState::Done);
+        let result: Value = odoo
+            .wait_job_result("a1", Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(result, json!({"count": 3}));
+
+        let error = odoo
+            .wait_job("b2", Duration::from_millis(1))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Job b2 (Export partners) failed: ValueError: bad input"
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_wait_job_notified() {
+        use std::time::Duration;
+
+        use serde_json::json;
+
+        use crate::error::Error;
+        use crate::job::JobState;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let id = server.insert(
+            "queue.job",
+            json!({
+                "uuid": "c3",
+                "name": "Render report",
+                "state": "started",
+                "model_name": "account.move",
+                "method_name": "render",
+                "date_created": "2024-05-01 08:00:00",
+            }),
+        );
+
+        let timeout = Duration::from_millis(200);
+        assert!(matches!(
+            odoo.wait_job_notified("c3", &["jobs"], timeout).await,
+            Err(Error::DeadlineExceeded)
+        ));
+
+        let _: bool = odoo
+            .call("queue.job", "write", ([id], json!({"state": "done"})))
+            .await
+            .unwrap()
+            .result;
+        server.notify("other", json!({"uuid": "c3"}));
+        server.notify(
+            "jobs",
+            json!({"type": "job_done", "payload": {"uuid": "c3"}}),
+        );
+        let job = odoo
+            .wait_job_notified("c3", &["jobs"], timeout)
+            .await
+            .unwrap();
+        assert_eq!(job.state, JobState::Done);
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::odoo::Odoo;
+
+/// A background task pinging the server, started with `Odoo::spawn_keepalive`. Stops when
+/// dropped.
+#[derive(Debug)]
+pub struct KeepAlive {
+    task: JoinHandle<()>,
+    healthy: watch::Receiver<bool>,
+}
+
+impl Odoo {
+    /// Calls `common.version` every `interval` to keep NAT/proxy connections warm, marking the
+    /// server unhealthy as soon as a ping fails or takes longer than `timeout`, and healthy
+    /// again when one succeeds.
+    pub fn spawn_keepalive(&self, interval: Duration, timeout: Duration) -> KeepAlive {
+        let (sender, healthy) = watch::channel(true);
+        let odoo = self.clone();
+        let task = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                let ok = odoo.ping(timeout).await.is_ok();
+                sender.send_if_modified(|healthy| std::mem::replace(healthy, ok) != ok);
+            }
+        });
+        KeepAlive { task, healthy }
+    }
+}
+
+impl KeepAlive {
+    /// Whether the last ping succeeded (true until the first one is done).
+    pub fn is_healthy(&self) -> bool {
+        *self.healthy.borrow()
+    }
+
+    /// Waits until the server turns healthy or unhealthy, and returns the new state.
+    pub async fn changed(&mut self) -> bool {
+        if self.healthy.changed().await.is_err() {
+            return false;
+        }
+        *self.healthy.borrow()
+    }
+
+    /// Stops the pings, like dropping the handle.
+    pub fn stop(self) {}
+}
+
+impl Drop for KeepAlive {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_keepalive() {
+        use std::time::Duration;
+
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let keepalive = odoo.spawn_keepalive(Duration::from_millis(10), Duration::from_secs(1));
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(keepalive.is_healthy());
+
+        let dead = Odoo::new("http://127.0.0.1:1", "mock");
+        let mut keepalive = dead.spawn_keepalive(Duration::from_millis(10), Duration::from_secs(1));
+        let healthy = tokio::time::timeout(Duration::from_secs(5), keepalive.changed())
+            .await
+            .unwrap();
+        assert!(!healthy);
+        assert!(!keepalive.is_healthy());
+        keepalive.stop();
+    }
+}
@@ -1,3 +1,62 @@
+pub mod access;
+pub mod action;
 pub mod api;
+pub mod attachment;
+pub mod audit;
+pub mod builder;
+pub mod bus;
+pub mod cache;
+pub mod checksum;
+pub mod command;
+mod correlation;
+pub mod cron;
+pub mod currency;
+pub mod date;
+pub mod domain;
+pub mod download;
+pub mod dump;
 pub mod error;
+pub mod export;
+mod field_rules;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod group;
+#[cfg(feature = "fixtures")]
+pub mod graph;
+pub mod import;
+mod incremental;
+pub mod job;
+pub mod keepalive;
+pub mod introspect;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod odoo;
+pub mod orm;
+pub mod prefetch;
+pub mod query;
+pub mod queue;
+#[cfg(feature = "recipes")]
+pub mod recipes;
+pub mod report;
+pub mod rest;
+pub mod retry;
+pub mod schema;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod shutdown;
+pub mod snapshot;
+pub mod strict;
+pub mod translation;
+pub mod typecheck;
+pub mod types;
+#[cfg(feature = "tz")]
+pub mod tz;
+pub mod unit_of_work;
+#[cfg(all(unix, feature = "unix-socket"))]
+mod unix;
+pub mod upload;
+pub mod values;
+pub mod vcr;
+pub mod web;
+pub mod wizard;
+pub mod xmlid;
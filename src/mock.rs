@@ -0,0 +1,828 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hyper::header::{CONTENT_TYPE, COOKIE, LOCATION, SET_COOKIE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Server};
+use serde_json::{json, Map, Value};
+use tokio::sync::oneshot;
+
+use crate::checksum::sha1_hex;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+type Handler = Arc<dyn Fn(&[Value], &Map<String, Value>) -> Result<Value, String> + Send + Sync>;
+
+/// In-process stand-in for an Odoo server, for running integration tests offline.
+///
+/// It answers `/jsonrpc` (`common.login`/`authenticate`/`version`, `db.list` and
+/// `execute_kw`), the `/web/session/authenticate` and `/web/session/get_session_info`
+/// routes, `/web/dataset/call_button`, and `/longpolling/poll` (see `notify`).
+///
+/// Models live in memory and support `create`, `read`, `write` (checking `write_date`
+/// against the `__last_update` context), `unlink`, `search`, `search_count`, `search_read`
+/// and `fields_get`; other methods can be provided with `on_call`. `ir.attachment` records
+/// get their `checksum` and `file_size` when created, and their content is served to web
+/// sessions by `/web/content/<id>`. `/report/pdf|html/<name>/<ids>` answers a placeholder
+/// document to web sessions and redirects others to `/web/login`. The server stops when
+/// dropped.
+pub struct MockServer {
+    url: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+struct State {
+    users: Vec<(String, String)>,
+    models: HashMap<String, BTreeMap<u32, Map<String, Value>>>,
+    handlers: HashMap<(String, String), Handler>,
+    notifications: Vec<(Value, Value)>,
+    next_id: u32,
+}
+
+impl MockServer {
+    /// Starts a server on a free local port, with an `admin`/`admin` user (uid 2).
+    pub async fn start() -> Result<MockServer, Error> {
+        let state = Arc::new(Mutex::new(State {
+            users: vec![
+                (String::from("__system__"), String::new()),
+                (String::from("admin"), String::from("admin")),
+            ],
+            models: HashMap::new(),
+            handlers: HashMap::new(),
+            notifications: vec![],
+            next_id: 1,
+        }));
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_conn| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(state.clone(), req))) }
+        });
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .map_err(|e| Error::Request(e.to_string()))?
+            .serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        let (shutdown, stop) = oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            let _ = stop.await;
+        }));
+
+        Ok(MockServer {
+            url,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Logs in as `admin` on the mock server.
+    pub async fn odoo(&self) -> Result<Odoo, Error> {
+        Odoo::new_and_login(&self.url, "mock", "admin", "admin").await
+    }
+
+    /// Adds a user and returns its uid.
+    pub fn add_user(&self, login: &str, password: &str) -> u32 {
+        let mut state = self.state.lock().unwrap();
+        state.users.push((login.to_string(), password.to_string()));
+        state.users.len() as u32
+    }
+
+    /// Stores a record directly, bypassing RPC, and returns its id.
+    pub fn insert(&self, model: &str, values: Value) -> u32 {
+        let values = match values {
+            Value::Object(values) => values,
+            _ => Map::new(),
+        };
+        self.state.lock().unwrap().create(model, values)
+    }
+
+    pub fn records(&self, model: &str) -> Vec<Map<String, Value>> {
+        let state = self.state.lock().unwrap();
+        state
+            .models
+            .get(model)
+            .map(|records| records.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Answers `model.method` with `handler(args, kwargs)`; an `Err` is returned to the client
+    /// as a `UserError`.
+    pub fn on_call<F>(&self, model: &str, method: &str, handler: F)
+    where
+        F: Fn(&[Value], &Map<String, Value>) -> Result<Value, String> + Send + Sync + 'static,
+    {
+        self.state
+            .lock()
+            .unwrap()
+            .handlers
+            .insert((model.to_string(), method.to_string()), Arc::new(handler));
+    }
+
+    /// Sends a bus notification, delivered by `/longpolling/poll`.
+    pub fn notify(&self, channel: &str, message: Value) {
+        self.state
+            .lock()
+            .unwrap()
+            .notifications
+            .push((Value::from(channel), message));
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<State>>,
+    req: hyper::Request<Body>,
+) -> Result<hyper::Response<Body>, Infallible> {
+    let path = req.uri().path().to_string();
+    let session_uid: Option<u32> = req
+        .headers()
+        .get(COOKIE)
+        .and_then(|cookie| cookie.to_str().ok())
+        .and_then(|cookie| cookie.split("session_id=mock").nth(1))
+        .and_then(|rest| rest.split(';').next())
+        .and_then(|uid| uid.parse().ok())
+        .filter(|&uid| state.lock().unwrap().user(uid).is_some());
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    if let Some(id) = path.strip_prefix("/web/content/") {
+        // the attachments are private, as if they were linked to records
+        let content = id.parse::<u32>().ok().filter(|_| session_uid.is_some());
+        let content = content.and_then(|id| {
+            let state = state.lock().unwrap();
+            let attachment = state.models.get("ir.attachment")?.get(&id)?;
+            attachment_content(attachment)
+        });
+        let response = match content {
+            Some(content) => hyper::Response::new(Body::from(content)),
+            None => hyper::Response::builder()
+                .status(hyper::StatusCode::NOT_FOUND)
+                .body(Body::empty())
+                .unwrap_or_default(),
+        };
+        return Ok(response);
+    }
+    if let Some(report) = path.strip_prefix("/report/") {
+        // like Odoo, an `auth='user'` http route sends anonymous visitors to the login page
+        let response = match (session_uid, report.split_once('/')) {
+            (None, _) => hyper::Response::builder()
+                .status(hyper::StatusCode::SEE_OTHER)
+                .header(LOCATION, format!("/web/login?redirect={}", path))
+                .body(Body::empty()),
+            (Some(_), Some(("pdf", report))) => hyper::Response::builder()
+                .header(CONTENT_TYPE, "application/pdf")
+                .body(Body::from(format!("%PDF-1.4 {}", report))),
+            (Some(_), Some(("html", report))) => hyper::Response::builder()
+                .header(CONTENT_TYPE, "text/html; charset=utf-8")
+                .body(Body::from(format!("<html><body>{}</body></html>", report))),
+            _ => hyper::Response::builder()
+                .status(hyper::StatusCode::NOT_FOUND)
+                .body(Body::empty()),
+        };
+        return Ok(response.unwrap_or_default());
+    }
+    if path == "/web/login" {
+        let response = hyper::Response::builder()
+            .header(CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::from(
+                "<html><body><form class=\"oe_login_form\"></form></body></html>",
+            ));
+        return Ok(response.unwrap_or_default());
+    }
+    let request: Value = serde_json::from_slice(&body).unwrap_or_default();
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let params = request.get("params").cloned().unwrap_or_default();
+
+    let mut session = None;
+    let result = match path.as_str() {
+        "/jsonrpc" => {
+            let service = params.get("service").and_then(Value::as_str);
+            let method = params.get("method").and_then(Value::as_str);
+            let args = match params.get("args") {
+                Some(Value::Array(args)) => args.as_slice(),
+                _ => &[],
+            };
+            let mut state = state.lock().unwrap();
+            match (service, method) {
+                (Some("common"), Some("version")) => Ok(json!({
+                    "server_version": "16.0",
+                    "server_version_info": [16, 0, 0, "final", 0, ""],
+                    "protocol_version": 1,
+                })),
+                (Some("common"), Some("login" | "authenticate")) => {
+                    let uid = state.authenticate(str_arg(args, 1), str_arg(args, 2));
+                    Ok(uid.map_or(Value::Bool(false), Value::from))
+                }
+                (Some("db"), Some("list")) => Ok(json!(["mock"])),
+                (Some("object"), Some("execute_kw" | "execute")) => state.execute_kw(args),
+                _ => Err(rpc_error(
+                    "builtins.KeyError",
+                    &format!("Unknown service method {:?}.{:?}", service, method),
+                )),
+            }
+        }
+        "/web/session/authenticate" => {
+            let login = params
+                .get("login")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let password = params
+                .get("password")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            match state.lock().unwrap().authenticate(login, password) {
+                Some(uid) => {
+                    session = Some(format!("session_id=mock{}; Path=/; HttpOnly", uid));
+                    Ok(json!({"uid": uid, "db": params.get("db"), "username": login}))
+                }
+                None => Err(rpc_error("odoo.exceptions.AccessDenied", "Access Denied")),
+            }
+        }
+        "/web/dataset/call_button" => {
+            let mut state = state.lock().unwrap();
+            let password = session_uid
+                .and_then(|uid| state.user(uid))
+                .map(|(_, password)| password.clone());
+            let args = [
+                json!("mock"),
+                json!(session_uid),
+                json!(password),
+                params.get("model").cloned().unwrap_or_default(),
+                params.get("method").cloned().unwrap_or_default(),
+                params.get("args").cloned().unwrap_or_default(),
+                params.get("kwargs").cloned().unwrap_or_default(),
+            ];
+            match session_uid {
+                Some(_) => state.execute_kw(&args),
+                None => Err(rpc_error(
+                    "odoo.http.SessionExpiredException",
+                    "Session expired",
+                )),
+            }
+        }
+        "/web/session/get_session_info" => {
+            let state = state.lock().unwrap();
+            let login = session_uid
+                .and_then(|uid| state.user(uid))
+                .map(|(login, _)| login.clone());
+            Ok(json!({
+                "uid": session_uid.map_or(Value::Bool(false), Value::from),
+                "db": "mock",
+                "username": login,
+                "is_admin": session_uid == Some(2),
+                "user_context": {"lang": "en_US", "tz": "UTC", "uid": session_uid},
+                "server_version": "16.0",
+                "server_version_info": [16, 0, 0, "final", 0, ""],
+                "user_companies": {
+                    "current_company": 1,
+                    "allowed_companies": {"1": {"id": 1, "name": "Mock Company"}},
+                },
+            }))
+        }
+        "/longpolling/poll" => {
+            let notifications = state.lock().unwrap().poll(&params);
+            if notifications.is_empty() {
+                // stands for the server holding the request until something happens
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            Ok(Value::Array(notifications))
+        }
+        _ => Err(rpc_error("werkzeug.exceptions.NotFound", "404: Not Found")),
+    };
+
+    let body = match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(error) => json!({"jsonrpc": "2.0", "id": id, "error": error}),
+    };
+    let mut response = hyper::Response::builder().header(CONTENT_TYPE, "application/json");
+    if let Some(session) = session {
+        response = response.header(SET_COOKIE, session);
+    }
+    Ok(response
+        .body(Body::from(body.to_string()))
+        .unwrap_or_default())
+}
+
+// the decoded `datas` of an attachment
+fn attachment_content(values: &Map<String, Value>) -> Option<Vec<u8>> {
+    STANDARD.decode(values.get("datas")?.as_str()?).ok()
+}
+
+fn rpc_error(name: &str, message: &str) -> Value {
+    json!({
+        "code": 200,
+        "message": "Odoo Server Error",
+        "data": {"name": name, "message": message, "debug": ""},
+    })
+}
+
+fn str_arg(args: &[Value], index: usize) -> &str {
+    args.get(index).and_then(Value::as_str).unwrap_or_default()
+}
+
+impl State {
+    fn authenticate(&self, login: &str, password: &str) -> Option<u32> {
+        self.users
+            .iter()
+            .position(|(l, p)| l == login && p == password && l != "__system__")
+            .map(|index| index as u32 + 1)
+    }
+
+    // uids are 1-based indexes into `users`
+    fn user(&self, uid: u32) -> Option<&(String, String)> {
+        self.users.get((uid as usize).checked_sub(1)?)
+    }
+
+    fn poll(&self, params: &Value) -> Vec<Value> {
+        let last = params
+            .get("last")
+            .and_then(Value::as_u64)
+            .unwrap_or_default();
+        let channels = params.get("channels").cloned().unwrap_or_default();
+        let channels = channels.as_array().map(Vec::as_slice).unwrap_or_default();
+        self.notifications
+            .iter()
+            .enumerate()
+            .map(|(index, notification)| (index as u64 + 1, notification))
+            .filter(|(id, (channel, _))| *id > last && channels.contains(channel))
+            .map(|(id, (channel, message))| json!({"id": id, "channel": channel, "message": message}))
+            .collect()
+    }
+
+    fn create(&mut self, model: &str, mut values: Map<String, Value>) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        values.insert("id".to_string(), Value::from(id));
+        if model == "ir.attachment" {
+            let content = attachment_content(&values).unwrap_or_default();
+            values.insert("checksum".to_string(), Value::from(sha1_hex(&content)));
+            values.insert("file_size".to_string(), Value::from(content.len()));
+        }
+        self.models
+            .entry(model.to_string())
+            .or_default()
+            .insert(id, values);
+        id
+    }
+
+    fn execute_kw(&mut self, args: &[Value]) -> Result<Value, Value> {
+        let uid = args.get(1).and_then(Value::as_u64).unwrap_or_default();
+        let password = str_arg(args, 2);
+        let authorized = u32::try_from(uid)
+            .ok()
+            .and_then(|uid| self.user(uid))
+            .is_some_and(|(_, p)| p == password);
+        if !authorized {
+            return Err(rpc_error("odoo.exceptions.AccessDenied", "Access Denied"));
+        }
+
+        let model = str_arg(args, 3).to_string();
+        let method = str_arg(args, 4).to_string();
+        let margs = match args.get(5) {
+            Some(Value::Array(margs)) => margs.clone(),
+            _ => vec![],
+        };
+        let kwargs = match args.get(6) {
+            Some(Value::Object(kwargs)) => kwargs.clone(),
+            _ => Map::new(),
+        };
+        if let Some(handler) = self.handlers.get(&(model.clone(), method.clone())) {
+            return handler(&margs, &kwargs)
+                .map_err(|message| rpc_error("odoo.exceptions.UserError", &message));
+        }
+
+        // positional arguments, falling back to keyword arguments
+        let arg = |index: usize, name: &str| -> Value {
+            margs
+                .get(index)
+                .or_else(|| kwargs.get(name))
+                .cloned()
+                .unwrap_or(Value::Null)
+        };
+        match method.as_str() {
+            "create" => match arg(0, "vals_list") {
+                Value::Array(vals_list) => Ok(Value::from(
+                    vals_list
+                        .into_iter()
+                        .map(|values| self.create(&model, object(values)))
+                        .collect::<Vec<u32>>(),
+                )),
+                values => Ok(Value::from(self.create(&model, object(values)))),
+            },
+            "read" => Ok(Value::from(self.read(
+                &model,
+                &ids(&arg(0, "ids")),
+                &arg(1, "fields"),
+            ))),
+            "write" => {
+                let values = object(arg(1, "vals"));
+                let records = self.models.entry(model.clone()).or_default();
+                // Odoo's concurrency check, on the `write_date` given per record
+                let last_update = kwargs
+                    .get("context")
+                    .and_then(|context| context.get("__last_update"))
+                    .cloned()
+                    .unwrap_or_default();
+                let modified = ids(&arg(0, "ids")).into_iter().find(|id| {
+                    let expected = last_update.get(format!("{},{}", model, id));
+                    let current = records.get(id).and_then(|record| record.get("write_date"));
+                    match (
+                        expected.and_then(Value::as_str),
+                        current.and_then(Value::as_str),
+                    ) {
+                        (Some(expected), Some(current)) => current > expected,
+                        _ => false,
+                    }
+                });
+                if let Some(id) = modified {
+                    return Err(rpc_error(
+                        "odoo.exceptions.ValidationError",
+                        &format!(
+                            "A document was modified since you last viewed it ({}:{})",
+                            model, id
+                        ),
+                    ));
+                }
+                for id in ids(&arg(0, "ids")) {
+                    if let Some(record) = records.get_mut(&id) {
+                        record.extend(values.clone());
+                    }
+                }
+                Ok(Value::Bool(true))
+            }
+            "unlink" => {
+                let records = self.models.entry(model.clone()).or_default();
+                for id in ids(&arg(0, "ids")) {
+                    records.remove(&id);
+                }
+                Ok(Value::Bool(true))
+            }
+            "search" => Ok(Value::from(self.search(
+                &model,
+                &arg(0, "domain"),
+                &arg(1, "offset"),
+                &arg(2, "limit"),
+                &arg(3, "order"),
+            ))),
+            "search_count" => Ok(Value::from(
+                self.search(
+                    &model,
+                    &arg(0, "domain"),
+                    &Value::Null,
+                    &Value::Null,
+                    &Value::Null,
+                )
+                .len(),
+            )),
+            "search_read" => {
+                let found = self.search(
+                    &model,
+                    &arg(0, "domain"),
+                    &arg(2, "offset"),
+                    &arg(3, "limit"),
+                    &arg(4, "order"),
+                );
+                Ok(Value::from(self.read(&model, &found, &arg(1, "fields"))))
+            }
+            "fields_get" => Ok(Value::Object(self.fields_get(&model))),
+            _ => Err(rpc_error(
+                "builtins.AttributeError",
+                &format!(
+                    "The method '{}' does not exist on the model '{}'",
+                    method, model
+                ),
+            )),
+        }
+    }
+
+    fn read(&self, model: &str, ids: &[u32], fields: &Value) -> Vec<Value> {
+        let fields: Vec<&str> = match fields {
+            Value::Array(fields) => fields.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        let records = match self.models.get(model) {
+            Some(records) => records,
+            None => return vec![],
+        };
+        ids.iter()
+            .filter_map(|id| records.get(id))
+            .map(|record| {
+                if fields.is_empty() {
+                    return Value::Object(record.clone());
+                }
+                let mut values = Map::new();
+                values.insert("id".to_string(), record["id"].clone());
+                for field in &fields {
+                    let value = record.get(*field).cloned().unwrap_or(Value::Bool(false));
+                    values.insert(field.to_string(), value);
+                }
+                Value::Object(values)
+            })
+            .collect()
+    }
+
+    fn search(
+        &self,
+        model: &str,
+        domain: &Value,
+        offset: &Value,
+        limit: &Value,
+        order: &Value,
+    ) -> Vec<u32> {
+        let domain = match domain {
+            Value::Array(domain) => domain.as_slice(),
+            _ => &[],
+        };
+        let mut records: Vec<&Map<String, Value>> = self
+            .models
+            .get(model)
+            .map(|records| {
+                records
+                    .values()
+                    .filter(|record| matches_domain(record, domain))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(order) = order.as_str() {
+            for spec in order.split(',').rev() {
+                let mut parts = spec.split_whitespace();
+                let field = parts.next().unwrap_or("id");
+                let desc = parts.next().is_some_and(|d| d.eq_ignore_ascii_case("desc"));
+                records.sort_by(|a, b| {
+                    let ordering = compare(
+                        a.get(field).unwrap_or(&Value::Null),
+                        b.get(field).unwrap_or(&Value::Null),
+                    )
+                    .unwrap_or(Ordering::Equal);
+                    if desc {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                });
+            }
+        }
+        records
+            .into_iter()
+            .skip(offset.as_u64().unwrap_or_default() as usize)
+            .take(limit.as_u64().map_or(usize::MAX, |limit| limit as usize))
+            .filter_map(|record| record.get("id").and_then(Value::as_u64))
+            .map(|id| id as u32)
+            .collect()
+    }
+
+    // field types are guessed from the stored values
+    fn fields_get(&self, model: &str) -> Map<String, Value> {
+        let mut fields = Map::new();
+        for record in self.models.get(model).into_iter().flat_map(|r| r.values()) {
+            for (name, value) in record {
+                let field_type = match value {
+                    Value::Bool(_) => "boolean",
+                    Value::Number(n) if n.is_f64() => "float",
+                    Value::Number(_) if name == "id" || !name.ends_with("_id") => "integer",
+                    Value::Number(_) => "many2one",
+                    Value::Array(_) if name.ends_with("_ids") => "many2many",
+                    _ => "char",
+                };
+                fields
+                    .entry(name.clone())
+                    .or_insert_with(|| json!({"type": field_type, "string": name}));
+            }
+        }
+        fields
+    }
+}
+
+fn object(values: Value) -> Map<String, Value> {
+    match values {
+        Value::Object(values) => values,
+        _ => Map::new(),
+    }
+}
+
+fn ids(ids: &Value) -> Vec<u32> {
+    match ids {
+        Value::Array(ids) => ids
+            .iter()
+            .filter_map(Value::as_u64)
+            .map(|id| id as u32)
+            .collect(),
+        Value::Number(id) => id.as_u64().map(|id| vec![id as u32]).unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+/// Evaluates a domain in prefix notation, with implicit `&` between top-level terms.
+fn matches_domain(record: &Map<String, Value>, domain: &[Value]) -> bool {
+    let mut terms = domain.iter();
+    let mut matches = true;
+    while terms.len() > 0 {
+        matches &= eval_term(record, &mut terms);
+    }
+    matches
+}
+
+fn eval_term(record: &Map<String, Value>, terms: &mut std::slice::Iter<Value>) -> bool {
+    match terms.next() {
+        Some(Value::String(op)) if op == "&" => {
+            let left = eval_term(record, terms);
+            eval_term(record, terms) && left
+        }
+        Some(Value::String(op)) if op == "|" => {
+            let left = eval_term(record, terms);
+            eval_term(record, terms) || left
+        }
+        Some(Value::String(op)) if op == "!" => !eval_term(record, terms),
+        Some(Value::Array(leaf)) if leaf.len() == 3 => {
+            let field = leaf[0].as_str().unwrap_or_default();
+            let operator = leaf[1].as_str().unwrap_or_default();
+            let value = record.get(field).unwrap_or(&Value::Bool(false));
+            eval_leaf(value, operator, &leaf[2])
+        }
+        _ => true,
+    }
+}
+
+fn eval_leaf(value: &Value, operator: &str, operand: &Value) -> bool {
+    // many2one values may be stored as `[id, name]`
+    let value = match value {
+        Value::Array(pair) if pair.len() == 2 && pair[1].is_string() => &pair[0],
+        value => value,
+    };
+    let contains = |operand: &Value| match operand {
+        Value::Array(items) => items.iter().any(|item| same(value, item)),
+        operand => same(value, operand),
+    };
+    let like = |case_sensitive: bool| match (value.as_str(), operand.as_str()) {
+        (Some(value), Some(operand)) if case_sensitive => value.contains(operand),
+        (Some(value), Some(operand)) => value.to_lowercase().contains(&operand.to_lowercase()),
+        _ => false,
+    };
+    match operator {
+        "=" | "==" => same(value, operand),
+        "!=" | "<>" => !same(value, operand),
+        "in" => contains(operand),
+        "not in" => !contains(operand),
+        "<" => compare(value, operand) == Some(Ordering::Less),
+        ">" => compare(value, operand) == Some(Ordering::Greater),
+        "<=" => matches!(
+            compare(value, operand),
+            Some(Ordering::Less | Ordering::Equal)
+        ),
+        ">=" => matches!(
+            compare(value, operand),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+        "like" => like(true),
+        "ilike" => like(false),
+        "not like" => !like(true),
+        "not ilike" => !like(false),
+        _ => false,
+    }
+}
+
+// `False` and `None` are interchangeable, as with Odoo's empty fields
+fn same(value: &Value, operand: &Value) -> bool {
+    match (value, operand) {
+        (Value::Null | Value::Bool(false), Value::Null | Value::Bool(false)) => true,
+        _ => compare(value, operand) == Some(Ordering::Equal),
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Null | Value::Bool(false), _) => Some(Ordering::Less),
+        (_, Value::Null | Value::Bool(false)) => Some(Ordering::Greater),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use crate::error::Error;
+    use crate::mock::MockServer;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Partner {
+        id: u32,
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_mock_server() {
+        let server = MockServer::start().await.unwrap();
+        server.insert("res.partner", json!({"name": "Azure", "is_company": true}));
+        let odoo = server.odoo().await.unwrap();
+
+        let id: u32 = odoo
+            .call(
+                "res.partner",
+                "create",
+                vec![json!({"name": "Brandon", "is_company": false})],
+            )
+            .await
+            .unwrap()
+            .result;
+        let partners: Vec<Partner> = odoo
+            .query("res.partner")
+            .domain(vec![("is_company", "=", false)])
+            .fields(&["name"])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(
+            partners,
+            vec![Partner {
+                id,
+                name: "Brandon".to_string()
+            }]
+        );
+
+        let _: bool = odoo
+            .call(
+                "res.partner",
+                "write",
+                (vec![id], json!({"name": "Brandon F."})),
+            )
+            .await
+            .unwrap()
+            .result;
+        assert_eq!(
+            server.records("res.partner")[1]["name"],
+            json!("Brandon F.")
+        );
+        assert_eq!(odoo.query("res.partner").count().await.unwrap(), 2);
+        let streamed: Vec<Partner> = odoo
+            .query("res.partner")
+            .fields(&["name"])
+            .stream_incremental()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(streamed.len(), 2);
+
+        server.on_call("res.partner", "action_archive", |_, _| {
+            Err("nope".to_string())
+        });
+        let result = odoo
+            .call::<_, Value>("res.partner", "action_archive", (vec![id],))
+            .await;
+        assert!(
+            matches!(result, Err(Error::User(e)) if e.data.as_ref().unwrap().message == "nope")
+        );
+
+        let wrong = crate::odoo::Odoo::new_and_login(server.url(), "mock", "admin", "x").await;
+        assert!(wrong.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_session() {
+        let server = MockServer::start().await.unwrap();
+        server.insert("ir.attachment", json!({"name": "a.txt", "datas": "YQ=="}));
+        let client = reqwest::Client::new();
+        for cookie in ["session_id=mock0", "session_id=mock9"] {
+            let info: Value = client
+                .post(format!("{}/web/session/get_session_info", server.url()))
+                .header(reqwest::header::COOKIE, cookie)
+                .json(&json!({"jsonrpc": "2.0", "method": "call", "params": {}}))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            assert_eq!(info["result"]["uid"], json!(false));
+            let content = client
+                .get(format!("{}/web/content/1", server.url()))
+                .header(reqwest::header::COOKIE, cookie)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(content.status(), reqwest::StatusCode::NOT_FOUND);
+        }
+    }
+}
@@ -1,12 +1,36 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+#[cfg(all(unix, feature = "unix-socket"))]
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
-use serde::de::DeserializeOwned;
-use serde::{Deserialize, Deserializer, Serialize};
+use futures_util::stream::{self, Stream};
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE, COOKIE, LOCATION, SET_COOKIE, USER_AGENT};
+use reqwest::{ResponseBuilderExt, StatusCode};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Number, Value};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
-use crate::api::{Request, Response};
+use crate::api::{Envelope, Request, Response, RpcError, WebRequest};
+use crate::audit::{redact, touched_ids, AuditEvent, AuditHook, AuditOutcome, AuditSink};
+use crate::builder::{OdooBuilder, RedirectPolicy};
+use crate::domain::{chunk_in_clause, DEFAULT_IN_CHUNK_SIZE};
+use crate::dump::{curl_command, DumpHook, RequestDump};
 use crate::error::Error;
+use crate::incremental::{ResultScanner, Scanned};
+use crate::query::SearchReadOptions;
+use crate::retry::{retry_after, RetryPolicy};
+use crate::schema::Fields;
+use crate::shutdown::{InFlight, Lifecycle};
+use crate::strict::UnknownFields;
+use crate::typecheck::MismatchPolicy;
+use crate::vcr::Cassette;
 
+#[deprecated(
+    since = "2.0.0",
+    note = "maps every error to None and hides type mismatches; use deserialize_odoo_optional"
+)]
 pub fn deserialize_odoo_nullable<'de, D, E>(data: D) -> Result<Option<E>, D::Error>
 where
     D: Deserializer<'de>,
@@ -20,31 +44,164 @@ where
     }
 }
 
-#[derive(Debug)]
+/// Maps Odoo's `false` (and `null`) to `None`, propagating any other deserialization error.
+pub fn deserialize_odoo_optional<'de, D, E>(data: D) -> Result<Option<E>, D::Error>
+where
+    D: Deserializer<'de>,
+    E: Deserialize<'de>,
+{
+    match Value::deserialize(data)? {
+        Value::Bool(false) | Value::Null => Ok(None),
+        value => E::deserialize(value).map(Some).map_err(D::Error::custom),
+    }
+}
+
+/// Serializes `None` as `false`, which is what `create`/`write` expect for empty values.
+pub fn serialize_odoo_nullable<S, E>(value: &Option<E>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    E: Serialize,
+{
+    match value {
+        Some(value) => value.serialize(serializer),
+        None => serializer.serialize_bool(false),
+    }
+}
+
+/// Both directions of the `false`-means-empty convention, for
+/// `#[serde(with = "async_odoors::odoo::odoo_nullable")]` on `Option` fields.
+pub mod odoo_nullable {
+    pub use super::deserialize_odoo_optional as deserialize;
+    pub use super::serialize_odoo_nullable as serialize;
+}
+
+#[derive(Debug, Clone)]
 pub struct Odoo {
     host: String,
     database: String,
     uid: Option<u32>,
+    login: Option<String>,
     password: Option<String>,
+    session_id: Option<String>,
+    deadline: Option<Instant>,
+    pub(crate) retry_policy: RetryPolicy,
+    field_validation: bool,
+    in_chunk_size: usize,
+    max_response_size: Option<usize>,
+    tracebacks: bool,
+    client: reqwest::Client,
+    builder: Option<Arc<OdooBuilder>>,
+    redirect_policy: RedirectPolicy,
+    request_dump: Option<DumpHook>,
+    audit: Option<AuditHook>,
+    pub(crate) cassette: Option<Cassette>,
+    pub(crate) correlation_header: Option<String>,
+    pub(crate) correlation_id: Option<String>,
+    user_agent: Option<String>,
+    client_id: Option<String>,
+    pub(crate) lifecycle: Arc<Lifecycle>,
+    pub(crate) draining: bool,
+    concurrency: Option<Arc<Semaphore>>,
+    pub(crate) default_fields: Arc<HashMap<String, Vec<String>>>,
+    pub(crate) field_blacklist: Arc<Vec<String>>,
+    pub(crate) response_validation: Option<MismatchPolicy>,
+    pub(crate) unknown_fields: UnknownFields,
+    #[cfg(all(unix, feature = "unix-socket"))]
+    unix_socket: Option<Arc<PathBuf>>,
+    pub(crate) fields_cache: Arc<Mutex<HashMap<String, Arc<Fields>>>>,
+}
+
+/// A call in flight, see `Odoo::begin_call`.
+pub(crate) struct CallGuard {
+    _in_flight: InFlight,
+    _permit: Option<OwnedSemaphorePermit>,
 }
 
 impl Odoo {
+    /// `host` is the base URL of the instance and may include a path prefix for installs
+    /// behind a path-routing proxy (`https://erp.example.com/odoo`); every route, from
+    /// `/jsonrpc` to `/web/...`, is joined under it.
+    ///
+    /// `host` isn't validated; a malformed one only fails on the first call. Prefer `try_new`.
     pub fn new(host: &str, database: &str) -> Odoo {
         Odoo {
-            host: host.to_string(),
+            host: host.trim_end_matches('/').to_string(),
             database: database.to_string(),
             uid: None,
+            login: None,
             password: None,
+            session_id: None,
+            deadline: None,
+            retry_policy: RetryPolicy::default(),
+            field_validation: false,
+            in_chunk_size: DEFAULT_IN_CHUNK_SIZE,
+            max_response_size: None,
+            tracebacks: false,
+            client: default_client(),
+            builder: None,
+            redirect_policy: RedirectPolicy::default(),
+            request_dump: None,
+            audit: None,
+            cassette: None,
+            correlation_header: None,
+            correlation_id: None,
+            user_agent: None,
+            client_id: None,
+            lifecycle: Arc::default(),
+            draining: false,
+            concurrency: None,
+            default_fields: Arc::default(),
+            field_blacklist: Arc::default(),
+            response_validation: None,
+            unknown_fields: UnknownFields::default(),
+            #[cfg(all(unix, feature = "unix-socket"))]
+            unix_socket: None,
+            fields_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Like `new`, checking that `host` is an absolute `http`/`https` URL.
+    pub fn try_new(host: &str, database: &str) -> Result<Odoo, Error> {
+        Ok(Odoo::new(&normalize_host(host)?, database))
+    }
+
+    pub(crate) fn with_client(mut self, client: reqwest::Client, redirect: RedirectPolicy) -> Odoo {
+        self.client = client;
+        self.redirect_policy = redirect;
+        self
+    }
+
+    pub(crate) fn with_builder(mut self, builder: OdooBuilder) -> Odoo {
+        self.builder = Some(Arc::new(builder));
+        self
+    }
+
+    /// A clone with the same settings but its own connection pool, for use on another
+    /// runtime: pooled connections are driven by the runtime that opened them.
+    #[cfg(feature = "fixtures")]
+    pub(crate) fn with_new_connections(&self) -> Result<Odoo, Error> {
+        let client = match &self.builder {
+            Some(builder) => builder.client()?,
+            None => default_client(),
+        };
+        let mut odoo = self.clone();
+        odoo.client = client;
+        Ok(odoo)
+    }
+
+    #[cfg(all(unix, feature = "unix-socket"))]
+    pub(crate) fn with_unix_socket(mut self, path: Option<PathBuf>) -> Odoo {
+        self.unix_socket = path.map(Arc::new);
+        self
+    }
+
     pub async fn new_and_login(
         host: &str,
         database: &str,
         login: &str,
         password: &str,
     ) -> Result<Odoo, Error> {
-        let mut odoo = Odoo::new(host, database);
+        let mut odoo = Odoo::try_new(host, database)?;
         odoo.login(login, password).await?;
         Ok(odoo)
     }
@@ -55,50 +212,274 @@ impl Odoo {
             Some("authenticate"),
             (self.database.as_str(), login, password, ""),
         );
-        let response: Response<u32> = self
-            .send(&request, None)
-            .await
-            .map_err(|e| Error(e.to_string()))?;
+        let response: Response<u32> = self.send(&request, None).await?;
         self.uid = Some(response.result);
+        self.login = Some(login.to_string());
         self.password = Some(password.to_string());
         Ok(response.result)
     }
 
+    /// Authenticates against `/web/session/authenticate`, keeping the session cookie so
+    /// web controllers can be called with `call_controller`.
+    pub async fn web_login(&mut self, login: &str, password: &str) -> Result<u32, Error> {
+        let request = WebRequest::new(serde_json::json!({
+            "db": self.database,
+            "login": login,
+            "password": password,
+        }));
+        let _call = self.begin_call().await?;
+        let resp = self.post("web/session/authenticate", &request).await?;
+        let session_id = session_cookie(&resp);
+        let response: Response<Value> = self.read_response(resp).await?;
+        let uid = response
+            .result
+            .get("uid")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| Error::Request(String::from("Authentication failed")))?;
+        self.uid = Some(uid as u32);
+        self.login = Some(login.to_string());
+        self.password = Some(password.to_string());
+        self.session_id = session_id;
+        Ok(uid as u32)
+    }
+
+    /// Returns a client for another database on the same server, with the same transport
+    /// settings, logged in again with the same credentials (through the web session if this
+    /// one uses it), since uids differ between databases.
+    pub async fn switch_database(&self, database: &str) -> Result<Odoo, Error> {
+        let mut odoo = self.clone();
+        odoo.database = database.to_string();
+        odoo.uid = None;
+        odoo.session_id = None;
+        odoo.fields_cache = Arc::new(Mutex::new(HashMap::new()));
+        if let (Some(login), Some(password)) = (self.login.clone(), self.password.clone()) {
+            match self.session_id {
+                Some(_) => odoo.web_login(&login, &password).await?,
+                None => odoo.login(&login, &password).await?,
+            };
+        }
+        Ok(odoo)
+    }
+
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    /// The uid of the logged-in user, if any.
+    pub fn uid(&self) -> Option<u32> {
+        self.uid
+    }
+
     pub async fn start(&self) -> Result<HashMap<String, String>, Error> {
         let request: Request<()> = Request::new("common", Some("start"), ());
 
-        let response: Response<HashMap<String, String>> = self
-            .send(&request, Some("start"))
-            .await
-            .map_err(|e| Error(e.to_string()))?;
+        let response: Response<HashMap<String, String>> =
+            self.send(&request, Some("start")).await?;
 
         Ok(response.result)
     }
 
+    /// Sets how calls rolled back by concurrent updates, rate-limited (HTTP 429) or hitting a
+    /// maintenance page (HTTP 503) are retried; `RetryPolicy::none()` disables retries.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Odoo {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Debug aid: check requested field names and domain paths against `fields_get` before
+    /// sending, so typos fail with `Error::InvalidField` instead of silently. Covers `read`,
+    /// `search_read`, `search` and `search_count`, however they are called.
+    pub fn with_field_validation(mut self, enabled: bool) -> Odoo {
+        self.field_validation = enabled;
+        self
+    }
+
+    /// Maximum number of ids sent in a single `in` clause by `search_read`; larger lists are
+    /// split into several requests whose results are merged, unless `limit`, `offset` or
+    /// `order` is given.
+    pub fn with_in_chunk_size(mut self, in_chunk_size: usize) -> Odoo {
+        self.in_chunk_size = in_chunk_size.max(1);
+        self
+    }
+
+    /// Aborts reading any response larger than `bytes` with `Error::ResponseTooLarge`, so a
+    /// query missing its limit can't exhaust memory. `call_kw_stream` doesn't buffer and is
+    /// not limited.
+    pub fn with_max_response_size(mut self, bytes: usize) -> Odoo {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Keeps the server's Python traceback (`data.debug`) in server errors, available through
+    /// `Error::traceback` and the alternate `{:#}` format. Off by default to keep errors terse.
+    pub fn with_tracebacks(mut self, enabled: bool) -> Odoo {
+        self.tracebacks = enabled;
+        self
+    }
+
+    /// Identifies the application in the `User-Agent` of every request, as
+    /// `{app}/{version} async-odoors/{crate version}`, for hosting providers to attribute traffic.
+    pub fn with_user_agent(mut self, app: &str, version: &str) -> Odoo {
+        self.user_agent = Some(format!(
+            "{}/{} async-odoors/{}",
+            app,
+            version,
+            env!("CARGO_PKG_VERSION")
+        ));
+        self
+    }
+
+    /// Sends `client` in an `X-Client` header on every request, e.g. the instance or tenant
+    /// of the application.
+    pub fn with_client_header(mut self, client: &str) -> Odoo {
+        self.client_id = Some(client.to_string());
+        self
+    }
+
+    /// Caps the requests in flight at `max` across this client and all its clones; further
+    /// calls wait for a slot, up to the deadline if one is set. Keeps fan-outs such as
+    /// `join_all` from exhausting the Odoo workers.
+    pub fn with_max_concurrency(mut self, max: usize) -> Odoo {
+        self.concurrency = Some(Arc::new(Semaphore::new(max)));
+        self
+    }
+
+    /// Debug aid: hands every HTTP exchange to `hook` as a redacted curl command plus the raw
+    /// response body, e.g. `.with_request_dump(|dump| eprintln!("{}", dump))`. Responses are
+    /// buffered to be dumped, including those of `call_kw_stream`.
+    pub fn with_request_dump<F>(mut self, hook: F) -> Odoo
+    where
+        F: Fn(&RequestDump) + Send + Sync + 'static,
+    {
+        self.request_dump = Some(DumpHook(Arc::new(hook)));
+        self
+    }
+
+    /// Hands an `AuditEvent` to `sink` after every `call`/`call_kw`, with the values of
+    /// `redacted` fields (e.g. `password`) masked in the payload.
+    pub fn with_audit_sink<S>(mut self, sink: S, redacted: &[&str]) -> Odoo
+    where
+        S: AuditSink + 'static,
+    {
+        self.audit = Some(AuditHook {
+            sink: Arc::new(sink),
+            redacted: Arc::new(redacted.iter().map(|field| field.to_string()).collect()),
+        });
+        self
+    }
+
+    /// Returns a handle on the same session whose calls all share the given deadline.
+    /// Retries, pagination loops and bulk helpers run through it abort with
+    /// `Error::DeadlineExceeded` once the budget is spent.
+    pub fn with_deadline(&self, deadline: Instant) -> Odoo {
+        let mut odoo = self.clone();
+        odoo.deadline = Some(match self.deadline {
+            Some(current) => current.min(deadline),
+            None => deadline,
+        });
+        odoo
+    }
+
+    pub fn with_timeout(&self, timeout: Duration) -> Odoo {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Time left before the deadline, if one is set.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    pub(crate) fn check_deadline(&self) -> Result<(), Error> {
+        match self.remaining() {
+            Some(remaining) if remaining.is_zero() => Err(Error::DeadlineExceeded),
+            _ => Ok(()),
+        }
+    }
+
+    /// Sleeps for `duration`, failing right away if that would overrun the deadline.
+    pub(crate) async fn sleep(&self, duration: Duration) -> Result<(), Error> {
+        match self.remaining() {
+            Some(remaining) if remaining < duration => Err(Error::DeadlineExceeded),
+            _ => {
+                tokio::time::sleep(duration).await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Cheap readiness check: calls `common.version` within `timeout` and returns the latency.
+    pub async fn ping(&self, timeout: Duration) -> Result<Duration, Error> {
+        let started = Instant::now();
+        tokio::time::timeout(timeout, self.call_service("common", "version", ()))
+            .await
+            .map_err(|_| Error::Request(format!("Ping timed out after {:?}", timeout)))??;
+        Ok(started.elapsed())
+    }
+
     pub async fn call<T: Serialize, U: DeserializeOwned>(
         &self,
         model: &str,
         method: &str,
         args: T,
     ) -> Result<Response<U>, Error> {
-        let password = self.password.as_ref().unwrap().as_str();
+        self.call_object(model, method, args, None).await
+    }
 
-        let request = Request::new(
-            "object",
-            None,
-            (
-                self.database.as_str(),
-                self.uid,
-                password,
-                model,
-                method,
-                args,
-            ),
-        );
+    /// Calls any `(service, method, args)` triple on `/jsonrpc` and returns the raw result,
+    /// e.g. `odoo.call_service("db", "list", ())`.
+    pub async fn call_service<T: Serialize>(
+        &self,
+        service: &str,
+        method: &str,
+        args: T,
+    ) -> Result<Value, Error> {
+        let request = Request::new(service, Some(method), args);
 
-        self.send(&request, None)
-            .await
-            .map_err(|e| Error(e.to_string()))
+        let response: Response<Value> = self.send(&request, None).await?;
+
+        Ok(response.result)
+    }
+
+    /// Databases on the server, or `None` when listing is disabled (`list_db = False`), in
+    /// which case the database name has to be asked for.
+    pub async fn list_databases(&self) -> Result<Option<Vec<String>>, Error> {
+        match self.call_service("db", "list", ()).await {
+            Ok(databases) => {
+                serde_json::from_value(databases)
+                    .map(Some)
+                    .map_err(|e| Error::Decode {
+                        path: None,
+                        message: e.to_string(),
+                    })
+            }
+            Err(e)
+                if e.rpc()
+                    .and_then(|e| e.data.as_ref())
+                    .is_some_and(|data| data.name == "odoo.exceptions.AccessDenied") =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Calls a custom `type="json"` controller route with the current web session and
+    /// returns the unwrapped `result`.
+    pub async fn call_controller<T: Serialize, U: DeserializeOwned>(
+        &self,
+        route: &str,
+        params: T,
+    ) -> Result<U, Error> {
+        let request = WebRequest::new(params);
+        let _call = self.begin_call().await?;
+        let resp = self.post(route, &request).await?;
+        let response: Response<U> = self.read_response(resp).await?;
+        Ok(response.result)
     }
 
     pub async fn search_read<T: Serialize, U: DeserializeOwned>(
@@ -109,10 +490,23 @@ impl Odoo {
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> Result<Response<U>, Error> {
-        let password = self.password.as_ref().unwrap().as_str();
-        let fields = fields.unwrap_or(vec![]);
+        let mut options = SearchReadOptions::new().fields(&fields.unwrap_or_default());
+        options.limit = limit;
+        options.offset = offset;
+        self.search_read_with(model, domain, options).await
+    }
 
-        let mut values = Map::new();
+    /// `search_read` with any keyword arguments (`order`, `context`, `load`, ...).
+    pub async fn search_read_with<T: Serialize, U: DeserializeOwned>(
+        &self,
+        model: &str,
+        domain: T,
+        options: SearchReadOptions,
+    ) -> Result<Response<U>, Error> {
+        let domain = serde_json::to_value(&domain).map_err(|e| Error::Request(e.to_string()))?;
+        let fields: Vec<&str> = options.fields.iter().map(String::as_str).collect();
+
+        let mut values = options.kwargs;
         values.insert(
             "fields".to_string(),
             Value::Array(
@@ -122,59 +516,722 @@ impl Odoo {
                     .collect(),
             ),
         );
-        if limit.is_some() {
-            values.insert(
-                "limit".to_string(),
-                Value::Number(Number::from(limit.unwrap())),
-            );
+        if let Some(limit) = options.limit {
+            values.insert("limit".to_string(), Value::Number(Number::from(limit)));
         }
-        if offset.is_some() {
-            values.insert(
-                "offset".to_string(),
-                Value::Number(Number::from(offset.unwrap())),
-            );
+        if let Some(offset) = options.offset {
+            values.insert("offset".to_string(), Value::Number(Number::from(offset)));
         }
 
-        let request = Request::new(
-            "object",
-            None,
-            (
-                self.database.as_str(),
-                self.uid,
-                password,
-                model,
-                "search_read",
-                vec![domain],
-                values,
-            ),
-        );
+        // huge `in` lists are split and merged; paging or ordering a merged result would be
+        // meaningless, each chunk being paged and ordered on its own
+        let ordered = values.contains_key("order");
+        if options.limit.is_none() && options.offset.is_none() && !ordered {
+            if let Some(domains) = chunk_in_clause(&domain, self.in_chunk_size) {
+                return self.search_read_chunks(model, domains, values).await;
+            }
+        }
 
-        self.send(&request, None)
+        self.call_kw(model, "search_read", vec![domain], values)
             .await
-            .map_err(|e| Error(e.to_string()))
+    }
+
+    async fn search_read_chunks<U: DeserializeOwned>(
+        &self,
+        model: &str,
+        domains: Vec<Value>,
+        values: Map<String, Value>,
+    ) -> Result<Response<U>, Error> {
+        let mut seen = HashSet::new();
+        let mut records = vec![];
+        let mut id = 0;
+        for domain in domains {
+            let response: Response<Vec<Value>> = self
+                .call_kw(model, "search_read", vec![domain], values.clone())
+                .await?;
+            id = response.id;
+            records.extend(
+                response
+                    .result
+                    .into_iter()
+                    .filter(|record| seen.insert(record.get("id").and_then(Value::as_u64))),
+            );
+        }
+        let result = self.decode_value(Value::Array(records))?;
+        Ok(Response { id, result })
+    }
+
+    /// Like `call`, with keyword arguments (`context`, `limit`, ...) passed to `execute_kw`.
+    pub async fn call_kw<T: Serialize, U: DeserializeOwned>(
+        &self,
+        model: &str,
+        method: &str,
+        args: T,
+        kwargs: Map<String, Value>,
+    ) -> Result<Response<U>, Error> {
+        self.call_object(model, method, args, Some(kwargs)).await
+    }
+
+    async fn call_object<T: Serialize, U: DeserializeOwned>(
+        &self,
+        model: &str,
+        method: &str,
+        args: T,
+        mut kwargs: Option<Map<String, Value>>,
+    ) -> Result<Response<U>, Error> {
+        let strict = self.unknown_fields == UnknownFields::Deny;
+        let checked = self.validates_response(method) || strict;
+        let validated = self.field_validation
+            && matches!(method, "read" | "search_read" | "search" | "search_count");
+        if !self.has_field_rules(model, method) && !checked && !validated {
+            return self.dispatch_object(model, method, args, kwargs).await;
+        }
+        let mut args = serde_json::to_value(args).map_err(|e| Error::Request(e.to_string()))?;
+        if validated {
+            self.validate_call(model, method, &args, &kwargs).await?;
+        }
+        self.apply_field_rules(model, method, &mut args, &mut kwargs)
+            .await?;
+        if !checked {
+            return self.dispatch_object(model, method, args, kwargs).await;
+        }
+        let Response { id, result } = self.dispatch_object(model, method, args, kwargs).await?;
+        if self.validates_response(method) {
+            self.validate_response(model, &result).await?;
+        }
+        let result = self.decode_value(result)?;
+        Ok(Response { id, result })
+    }
+
+    pub(crate) async fn dispatch_object<T: Serialize, U: DeserializeOwned>(
+        &self,
+        model: &str,
+        method: &str,
+        args: T,
+        kwargs: Option<Map<String, Value>>,
+    ) -> Result<Response<U>, Error> {
+        match &self.audit {
+            Some(hook) => self.audited(hook, model, method, args, kwargs).await,
+            None => self.execute_object(model, method, args, kwargs).await,
+        }
+    }
+
+    async fn execute_object<T: Serialize, U: DeserializeOwned>(
+        &self,
+        model: &str,
+        method: &str,
+        args: T,
+        kwargs: Option<Map<String, Value>>,
+    ) -> Result<Response<U>, Error> {
+        let password = self.password.as_ref().unwrap().as_str();
+        let database = self.database.as_str();
+
+        match kwargs {
+            Some(kwargs) => {
+                let params = (database, self.uid, password, model, method, args, kwargs);
+                self.send(&Request::new("object", None, params), None).await
+            }
+            None => {
+                let params = (database, self.uid, password, model, method, args);
+                self.send(&Request::new("object", None, params), None).await
+            }
+        }
+    }
+
+    async fn audited<T: Serialize, U: DeserializeOwned>(
+        &self,
+        hook: &AuditHook,
+        model: &str,
+        method: &str,
+        args: T,
+        kwargs: Option<Map<String, Value>>,
+    ) -> Result<Response<U>, Error> {
+        let timestamp = SystemTime::now();
+        let args = serde_json::to_value(args).map_err(|e| Error::Request(e.to_string()))?;
+        let mut payload =
+            serde_json::json!({"args": args, "kwargs": kwargs.clone().unwrap_or_default()});
+        let response: Result<Response<Value>, Error> =
+            self.execute_object(model, method, &args, kwargs).await;
+
+        redact(&mut payload, &hook.redacted);
+        hook.sink.record(&AuditEvent {
+            timestamp,
+            uid: self.uid,
+            model: model.to_string(),
+            method: method.to_string(),
+            ids: touched_ids(method, &args, response.as_ref().ok().map(|r| &r.result)),
+            payload,
+            outcome: match &response {
+                Ok(_) => AuditOutcome::Success,
+                Err(e) => AuditOutcome::Failure(e.to_string()),
+            },
+        });
+
+        let Response { id, result } = response?;
+        let result = self.decode_value(result)?;
+        Ok(Response { id, result })
+    }
+
+    /// Like `call_kw` for methods returning a list, yielding the elements as the response body
+    /// downloads instead of buffering the whole payload first.
+    pub fn call_kw_stream<T: Serialize, U: DeserializeOwned>(
+        &self,
+        model: &str,
+        method: &str,
+        args: T,
+        kwargs: Map<String, Value>,
+    ) -> impl Stream<Item = Result<U, Error>> {
+        let call = serde_json::to_value(args)
+            .map(|args| (args, Some(kwargs)))
+            .map_err(|e| Error::Request(e.to_string()));
+        let (model, method) = (model.to_string(), method.to_string());
+        let state = (Some(call), None, ResultScanner::default());
+        let odoo = self.clone();
+        stream::try_unfold(state, move |(mut call, mut resp, mut scanner)| {
+            let (odoo, model, method) = (odoo.clone(), model.clone(), method.clone());
+            async move {
+                loop {
+                    match scanner.next() {
+                        Some(Scanned::Element(bytes)) | Some(Scanned::Result(bytes)) => {
+                            let item = decode(&bytes)?;
+                            return Ok(Some((item, (call, resp, scanner))));
+                        }
+                        Some(Scanned::Error(bytes)) => {
+                            let error: RpcError = decode(&bytes)?;
+                            return Err(odoo.rpc_error(error));
+                        }
+                        None => {}
+                    }
+                    if let Some(pending) = call.take() {
+                        let (mut args, mut kwargs) = pending?;
+                        odoo.apply_field_rules(&model, &method, &mut args, &mut kwargs)
+                            .await?;
+                        let request = Request::new(
+                            "object",
+                            None,
+                            (
+                                &odoo.database,
+                                odoo.uid,
+                                odoo.password.as_deref().unwrap_or_default(),
+                                &model,
+                                &method,
+                                args,
+                                kwargs.unwrap_or_default(),
+                            ),
+                        );
+                        let call = odoo.begin_call().await?;
+                        resp = Some((call, odoo.post("jsonrpc", &request).await?));
+                        continue;
+                    }
+                    let chunk = match resp.as_mut() {
+                        Some((_, resp)) => resp
+                            .chunk()
+                            .await
+                            .map_err(|e| odoo.map_transport_error(e))?,
+                        None => None,
+                    };
+                    match chunk {
+                        Some(chunk) => scanner.push(&chunk),
+                        None => return Ok(None),
+                    }
+                }
+            }
+        })
     }
 
     async fn send<T: Serialize, U: DeserializeOwned>(
         &self,
         request: &Request<T>,
         url: Option<&str>,
-    ) -> Result<Response<U>, reqwest::Error> {
-        let client = reqwest::Client::new();
-        let url = format!("{}/{}", self.host, url.unwrap_or("jsonrpc"));
-        let resp = client.post(&url).json(&request).send().await;
-        Ok(resp?.json().await?)
+    ) -> Result<Response<U>, Error> {
+        let _call = self.begin_call().await?;
+        let mut attempt = 0;
+        loop {
+            let result = match self.post(url.unwrap_or("jsonrpc"), request).await {
+                Ok(resp) => self.read_response(resp).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Err(e)
+                    if e.is_serialization_failure() && attempt < self.retry_policy.max_retries =>
+                {
+                    self.sleep(self.retry_policy.backoff(attempt)).await?;
+                    self.lifecycle.check(self.draining)?;
+                    attempt += 1;
+                }
+                // a 503 page means the request never reached Odoo, unlike 502/504
+                Err(e @ (Error::RateLimited(_) | Error::ServerUnavailable { status: 503, .. }))
+                    if attempt < self.retry_policy.max_retries =>
+                {
+                    let delay = e.retry_after();
+                    self.sleep(delay.unwrap_or_else(|| self.retry_policy.backoff(attempt)))
+                        .await?;
+                    self.lifecycle.check(self.draining)?;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn post<B: Serialize>(&self, path: &str, body: &B) -> Result<reqwest::Response, Error> {
+        self.check_deadline()?;
+        let resp = self
+            .execute(self.http(reqwest::Method::POST, path).json(body))
+            .await?;
+        if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::RateLimited(retry_after(resp.headers())));
+        }
+        if resp.status().is_server_error() && !is_json(&resp) {
+            return Err(Error::ServerUnavailable {
+                status: resp.status().as_u16(),
+                retry_after: retry_after(resp.headers()),
+            });
+        }
+        if resp.status().is_redirection() {
+            return Err(Error::Request(format!(
+                "Server redirected ({}) to {}",
+                resp.status(),
+                resp.headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .unwrap_or("an unknown location")
+            )));
+        }
+        Ok(resp)
     }
+
+    /// Sends a request built by `http`, over the Unix socket when one is configured. The
+    /// caller holds a `begin_call` guard until the response body is read.
+    pub(crate) async fn execute(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, Error> {
+        let request = builder.build().map_err(|e| self.map_transport_error(e))?;
+        let curl = self.request_dump.as_ref().map(|_| curl_command(&request));
+        let resp = self.follow_redirects(request).await?;
+
+        let (hook, curl) = match (&self.request_dump, curl) {
+            (Some(DumpHook(hook)), Some(curl)) => (hook, curl),
+            _ => return Ok(resp),
+        };
+        let status = resp.status();
+        if status.is_redirection() {
+            hook(&RequestDump {
+                curl,
+                status: status.as_u16(),
+                response_body: String::new(),
+            });
+            return Ok(resp);
+        }
+        // the body is read for the dump, then handed back in a rebuilt response
+        let headers = resp.headers().clone();
+        let url = resp.url().clone();
+        let body = self.read_body(resp).await?;
+        hook(&RequestDump {
+            curl,
+            status: status.as_u16(),
+            response_body: String::from_utf8_lossy(&body).into_owned(),
+        });
+        let mut rebuilt = http::Response::builder()
+            .status(status)
+            .url(url)
+            .body(body)
+            .map_err(|e| Error::Request(e.to_string()))?;
+        *rebuilt.headers_mut() = headers;
+        Ok(reqwest::Response::from(rebuilt))
+    }
+
+    // redirects are followed here rather than by reqwest, with a single count, so that a body
+    // carrying the credentials is never sent to another origin or over plain http
+    async fn follow_redirects(
+        &self,
+        mut request: reqwest::Request,
+    ) -> Result<reqwest::Response, Error> {
+        let mut redirects = 0;
+        loop {
+            let retry = request.try_clone();
+            let url = request.url().clone();
+            let resp = match &self.cassette {
+                Some(cassette) => cassette.execute(self, request).await?,
+                None => self.transport(request).await?,
+            };
+            let (max, keep_method) = match self.redirect_policy {
+                RedirectPolicy::None => return Ok(resp),
+                RedirectPolicy::Limited(max) => (max, false),
+                RedirectPolicy::FollowSameOrigin(max) => (max, true),
+            };
+            let status = resp.status();
+            let location = match status {
+                StatusCode::MOVED_PERMANENTLY
+                | StatusCode::FOUND
+                | StatusCode::SEE_OTHER
+                | StatusCode::TEMPORARY_REDIRECT
+                | StatusCode::PERMANENT_REDIRECT => resp
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .and_then(|location| url.join(location).ok()),
+                _ => None,
+            };
+            let (location, mut next) = match (location, retry) {
+                (Some(location), Some(next)) if redirects < max => (location, next),
+                _ => return Ok(resp),
+            };
+            let rewritten = !keep_method
+                && !matches!(
+                    status,
+                    StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT
+                )
+                && next.method() != reqwest::Method::HEAD;
+            if rewritten {
+                *next.method_mut() = reqwest::Method::GET;
+                *next.body_mut() = None;
+                next.headers_mut().remove(CONTENT_TYPE);
+                next.headers_mut().remove(CONTENT_LENGTH);
+            }
+            let downgrade = url.scheme() == "https" && location.scheme() != "https";
+            if location.origin() != url.origin() || downgrade {
+                if next.body().is_some() {
+                    return Err(Error::Request(format!(
+                        "Refused to send the request body to {} after a redirect ({})",
+                        location, status
+                    )));
+                }
+                next.headers_mut().remove(COOKIE);
+            }
+            *next.url_mut() = location;
+            request = next;
+            redirects += 1;
+        }
+    }
+
+    pub(crate) async fn transport(
+        &self,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, Error> {
+        #[cfg(all(unix, feature = "unix-socket"))]
+        if let Some(path) = &self.unix_socket {
+            return crate::unix::send(path, request).await;
+        }
+        self.send_request(request).await
+    }
+
+    /// Counts a call for `shutdown` and takes a `with_max_concurrency` slot, both held by the
+    /// returned guard until the call, retries included, is over.
+    pub(crate) async fn begin_call(&self) -> Result<CallGuard, Error> {
+        let in_flight = self.lifecycle.enter(self.draining)?;
+        let permit = self.acquire_slot().await?;
+        Ok(CallGuard {
+            _in_flight: in_flight,
+            _permit: permit,
+        })
+    }
+
+    async fn acquire_slot(&self) -> Result<Option<OwnedSemaphorePermit>, Error> {
+        let semaphore = match &self.concurrency {
+            Some(semaphore) => semaphore,
+            None => return Ok(None),
+        };
+        let permit = match self.remaining() {
+            Some(remaining) => tokio::time::timeout(remaining, semaphore.clone().acquire_owned())
+                .await
+                .map_err(|_| Error::DeadlineExceeded)?,
+            None => semaphore.clone().acquire_owned().await,
+        };
+        // the semaphore is never closed
+        Ok(permit.ok())
+    }
+
+    async fn send_request(&self, request: reqwest::Request) -> Result<reqwest::Response, Error> {
+        self.client
+            .execute(request)
+            .await
+            .map_err(|e| self.map_transport_error(e))
+    }
+
+    pub(crate) fn map_transport_error(&self, error: reqwest::Error) -> Error {
+        if error.is_timeout() && self.check_deadline().is_err() {
+            Error::DeadlineExceeded
+        } else if error.is_connect() {
+            Error::NotSent(error.to_string())
+        } else {
+            Error::Request(error.to_string())
+        }
+    }
+
+    pub(crate) fn http(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.request(method, &self.url(path))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.host, path.trim_start_matches('/'))
+    }
+
+    /// The base URL, including any path prefix.
+    pub fn base_url(&self) -> &str {
+        &self.host
+    }
+
+    /// URL of the bus websocket (`/websocket`, Odoo 16+), under the same prefix.
+    pub fn websocket_url(&self) -> String {
+        let url = self.url("websocket");
+        match url.split_once("://") {
+            Some(("https", rest)) => format!("wss://{}", rest),
+            Some(("http", rest)) => format!("ws://{}", rest),
+            _ => url,
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let mut builder = self.client.request(method, url);
+        if let Some(remaining) = self.remaining() {
+            builder = builder.timeout(remaining);
+        }
+        if let Some(session_id) = &self.session_id {
+            builder = builder.header(COOKIE, format!("session_id={}", session_id));
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.header(USER_AGENT, user_agent);
+        }
+        if let Some(client_id) = &self.client_id {
+            builder = builder.header("X-Client", client_id);
+        }
+        if let Some((header, id)) = self.correlation() {
+            builder = builder.header(header, id);
+        }
+        builder
+    }
+
+    /// Reads the whole body, enforcing `max_response_size`.
+    pub(crate) async fn read_body(&self, mut resp: reqwest::Response) -> Result<Vec<u8>, Error> {
+        let limit = match self.max_response_size {
+            Some(limit) => limit,
+            None => {
+                let body = resp
+                    .bytes()
+                    .await
+                    .map_err(|e| self.map_transport_error(e))?;
+                return Ok(body.to_vec());
+            }
+        };
+        if resp
+            .content_length()
+            .is_some_and(|length| length > limit as u64)
+        {
+            return Err(Error::ResponseTooLarge(limit));
+        }
+        let mut body = vec![];
+        while let Some(chunk) = resp
+            .chunk()
+            .await
+            .map_err(|e| self.map_transport_error(e))?
+        {
+            if body.len() + chunk.len() > limit {
+                return Err(Error::ResponseTooLarge(limit));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    // tracebacks are only kept when asked for
+    fn rpc_error(&self, mut error: RpcError) -> Error {
+        if !self.tracebacks {
+            if let Some(data) = &mut error.data {
+                data.debug = None;
+            }
+        }
+        error.into()
+    }
+
+    async fn read_response<U: DeserializeOwned>(
+        &self,
+        resp: reqwest::Response,
+    ) -> Result<Response<U>, Error> {
+        let body = self.read_body(resp).await?;
+        let envelope: Envelope<U> = decode(&body)?;
+        if let Some(error) = envelope.error {
+            return Err(self.rpc_error(error));
+        }
+        let result = match envelope.result {
+            Some(result) => result,
+            // `null` results (methods returning None) only fit types that accept null
+            None => serde_json::from_value(Value::Null).map_err(|e| Error::Decode {
+                path: None,
+                message: e.to_string(),
+            })?,
+        };
+        Ok(Response {
+            id: envelope.id.unwrap_or_default(),
+            result,
+        })
+    }
+}
+
+// redirects are handled by `Odoo::follow_redirects`
+pub(crate) fn default_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("the default client is valid")
+}
+
+/// Validates a base URL, returning it without trailing slash.
+pub(crate) fn normalize_host(host: &str) -> Result<String, Error> {
+    let host = host.trim();
+    let invalid =
+        |reason: String| Error::InvalidUrl(format!("Invalid host \"{}\": {}", host, reason));
+    if !host.contains("://") {
+        return Err(invalid(format!(
+            "missing scheme, use e.g. \"https://{}\"",
+            host.trim_end_matches('/')
+        )));
+    }
+    let url = reqwest::Url::parse(host).map_err(|e| invalid(e.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(format!(
+            "unsupported scheme \"{}\", expected http or https",
+            url.scheme()
+        )));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(invalid(String::from("missing hostname")));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(invalid(String::from("unexpected query or fragment")));
+    }
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+fn is_json(resp: &reqwest::Response) -> bool {
+    resp.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("json"))
+}
+
+#[cfg(not(any(feature = "path-to-error", feature = "simd")))]
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, Error> {
+    serde_json::from_slice(body).map_err(|e| Error::Decode {
+        path: None,
+        message: e.to_string(),
+    })
+}
+
+/// SIMD-accelerated parsing for bulk reads; `path-to-error` takes precedence when both are on.
+#[cfg(all(feature = "simd", not(feature = "path-to-error")))]
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, Error> {
+    // simd-json parses in place
+    let mut body = body.to_vec();
+    simd_json::serde::from_slice(&mut body).map_err(|e| Error::Decode {
+        path: None,
+        message: e.to_string(),
+    })
+}
+
+/// Strict mode: reports which record and field (`result[3].default_code`) failed to decode.
+#[cfg(feature = "path-to-error")]
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, Error> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| Error::Decode {
+        path: Some(e.path().to_string()),
+        message: e.inner().to_string(),
+    })
+}
+
+fn session_cookie(resp: &reqwest::Response) -> Option<String> {
+    resp.headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find_map(|cookie| {
+            let value = cookie.strip_prefix("session_id=")?;
+            Some(value.split(';').next().unwrap_or_default().to_string())
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::time::Duration;
 
-    use serde::Deserialize;
+    use futures_util::TryStreamExt;
+    use serde::{Deserialize, Serialize};
     use serde_json::{Map, Value};
 
     use crate::api::Response;
-    use crate::odoo::{deserialize_odoo_nullable, Odoo};
+    use crate::error::Error;
+    use crate::odoo::{deserialize_odoo_optional, odoo_nullable, serialize_odoo_nullable, Odoo};
+
+    #[tokio::test]
+    async fn test_max_concurrency() {
+        let odoo = Odoo::new("http://127.0.0.1:1", "demo").with_max_concurrency(1);
+        let permit = odoo.acquire_slot().await.unwrap();
+        assert!(permit.is_some());
+
+        // clones share the limit
+        let clone = odoo.with_timeout(Duration::from_millis(20));
+        let result = clone.begin_call().await;
+        assert!(matches!(result, Err(Error::DeadlineExceeded)));
+        drop(permit);
+        assert!(clone.acquire_slot().await.unwrap().is_some());
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_in_chunks_keep_order() {
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+        use crate::query::SearchReadOptions;
+
+        let server = MockServer::start().await.unwrap();
+        let ids: Vec<u32> = (0..5)
+            .map(|i| server.insert("res.partner", json!({"name": format!("Partner {}", i)})))
+            .collect();
+        let odoo = server.odoo().await.unwrap().with_in_chunk_size(2);
+        let domain = json!([["id", "in", ids]]);
+
+        let records: Vec<Value> = odoo
+            .search_read_with(
+                "res.partner",
+                &domain,
+                SearchReadOptions::new().fields(&["name"]),
+            )
+            .await
+            .unwrap()
+            .result;
+        assert_eq!(records.len(), 5);
+
+        let options = SearchReadOptions::new().fields(&["name"]).order("id desc");
+        let records: Vec<Value> = odoo
+            .search_read_with("res.partner", &domain, options)
+            .await
+            .unwrap()
+            .result;
+        let found: Vec<u64> = records.iter().filter_map(|r| r["id"].as_u64()).collect();
+        let mut expected: Vec<u64> = ids.iter().map(|&id| id as u64).collect();
+        expected.reverse();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_client_identification() {
+        let odoo = Odoo::new("http://localhost:8069", "demo")
+            .with_user_agent("invoice-sync", "2.3.0")
+            .with_client_header("acme-prod");
+        let request = odoo.http(reqwest::Method::GET, "web").build().unwrap();
+        assert_eq!(
+            request.headers()["user-agent"],
+            format!(
+                "invoice-sync/2.3.0 async-odoors/{}",
+                env!("CARGO_PKG_VERSION")
+            )
+            .as_str()
+        );
+        assert_eq!(request.headers()["x-client"], "acme-prod");
+    }
 
     async fn get_odoo() -> Odoo {
         let odoo = Odoo::new("https://demo.odoo.com", "");
@@ -200,6 +1257,22 @@ mod tests {
         assert_eq!(values.contains_key("password"), true);
     }
 
+    #[tokio::test]
+    async fn test_ping() {
+        let odoo = Odoo::new("https://demo.odoo.com", "");
+        let latency = odoo.ping(Duration::from_secs(10)).await.unwrap();
+        assert!(latency < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_exceeded() {
+        let odoo = get_odoo().await.with_timeout(Duration::ZERO);
+        let resp: Result<Response<Vec<u32>>, Error> = odoo
+            .call("res.partner", "search", [[["id", ">", "2"]]])
+            .await;
+        assert!(matches!(resp, Err(Error::DeadlineExceeded)));
+    }
+
     #[tokio::test]
     async fn test_login() {
         let odoo = get_odoo().await;
@@ -219,6 +1292,29 @@ mod tests {
         assert_eq!(odoo.is_err(), true);
     }
 
+    #[tokio::test]
+    async fn test_call_service() {
+        let odoo = Odoo::new("https://demo.odoo.com", "");
+        let version = odoo.call_service("common", "version", ()).await.unwrap();
+        assert!(version.get("server_version").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_call_controller() {
+        let odoo = Odoo::new("https://demo.odoo.com", "");
+        let values = odoo.start().await.unwrap();
+        let mut odoo = Odoo::new(values.get("host").unwrap(), values.get("database").unwrap());
+        let uid = odoo
+            .web_login(values.get("user").unwrap(), values.get("password").unwrap())
+            .await
+            .unwrap();
+        let info: HashMap<String, Value> = odoo
+            .call_controller("/web/session/get_session_info", Map::new())
+            .await
+            .unwrap();
+        assert_eq!(info.get("uid").unwrap(), uid);
+    }
+
     #[tokio::test]
     async fn test_search() {
         let odoo = get_odoo();
@@ -273,6 +1369,23 @@ mod tests {
         assert_eq!(partners.result.len(), 5);
     }
 
+    #[tokio::test]
+    async fn test_query_stream() {
+        let odoo = get_odoo().await;
+        let query = odoo
+            .query("res.partner")
+            .domain((("id", ">", 0),))
+            .fields(&["name"])
+            .limit(7);
+
+        let partners: Vec<Partner> = query.clone().stream(3).try_collect().await.unwrap();
+        assert_eq!(partners.len(), 7);
+
+        let partners: Vec<Partner> = query.by_id_cursor().stream(3).try_collect().await.unwrap();
+        assert_eq!(partners.len(), 7);
+        assert!(partners.windows(2).all(|pair| pair[0].id < pair[1].id));
+    }
+
     #[tokio::test]
     async fn test_create_and_write() {
         let odoo = get_odoo().await;
@@ -317,10 +1430,61 @@ mod tests {
     struct ProductTemplate {
         pub id: u32,
         pub name: String,
-        #[serde(deserialize_with = "deserialize_odoo_nullable")]
+        #[serde(deserialize_with = "deserialize_odoo_optional")]
         pub default_code: Option<String>,
     }
 
+    #[test]
+    fn test_deserialize_odoo_optional() {
+        let product: ProductTemplate =
+            serde_json::from_str(r#"{"id": 1, "name": "Desk", "default_code": false}"#).unwrap();
+        assert_eq!(product.default_code, None);
+        let product: ProductTemplate =
+            serde_json::from_str(r#"{"id": 1, "name": "Desk", "default_code": "D01"}"#).unwrap();
+        assert_eq!(product.default_code.unwrap(), "D01");
+        let product: Result<ProductTemplate, _> =
+            serde_json::from_str(r#"{"id": 1, "name": "Desk", "default_code": 7}"#);
+        assert!(product.is_err());
+    }
+
+    #[cfg(all(feature = "simd", not(feature = "path-to-error")))]
+    #[test]
+    fn test_simd_decode() {
+        use crate::odoo::decode;
+
+        let body = br#"{"jsonrpc": "2.0", "id": 1, "result": [
+            {"id": 1, "name": "Desk", "default_code": false},
+            {"id": 2, "name": "Chair \u00e9", "default_code": "C01"}
+        ]}"#;
+        let response: Response<Vec<ProductTemplate>> = decode(body).unwrap();
+        assert_eq!(response.result.len(), 2);
+        assert_eq!(response.result[0].default_code, None);
+        assert_eq!(response.result[1].name, "Chair é");
+        let result = decode::<Vec<ProductTemplate>>(br#"[{"id": "1"}]"#);
+        assert!(matches!(result, Err(Error::Decode { path: None, .. })));
+    }
+
+    #[derive(Serialize)]
+    struct PartnerValues {
+        name: String,
+        #[serde(serialize_with = "serialize_odoo_nullable")]
+        email: Option<String>,
+        #[serde(with = "odoo_nullable")]
+        phone: Option<String>,
+    }
+
+    #[test]
+    fn test_serialize_odoo_nullable() {
+        let values = PartnerValues {
+            name: String::from("Test"),
+            email: None,
+            phone: Some(String::from("555")),
+        };
+        let values = serde_json::to_value(&values).unwrap();
+        assert_eq!(values["email"], Value::Bool(false));
+        assert_eq!(values["phone"], "555");
+    }
+
     #[tokio::test]
     async fn test_search_read_serde_nullable() {
         let odoo = get_odoo().await;
@@ -343,4 +1507,186 @@ mod tests {
             assert_eq!(product.default_code, None);
         }
     }
+
+    #[tokio::test]
+    async fn test_maintenance_page() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        use crate::retry::RetryPolicy;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let page = "<html><body>Upgrading...</body></html>";
+            let json = r#"{"jsonrpc": "2.0", "id": 1, "result": {"server_version": "16.0"}}"#;
+            for body in [page, page, json] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 4096];
+                let _ = socket.read(&mut buf).await.unwrap();
+                let (status, content_type) = match body {
+                    body if body == json => ("200 OK", "application/json"),
+                    _ => ("503 Service Unavailable", "text/html"),
+                };
+                let resp = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nRetry-After: 0\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    content_type,
+                    body.len(),
+                    body
+                );
+                socket.write_all(resp.as_bytes()).await.unwrap();
+            }
+        });
+
+        let odoo = Odoo::new(&url, "").with_retry_policy(RetryPolicy::none());
+        let result = odoo.call_service("common", "version", ()).await;
+        assert!(matches!(
+            result,
+            Err(Error::ServerUnavailable {
+                status: 503,
+                retry_after: Some(Duration::ZERO)
+            })
+        ));
+        let odoo = odoo.with_retry_policy(RetryPolicy::default());
+        let version = odoo.call_service("common", "version", ()).await.unwrap();
+        assert_eq!(version["server_version"], "16.0");
+    }
+
+    #[test]
+    fn test_normalize_host() {
+        use crate::odoo::normalize_host;
+
+        assert_eq!(
+            normalize_host("https://erp.example.com/").unwrap(),
+            "https://erp.example.com"
+        );
+        assert_eq!(
+            normalize_host(" http://localhost:8069/odoo/ ").unwrap(),
+            "http://localhost:8069/odoo"
+        );
+        assert_eq!(
+            normalize_host("erp.example.com").unwrap_err().to_string(),
+            "Invalid host \"erp.example.com\": missing scheme, use e.g. \"https://erp.example.com\""
+        );
+        assert!(matches!(
+            normalize_host("https://erp.example.com:80x"),
+            Err(Error::InvalidUrl(message)) if message.contains("invalid port")
+        ));
+        assert!(normalize_host("ftp://erp.example.com").is_err());
+        assert!(normalize_host("https://erp.example.com/?db=prod").is_err());
+        assert!(Odoo::try_new("localhost:8069", "db").is_err());
+    }
+
+    #[test]
+    fn test_path_prefix() {
+        let odoo = Odoo::new("https://erp.example.com/odoo/", "prod");
+        assert_eq!(odoo.base_url(), "https://erp.example.com/odoo");
+        assert_eq!(odoo.url("/jsonrpc"), "https://erp.example.com/odoo/jsonrpc");
+        assert_eq!(
+            odoo.url("web/session/authenticate"),
+            "https://erp.example.com/odoo/web/session/authenticate"
+        );
+        assert_eq!(odoo.websocket_url(), "wss://erp.example.com/odoo/websocket");
+        let odoo = Odoo::new("http://localhost:8069", "dev");
+        assert_eq!(odoo.websocket_url(), "ws://localhost:8069/websocket");
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_switch_database() {
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let other = odoo.switch_database("other").await.unwrap();
+        assert_eq!(
+            odoo.list_databases().await.unwrap(),
+            Some(vec!["mock".to_string()])
+        );
+        assert_eq!(other.database(), "other");
+        assert_eq!(other.uid(), Some(2));
+        assert_eq!(odoo.database(), "mock");
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_max_response_size() {
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        for i in 0..100 {
+            server.insert("res.partner", json!({"name": format!("Partner {}", i)}));
+        }
+        let odoo = server.odoo().await.unwrap().with_max_response_size(1024);
+        let result = odoo.query("res.partner").all::<Value>().await;
+        assert!(matches!(result, Err(Error::ResponseTooLarge(1024))));
+        let result = odoo.query("res.partner").limit(1).all::<Value>().await;
+        assert_eq!(result.unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_field_validation() {
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        server.on_call("product.template", "fields_get", |_, _| {
+            Ok(json!({
+                "name": {"type": "char", "string": "Name"},
+                "default_code": {"type": "char", "string": "Reference"},
+                "categ_id": {"type": "many2one", "string": "Category", "relation": "product.category"},
+            }))
+        });
+        server.on_call("product.category", "fields_get", |_, _| {
+            Ok(json!({"name": {"type": "char", "string": "Name"}}))
+        });
+        let id = server.insert("product.template", json!({"name": "Desk"}));
+        let odoo = server.odoo().await.unwrap().with_field_validation(true);
+
+        let resp: Result<Response<Vec<Value>>, Error> = odoo
+            .search_read(
+                "product.template",
+                (("categ_id.nmae", "=", "All"),),
+                Some(vec!["default_code"]),
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(resp, Err(Error::InvalidField(m)) if m.contains("\"name\"")));
+        let resp: Result<Response<Vec<Value>>, Error> = odoo
+            .search_read(
+                "product.template",
+                (),
+                Some(vec!["defautl_code"]),
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(resp, Err(Error::InvalidField(_))));
+
+        // the same checks apply to `call`
+        let resp = odoo
+            .call::<_, Value>("product.template", "read", ([id], ["defautl_code"]))
+            .await;
+        assert!(matches!(resp, Err(Error::InvalidField(_))));
+        let resp = odoo
+            .call::<_, Value>(
+                "product.template",
+                "search_count",
+                ([("categ_id.name", "=", "All"), ("nmae", "=", "Desk")],),
+            )
+            .await;
+        assert!(matches!(resp, Err(Error::InvalidField(_))));
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(["name", "default_code"]));
+        let records: Response<Vec<Value>> = odoo
+            .call_kw("product.template", "read", ([id],), kwargs)
+            .await
+            .unwrap();
+        assert_eq!(records.result[0]["name"], "Desk");
+    }
 }
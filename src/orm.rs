@@ -0,0 +1,353 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+use crate::values::Values;
+
+/// Result of an `onchange` call: the values the server recomputed for the record.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct OnchangeResult {
+    #[serde(default)]
+    pub value: Map<String, Value>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub warning: Option<OnchangeWarning>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub domain: Option<Map<String, Value>>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OnchangeWarning {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// Client-generated reference identifying a record across retries, for `create_once`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UniqueKey {
+    /// A field holding a unique reference, e.g. `("ref", "SHOP-1042")`.
+    Field(String, Value),
+    /// An external id (`module.name`) registered for the record once created.
+    XmlId(String),
+}
+
+impl Odoo {
+    /// Runs the ORM `onchange` like the web client does when `field_names` are edited.
+    ///
+    /// `values` holds the current record values and `specification` the onchange spec of
+    /// the form (`{"product_id": "1", ...}` before Odoo 17, the fields spec since).
+    pub async fn onchange(
+        &self,
+        model: &str,
+        ids: &[u32],
+        values: &Map<String, Value>,
+        field_names: &[&str],
+        specification: &Map<String, Value>,
+    ) -> Result<OnchangeResult, Error> {
+        Ok(self
+            .call_kw(
+                model,
+                "onchange",
+                (ids, values, field_names, specification),
+                Map::new(),
+            )
+            .await?
+            .result)
+    }
+
+    /// Reads `fields` of the records `ids` (all fields if empty).
+    pub async fn read<U: DeserializeOwned>(
+        &self,
+        model: &str,
+        ids: &[u32],
+        fields: &[&str],
+    ) -> Result<Vec<U>, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(fields));
+        Ok(self.call_kw(model, "read", (ids,), kwargs).await?.result)
+    }
+
+    /// Like `read` with `load=""`: many2one fields come back as plain ids (or `false`)
+    /// instead of `[id, display_name]` pairs, which is smaller and maps onto `Option<u32>`.
+    pub async fn read_raw<U: DeserializeOwned>(
+        &self,
+        model: &str,
+        ids: &[u32],
+        fields: &[&str],
+    ) -> Result<Vec<U>, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(fields));
+        kwargs.insert("load".to_string(), Value::from(""));
+        Ok(self.call_kw(model, "read", (ids,), kwargs).await?.result)
+    }
+
+    /// Creates a record unless one with `key` already exists, so a job retried after a
+    /// timeout doesn't create it twice. Returns the id and whether the record was created.
+    ///
+    /// Archived records count as existing. Two concurrent calls can still both create unless
+    /// the reference field has a unique constraint.
+    pub async fn create_once(
+        &self,
+        model: &str,
+        key: &UniqueKey,
+        values: impl Into<Values>,
+    ) -> Result<(u32, bool), Error> {
+        let mut values = values.into();
+        match key {
+            UniqueKey::Field(field, value) => {
+                let mut kwargs = Map::new();
+                kwargs.insert("limit".to_string(), Value::from(1));
+                kwargs.insert("context".to_string(), json!({"active_test": false}));
+                let ids: Vec<u32> = self
+                    .call_kw(model, "search", ([(field, "=", value)],), kwargs)
+                    .await?
+                    .result;
+                if let Some(id) = ids.first() {
+                    return Ok((*id, false));
+                }
+                values = values.set(field, value.clone());
+            }
+            UniqueKey::XmlId(xmlid) => match self.resolve_xmlid(xmlid).await {
+                Ok((found, id)) if found == model => return Ok((id, false)),
+                Ok((found, _)) => {
+                    return Err(Error::Request(format!(
+                        "External id \"{}\" is a {} record, not {}",
+                        xmlid, found, model
+                    )))
+                }
+                Err(Error::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            },
+        }
+        let id: u32 = self.call(model, "create", (values,)).await?.result;
+        if let UniqueKey::XmlId(xmlid) = key {
+            self.create_xmlid(xmlid, model, id).await?;
+        }
+        Ok((id, true))
+    }
+
+    /// Writes `values` only if the record's `write_date` is still `expected_write_date` (as
+    /// read earlier), failing with `Error::Conflict` instead of overwriting concurrent edits.
+    ///
+    /// The write carries the date in the `__last_update` context, which Odoo checks in the
+    /// same transaction; from 17.0, which dropped that check, only the read beforehand
+    /// guards it, so a write landing in between goes unnoticed.
+    pub async fn write_if_unchanged(
+        &self,
+        model: &str,
+        id: u32,
+        expected_write_date: &str,
+        values: impl Into<Values>,
+    ) -> Result<(), Error> {
+        let conflict = |write_date: String| Error::Conflict {
+            model: model.to_string(),
+            id,
+            write_date,
+        };
+        let write_date = self.write_date(model, id).await?;
+        if write_date != expected_write_date {
+            return Err(conflict(write_date));
+        }
+        let mut kwargs = Map::new();
+        kwargs.insert(
+            "context".to_string(),
+            json!({"__last_update": {format!("{},{}", model, id): expected_write_date}}),
+        );
+        let result = self
+            .call_kw::<_, bool>(model, "write", ([id], values.into()), kwargs)
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            // the concurrency check raises a ValidationError
+            Err(Error::Validation(error)) => match self.write_date(model, id).await? {
+                write_date if write_date != expected_write_date => Err(conflict(write_date)),
+                _ => Err(Error::Validation(error)),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn write_date(&self, model: &str, id: u32) -> Result<String, Error> {
+        let records: Vec<Map<String, Value>> = self
+            .call(model, "read", ([id], ["write_date"]))
+            .await?
+            .result;
+        Ok(records
+            .first()
+            .ok_or_else(|| Error::NotFound(format!("{}({}) does not exist", model, id)))?
+            .get("write_date")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::orm::OnchangeResult;
+
+    #[test]
+    fn test_onchange_result() {
+        let result: OnchangeResult = serde_json::from_value(json!({
+            "value": {"price_unit": 12.5},
+            "warning": false,
+        }))
+        .unwrap();
+        assert_eq!(result.value["price_unit"], 12.5);
+        assert_eq!(result.warning, None);
+        assert_eq!(result.domain, None);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_create_once() {
+        use crate::mock::MockServer;
+        use crate::orm::UniqueKey;
+        use crate::values::Values;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+
+        let key = UniqueKey::Field("ref".to_string(), json!("SHOP-1042"));
+        let values = Values::new().set("name", "Order");
+        let (id, created) = odoo
+            .create_once("sale.order", &key, values.clone())
+            .await
+            .unwrap();
+        assert!(created);
+        let (again, created) = odoo
+            .create_once("sale.order", &key, values.clone())
+            .await
+            .unwrap();
+        assert_eq!((again, created), (id, false));
+        assert_eq!(server.records("sale.order")[0]["ref"], "SHOP-1042");
+
+        let key = UniqueKey::XmlId("shop.order_1043".to_string());
+        let (id, created) = odoo
+            .create_once("sale.order", &key, values.clone())
+            .await
+            .unwrap();
+        assert!(created);
+        assert_eq!(
+            odoo.resolve_xmlid("shop.order_1043").await.unwrap(),
+            ("sale.order".to_string(), id)
+        );
+        let (again, created) = odoo.create_once("sale.order", &key, values).await.unwrap();
+        assert_eq!((again, created), (id, false));
+        assert_eq!(server.records("sale.order").len(), 2);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_write_if_unchanged() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use crate::error::Error;
+        use crate::mock::MockServer;
+        use crate::values::Values;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let id = server.insert(
+            "res.partner",
+            json!({"name": "A", "write_date": "2024-03-01 10:00:00"}),
+        );
+
+        let result = odoo
+            .write_if_unchanged(
+                "res.partner",
+                id,
+                "2024-02-01 09:00:00",
+                Values::new().set("name", "B"),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Conflict { write_date, .. }) if write_date == "2024-03-01 10:00:00"
+        ));
+        odoo.write_if_unchanged(
+            "res.partner",
+            id,
+            "2024-03-01 10:00:00",
+            Values::new().set("name", "B"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(server.records("res.partner")[0]["name"], "B");
+
+        // a write landing between the read and ours is caught by the server
+        let reads = Arc::new(AtomicUsize::new(0));
+        let count = reads.clone();
+        server.on_call("res.partner", "read", move |_, _| {
+            let write_date = match count.fetch_add(1, Ordering::SeqCst) {
+                0 => "2024-03-01 10:00:00",
+                _ => "2024-03-02 08:00:00",
+            };
+            Ok(json!([{"id": 1, "write_date": write_date}]))
+        });
+        server.insert(
+            "res.partner",
+            json!({"name": "C", "write_date": "2024-03-02 08:00:00"}),
+        );
+        let result = odoo
+            .write_if_unchanged(
+                "res.partner",
+                2,
+                "2024-03-01 10:00:00",
+                Values::new().set("name", "D"),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Conflict { write_date, .. }) if write_date == "2024-03-02 08:00:00"
+        ));
+        assert_eq!(server.records("res.partner")[1]["name"], "C");
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_read_raw() {
+        use serde::Deserialize;
+        use serde_json::Value;
+
+        use crate::mock::MockServer;
+        use crate::odoo::deserialize_odoo_optional;
+
+        #[derive(Deserialize)]
+        struct Partner {
+            name: String,
+            #[serde(deserialize_with = "deserialize_odoo_optional")]
+            parent_id: Option<u32>,
+        }
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let parent = server.insert("res.partner", json!({"name": "Parent"}));
+        let child = server.insert("res.partner", json!({"name": "Child", "parent_id": parent}));
+
+        let partners: Vec<Partner> = odoo
+            .read_raw("res.partner", &[parent, child], &["name", "parent_id"])
+            .await
+            .unwrap();
+        assert_eq!(partners[0].parent_id, None);
+        assert_eq!(partners[1].parent_id, Some(parent));
+        assert_eq!(partners[1].name, "Child");
+
+        server.on_call("res.partner", "read", |_, kwargs| {
+            assert_eq!(kwargs.get("load"), None);
+            Ok(json!([{"id": 2, "name": "Child", "parent_id": [1, "Parent"]}]))
+        });
+        let partners: Vec<Value> = odoo
+            .read("res.partner", &[child], &["name", "parent_id"])
+            .await
+            .unwrap();
+        assert_eq!(partners[0]["parent_id"], json!([1, "Parent"]));
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::query::SearchReadOptions;
+
+impl Odoo {
+    /// Loads the records of `relation` that `records` link to through a many2one, with one
+    /// read for all of them instead of one per record, and hands each to `set`.
+    ///
+    /// `id` extracts the linked id from a record (`None` when the link is empty). Records
+    /// whose target can't be read are left untouched.
+    ///
+    /// ```ignore
+    /// let mut orders: Vec<Order> = odoo.query("sale.order").fields(&["name", "partner_id"]).all().await?;
+    /// odoo.prefetch(&mut orders, "res.partner", &["name", "email"],
+    ///     |order| order.partner_id.map(|(id, _)| id),
+    ///     |order, partner| order.partner = Some(partner),
+    /// ).await?;
+    /// ```
+    pub async fn prefetch<T, R, I, S>(
+        &self,
+        records: &mut [T],
+        relation: &str,
+        fields: &[&str],
+        id: I,
+        mut set: S,
+    ) -> Result<(), Error>
+    where
+        R: DeserializeOwned + Clone,
+        I: Fn(&T) -> Option<u32>,
+        S: FnMut(&mut T, R),
+    {
+        let ids: BTreeSet<u32> = records.iter().filter_map(&id).collect();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let related = self.read_related::<R>(relation, ids, fields).await?;
+        for record in records.iter_mut() {
+            if let Some(target) = id(record).and_then(|id| related.get(&id)) {
+                set(record, target.clone());
+            }
+        }
+        Ok(())
+    }
+
+    // archived targets are still linked, so they are read too
+    pub(crate) async fn read_related<R: DeserializeOwned>(
+        &self,
+        relation: &str,
+        ids: BTreeSet<u32>,
+        fields: &[&str],
+    ) -> Result<HashMap<u32, R>, Error> {
+        let options = SearchReadOptions::new()
+            .fields(fields)
+            .context(json!({"active_test": false}));
+        let ids: Vec<u32> = ids.into_iter().collect();
+        let targets: Vec<Value> = self
+            .search_read_with(relation, [("id", "in", ids)], options)
+            .await?
+            .result;
+        targets
+            .into_iter()
+            .map(|target| {
+                let id = target.get("id").and_then(Value::as_u64).unwrap_or_default() as u32;
+                let target = self.decode_value(target)?;
+                Ok((id, target))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_prefetch() {
+        use serde::Deserialize;
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+        use crate::odoo::deserialize_odoo_optional;
+
+        #[derive(Deserialize, Clone, Debug, PartialEq)]
+        struct Partner {
+            id: u32,
+            name: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Order {
+            name: String,
+            #[serde(deserialize_with = "deserialize_odoo_optional")]
+            partner_id: Option<u32>,
+            #[serde(skip)]
+            partner: Option<Partner>,
+        }
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let azure = server.insert("res.partner", json!({"name": "Azure", "active": false}));
+        let deco = server.insert("res.partner", json!({"name": "Deco"}));
+        server.insert("sale.order", json!({"name": "S1", "partner_id": azure}));
+        server.insert("sale.order", json!({"name": "S2", "partner_id": deco}));
+        server.insert("sale.order", json!({"name": "S3", "partner_id": azure}));
+        server.insert("sale.order", json!({"name": "S4"}));
+
+        let mut orders: Vec<Order> = odoo
+            .query("sale.order")
+            .fields(&["name", "partner_id"])
+            .order("id")
+            .all()
+            .await
+            .unwrap();
+        odoo.prefetch(
+            &mut orders,
+            "res.partner",
+            &["name"],
+            |order| order.partner_id,
+            |order, partner| order.partner = Some(partner),
+        )
+        .await
+        .unwrap();
+        let partners: Vec<(&str, Option<&str>)> = orders
+            .iter()
+            .map(|order| {
+                (
+                    order.name.as_str(),
+                    order.partner.as_ref().map(|p| p.name.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            partners,
+            vec![
+                ("S1", Some("Azure")),
+                ("S2", Some("Deco")),
+                ("S3", Some("Azure")),
+                ("S4", None)
+            ]
+        );
+        assert_eq!(orders[0].partner.as_ref().unwrap().id, azure);
+    }
+}
@@ -0,0 +1,652 @@
+use std::collections::{BTreeSet, HashMap};
+
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// How `Query::pages`/`Query::stream` walk through the matching records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pagination {
+    /// `limit`/`offset` paging; rows can be skipped or duplicated if data changes mid-scan.
+    Offset,
+    /// Pages with `("id", ">", last_id)` ordered by id, stable under concurrent changes.
+    IdCursor,
+}
+
+/// Builder for `search_read` queries, returned by `Odoo::query`.
+#[derive(Debug, Clone)]
+pub struct Query {
+    odoo: Odoo,
+    model: String,
+    domain: Result<Value, Error>,
+    fields: Vec<String>,
+    order: Option<String>,
+    limit: Option<u32>,
+    offset: u32,
+    pagination: Pagination,
+    raw_many2one: bool,
+    expand: Vec<(String, Vec<String>)>,
+}
+
+/// Keyword arguments of `Odoo::search_read_with`, for queries needing more than
+/// fields/limit/offset.
+///
+/// ```ignore
+/// let options = SearchReadOptions::new()
+///     .fields(&["name", "parent_id"])
+///     .order("name")
+///     .context(json!({"lang": "fr_FR", "active_test": false}))
+///     .raw_many2one();
+/// let partners: Response<Vec<Partner>> = odoo.search_read_with("res.partner", domain, options).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchReadOptions {
+    pub(crate) fields: Vec<String>,
+    pub(crate) limit: Option<u32>,
+    pub(crate) offset: Option<u32>,
+    pub(crate) kwargs: Map<String, Value>,
+}
+
+impl SearchReadOptions {
+    pub fn new() -> SearchReadOptions {
+        SearchReadOptions::default()
+    }
+
+    pub fn fields(mut self, fields: &[&str]) -> SearchReadOptions {
+        self.fields = fields.iter().map(|f| f.to_string()).collect();
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> SearchReadOptions {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u32) -> SearchReadOptions {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn order(self, order: &str) -> SearchReadOptions {
+        self.kwarg("order", order)
+    }
+
+    pub fn context(self, context: Value) -> SearchReadOptions {
+        self.kwarg("context", context)
+    }
+
+    /// Reads many2one fields as plain ids instead of `[id, display_name]` (`load=""`).
+    pub fn raw_many2one(self) -> SearchReadOptions {
+        self.kwarg("load", "")
+    }
+
+    /// Any other keyword argument of `search_read`, passed as is.
+    pub fn kwarg(mut self, name: &str, value: impl Into<Value>) -> SearchReadOptions {
+        self.kwargs.insert(name.to_string(), value.into());
+        self
+    }
+}
+
+/// A page of records, returned by `Query::page`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub records: Vec<T>,
+    /// Number of records matching the query, across all pages.
+    pub total: u32,
+    pub offset: u32,
+    pub limit: Option<u32>,
+    /// Whether records remain after this page.
+    pub has_more: bool,
+}
+
+impl<T> Page<T> {
+    /// Offset of the following page, if any.
+    pub fn next_offset(&self) -> Option<u32> {
+        self.has_more
+            .then(|| self.offset + self.records.len() as u32)
+    }
+
+    /// Offset of the preceding page, if any.
+    pub fn previous_offset(&self) -> Option<u32> {
+        match (self.offset, self.limit) {
+            (0, _) => None,
+            (offset, Some(limit)) => Some(offset.saturating_sub(limit)),
+            (_, None) => Some(0),
+        }
+    }
+
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Page<U> {
+        Page {
+            records: self.records.into_iter().map(f).collect(),
+            total: self.total,
+            offset: self.offset,
+            limit: self.limit,
+            has_more: self.has_more,
+        }
+    }
+}
+
+impl Odoo {
+    pub fn query(&self, model: &str) -> Query {
+        Query {
+            odoo: self.clone(),
+            model: model.to_string(),
+            domain: Ok(Value::Array(vec![])),
+            fields: vec![],
+            order: None,
+            limit: None,
+            offset: 0,
+            pagination: Pagination::Offset,
+            raw_many2one: false,
+            expand: vec![],
+        }
+    }
+}
+
+impl Query {
+    pub fn domain<T: Serialize>(mut self, domain: T) -> Query {
+        self.domain = serde_json::to_value(domain).map_err(|e| Error::Request(e.to_string()));
+        self
+    }
+
+    pub fn fields(mut self, fields: &[&str]) -> Query {
+        self.fields = fields.iter().map(|f| f.to_string()).collect();
+        self
+    }
+
+    pub fn order(mut self, order: &str) -> Query {
+        self.order = Some(order.to_string());
+        self
+    }
+
+    /// Caps the total number of records returned, across all pages.
+    pub fn limit(mut self, limit: u32) -> Query {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u32) -> Query {
+        self.offset = offset;
+        self
+    }
+
+    /// Reads many2one fields as plain ids instead of `[id, display_name]` (`load=""`).
+    pub fn raw_many2one(mut self) -> Query {
+        self.raw_many2one = true;
+        self
+    }
+
+    /// Replaces the ids in relational `field` by the linked records with their `fields`,
+    /// read in one extra call per page like `web_search_read` specifications, but working
+    /// on any server. Empty many2one links become `false`. Not applied by
+    /// `stream_incremental`.
+    pub fn expand(mut self, field: &str, fields: &[&str]) -> Query {
+        let fields = fields.iter().map(|f| f.to_string()).collect();
+        self.expand.push((field.to_string(), fields));
+        self
+    }
+
+    /// Switches `pages`/`stream` to id-cursor pagination for consistent full-table scans.
+    /// The query is then ordered by id, overriding `order`.
+    pub fn by_id_cursor(mut self) -> Query {
+        self.pagination = Pagination::IdCursor;
+        self
+    }
+
+    /// Fetches all matching records in a single call.
+    pub async fn all<U: DeserializeOwned>(&self) -> Result<Vec<U>, Error> {
+        let domain = self.domain.clone()?;
+        let records = self
+            .search_read(domain, self.limit, self.offset, self.order.as_deref())
+            .await?;
+        from_records(&self.odoo, records)
+    }
+
+    /// One page of the matching records (per `offset` and `limit`) with how many records
+    /// match in total, for pagination UIs. `search_count` is skipped when the page already
+    /// tells the total.
+    pub async fn page<U: DeserializeOwned>(&self) -> Result<Page<U>, Error> {
+        let domain = self.domain.clone()?;
+        let records = self
+            .search_read(domain, self.limit, self.offset, self.order.as_deref())
+            .await?;
+        let fetched = records.len() as u32;
+        let total = match self.limit {
+            Some(limit) if fetched >= limit => self.count().await?,
+            _ if fetched == 0 && self.offset > 0 => self.count().await?,
+            _ => self.offset + fetched,
+        };
+        Ok(Page {
+            records: from_records(&self.odoo, records)?,
+            total,
+            offset: self.offset,
+            limit: self.limit,
+            has_more: self.offset + fetched < total,
+        })
+    }
+
+    /// Like `all`, also returning how many records match regardless of limit and offset;
+    /// see `page`.
+    pub async fn all_with_total<U: DeserializeOwned>(&self) -> Result<(Vec<U>, u32), Error> {
+        let page = self.page().await?;
+        Ok((page.records, page.total))
+    }
+
+    pub async fn count(&self) -> Result<u32, Error> {
+        let domain = self.domain.clone()?;
+        Ok(self
+            .odoo
+            .call_kw(&self.model, "search_count", vec![domain], Map::new())
+            .await?
+            .result)
+    }
+
+    /// Fetches the matching records `page_size` at a time.
+    pub fn pages<U: DeserializeOwned>(
+        self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<Vec<U>, Error>> {
+        let page_size = page_size.max(1);
+        let state = PageState {
+            offset: self.offset,
+            last_id: 0,
+            fetched: 0,
+        };
+        stream::try_unfold(Some((self, state)), move |next| async move {
+            let (query, mut state) = match next {
+                Some(next) => next,
+                None => return Ok(None),
+            };
+            let size = match query.limit {
+                Some(limit) if limit <= state.fetched => return Ok(None),
+                Some(limit) => page_size.min(limit - state.fetched),
+                None => page_size,
+            };
+            let domain = query.domain.clone()?;
+            let records = match query.pagination {
+                Pagination::Offset => {
+                    query
+                        .search_read(domain, Some(size), state.offset, query.order.as_deref())
+                        .await?
+                }
+                Pagination::IdCursor => {
+                    let mut domain = match domain {
+                        Value::Array(domain) => domain,
+                        _ => vec![],
+                    };
+                    domain.insert(0, serde_json::json!(["id", ">", state.last_id]));
+                    query
+                        .search_read(Value::Array(domain), Some(size), 0, Some("id asc"))
+                        .await?
+                }
+            };
+            if records.is_empty() {
+                return Ok(None);
+            }
+
+            let count = records.len() as u32;
+            state.fetched += count;
+            state.offset += count;
+            if let Some(id) = records
+                .last()
+                .and_then(|record| record.get("id"))
+                .and_then(Value::as_u64)
+            {
+                state.last_id = id;
+            }
+            let page = from_records(&query.odoo, records)?;
+            let next = if count < size {
+                None
+            } else {
+                Some((query, state))
+            };
+            Ok(Some((page, next)))
+        })
+    }
+
+    /// Like `pages`, yielding records one by one.
+    pub fn stream<U: DeserializeOwned>(
+        self,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<U, Error>> {
+        self.pages(page_size)
+            .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Fetches all matching records in a single call like `all`, decoding them one by one as
+    /// the response downloads. Meant for very large results that shouldn't be held in memory.
+    pub fn stream_incremental<U: DeserializeOwned>(&self) -> impl Stream<Item = Result<U, Error>> {
+        match self.domain.clone() {
+            Ok(domain) => {
+                let kwargs = self.kwargs(self.limit, self.offset, self.order.as_deref());
+                self.odoo
+                    .call_kw_stream(&self.model, "search_read", vec![domain], kwargs)
+                    .left_stream()
+            }
+            Err(e) => stream::once(async { Err(e) }).right_stream(),
+        }
+    }
+
+    async fn search_read(
+        &self,
+        domain: Value,
+        limit: Option<u32>,
+        offset: u32,
+        order: Option<&str>,
+    ) -> Result<Vec<Value>, Error> {
+        let mut values = self.kwargs(limit, offset, order);
+        if !self.fields.is_empty() {
+            for (field, _) in &self.expand {
+                if !self.fields.contains(field) {
+                    if let Some(Value::Array(fields)) = values.get_mut("fields") {
+                        fields.push(Value::String(field.clone()));
+                    }
+                }
+            }
+        }
+        let mut records: Vec<Value> = self
+            .odoo
+            .call_kw(&self.model, "search_read", vec![domain], values)
+            .await?
+            .result;
+        if !self.expand.is_empty() && !records.is_empty() {
+            self.expand_records(&mut records).await?;
+        }
+        Ok(records)
+    }
+
+    async fn expand_records(&self, records: &mut [Value]) -> Result<(), Error> {
+        let model_fields = self.odoo.fields_get(&self.model).await?;
+        for (field, fields) in &self.expand {
+            let relation = model_fields
+                .get(field)
+                .and_then(|info| info.relation.clone())
+                .ok_or_else(|| {
+                    Error::InvalidField(format!(
+                        "Field \"{}\" on {} is not relational, cannot expand it",
+                        field, self.model
+                    ))
+                })?;
+            let ids: BTreeSet<u32> = records
+                .iter()
+                .flat_map(|record| linked_ids(record.get(field)))
+                .collect();
+            let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+            let related: HashMap<u32, Value> =
+                self.odoo.read_related(&relation, ids, &fields).await?;
+            for record in records.iter_mut() {
+                let value = match record.get_mut(field) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                let targets = linked_ids(Some(value))
+                    .into_iter()
+                    .filter_map(|id| related.get(&id).cloned());
+                *value = match value {
+                    // x2many ids
+                    Value::Array(ids) if !is_many2one_pair(ids) => Value::Array(targets.collect()),
+                    _ => targets.into_iter().next().unwrap_or(Value::Bool(false)),
+                };
+            }
+        }
+        Ok(())
+    }
+
+    fn kwargs(&self, limit: Option<u32>, offset: u32, order: Option<&str>) -> Map<String, Value> {
+        let mut values = Map::new();
+        values.insert(
+            "fields".to_string(),
+            Value::Array(self.fields.iter().cloned().map(Value::String).collect()),
+        );
+        if let Some(limit) = limit {
+            values.insert("limit".to_string(), Value::Number(Number::from(limit)));
+        }
+        if offset > 0 {
+            values.insert("offset".to_string(), Value::Number(Number::from(offset)));
+        }
+        if let Some(order) = order {
+            values.insert("order".to_string(), Value::String(order.to_string()));
+        }
+        if self.raw_many2one {
+            values.insert("load".to_string(), Value::String(String::new()));
+        }
+        values
+    }
+}
+
+struct PageState {
+    offset: u32,
+    last_id: u64,
+    fetched: u32,
+}
+
+// many2one values are read as `[id, display_name]`, or a plain id with `load=""`
+fn is_many2one_pair(values: &[Value]) -> bool {
+    values.len() == 2 && values[1].is_string()
+}
+
+fn linked_ids(value: Option<&Value>) -> Vec<u32> {
+    match value {
+        Some(Value::Array(values)) if is_many2one_pair(values) => {
+            values[0].as_u64().map(|id| id as u32).into_iter().collect()
+        }
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(Value::as_u64)
+            .map(|id| id as u32)
+            .collect(),
+        Some(value) => value.as_u64().map(|id| id as u32).into_iter().collect(),
+        None => vec![],
+    }
+}
+
+fn from_records<U: DeserializeOwned>(odoo: &Odoo, records: Vec<Value>) -> Result<Vec<U>, Error> {
+    records
+        .into_iter()
+        .map(|record| odoo.decode_value(record))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_all_with_total() {
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        for i in 0..5 {
+            server.insert("res.partner", json!({"name": format!("Partner {}", i)}));
+        }
+
+        let query = odoo.query("res.partner").fields(&["name"]).order("id");
+        let (records, total) = query
+            .clone()
+            .limit(2)
+            .all_with_total::<Value>()
+            .await
+            .unwrap();
+        assert_eq!((records.len(), total), (2, 5));
+        let (records, total) = query
+            .clone()
+            .offset(4)
+            .limit(2)
+            .all_with_total::<Value>()
+            .await
+            .unwrap();
+        assert_eq!((records.len(), total), (1, 5));
+        let (records, total) = query.offset(9).all_with_total::<Value>().await.unwrap();
+        assert_eq!((records.len(), total), (0, 5));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_page() {
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        for i in 0..5 {
+            server.insert("res.partner", json!({"name": format!("Partner {}", i)}));
+        }
+
+        let query = odoo
+            .query("res.partner")
+            .fields(&["name"])
+            .order("id")
+            .limit(2);
+        let page = query.clone().page::<Value>().await.unwrap();
+        assert_eq!((page.records.len(), page.total), (2, 5));
+        assert_eq!(page.next_offset(), Some(2));
+        assert_eq!(page.previous_offset(), None);
+
+        let page = query.clone().offset(4).page::<Value>().await.unwrap();
+        assert_eq!((page.records.len(), page.total), (1, 5));
+        assert_eq!(page.next_offset(), None);
+        assert_eq!(page.previous_offset(), Some(2));
+
+        let page = query.offset(9).page::<Value>().await.unwrap();
+        assert_eq!((page.records.len(), page.total), (0, 5));
+        assert!(!page.has_more);
+        let names = page.map(|record| record["name"].to_string());
+        assert!(names.records.is_empty());
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_expand() {
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let azure = server.insert(
+            "res.partner",
+            json!({"name": "Azure", "email": "azure@example.com"}),
+        );
+        let tag = server.insert("crm.tag", json!({"name": "VIP"}));
+        server.insert(
+            "sale.order",
+            json!({"name": "S1", "partner_id": [azure, "Azure"], "tag_ids": [tag]}),
+        );
+        server.insert("sale.order", json!({"name": "S2", "tag_ids": []}));
+        server.on_call("sale.order", "fields_get", |_, _| {
+            Ok(json!({
+                "name": {"type": "char"},
+                "partner_id": {"type": "many2one", "relation": "res.partner"},
+                "tag_ids": {"type": "many2many", "relation": "crm.tag"},
+            }))
+        });
+
+        let orders: Vec<Value> = odoo
+            .query("sale.order")
+            .fields(&["name"])
+            .order("id")
+            .expand("partner_id", &["name", "email"])
+            .expand("tag_ids", &["name"])
+            .all()
+            .await
+            .unwrap();
+        assert_eq!(
+            orders[0]["partner_id"],
+            json!({"id": azure, "name": "Azure", "email": "azure@example.com"})
+        );
+        assert_eq!(orders[0]["tag_ids"], json!([{"id": tag, "name": "VIP"}]));
+        assert_eq!(orders[1]["partner_id"], json!(false));
+        assert_eq!(orders[1]["tag_ids"], json!([]));
+
+        let result = odoo
+            .query("sale.order")
+            .expand("name", &["name"])
+            .all::<Value>()
+            .await;
+        assert!(matches!(result, Err(crate::error::Error::InvalidField(_))));
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_search_read_with() {
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+        use crate::query::SearchReadOptions;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        for name in ["Azure", "Deco", "Gemini"] {
+            server.insert("res.partner", json!({"name": name, "active": true}));
+        }
+
+        let options = SearchReadOptions::new()
+            .fields(&["name"])
+            .order("name desc")
+            .limit(2);
+        let partners: Vec<Value> = odoo
+            .search_read_with("res.partner", json!([]), options)
+            .await
+            .unwrap()
+            .result;
+        assert_eq!(partners.len(), 2);
+        assert_eq!(partners[0]["name"], "Gemini");
+        assert_eq!(partners[0].get("active"), None);
+
+        server.on_call("res.partner", "search_read", |_, kwargs| {
+            assert_eq!(kwargs["context"], json!({"active_test": false}));
+            assert_eq!(kwargs["load"], "");
+            assert_eq!(kwargs.get("limit"), None);
+            Ok(json!([]))
+        });
+        let options = SearchReadOptions::new()
+            .context(json!({"active_test": false}))
+            .raw_many2one();
+        let partners: Vec<Value> = odoo
+            .search_read_with("res.partner", json!([]), options)
+            .await
+            .unwrap()
+            .result;
+        assert!(partners.is_empty());
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_all_denies_unknown_fields() {
+        use serde::Deserialize;
+        use serde_json::json;
+
+        use crate::error::Error;
+        use crate::mock::MockServer;
+        use crate::strict::UnknownFields;
+
+        #[derive(Deserialize)]
+        struct Partner {
+            #[allow(dead_code)]
+            id: u32,
+            #[allow(dead_code)]
+            name: String,
+        }
+
+        let server = MockServer::start().await.unwrap();
+        server.insert("res.partner", json!({"name": "Azure", "ref": "P001"}));
+        let odoo = server.odoo().await.unwrap();
+        let query = odoo.query("res.partner").fields(&["name", "ref"]);
+        assert_eq!(query.all::<Partner>().await.unwrap().len(), 1);
+
+        let odoo = odoo.with_unknown_fields(UnknownFields::Deny);
+        let query = odoo.query("res.partner").fields(&["name", "ref"]);
+        let error = query.all::<Partner>().await.err().unwrap();
+        assert!(matches!(error, Error::Decode { path: Some(path), .. } if path == "ref"));
+        let query = odoo.query("res.partner").fields(&["name"]);
+        assert_eq!(query.all::<Partner>().await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// A write operation recorded while the server was unreachable.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum QueuedOp {
+    Create {
+        model: String,
+        values: Map<String, Value>,
+    },
+    Write {
+        model: String,
+        ids: Vec<u32>,
+        values: Map<String, Value>,
+    },
+    Unlink {
+        model: String,
+        ids: Vec<u32>,
+    },
+}
+
+impl QueuedOp {
+    pub fn create(model: &str, values: impl Into<Map<String, Value>>) -> QueuedOp {
+        QueuedOp::Create {
+            model: model.to_string(),
+            values: values.into(),
+        }
+    }
+
+    pub fn write(model: &str, ids: &[u32], values: impl Into<Map<String, Value>>) -> QueuedOp {
+        QueuedOp::Write {
+            model: model.to_string(),
+            ids: ids.to_vec(),
+            values: values.into(),
+        }
+    }
+
+    pub fn unlink(model: &str, ids: &[u32]) -> QueuedOp {
+        QueuedOp::Unlink {
+            model: model.to_string(),
+            ids: ids.to_vec(),
+        }
+    }
+
+    /// Sends the operation and returns the raw result (the new id for `create`).
+    pub async fn send(&self, odoo: &Odoo) -> Result<Value, Error> {
+        let response = match self {
+            QueuedOp::Create { model, values } => odoo.call(model, "create", (values,)).await?,
+            QueuedOp::Write { model, ids, values } => {
+                odoo.call(model, "write", (ids, values)).await?
+            }
+            QueuedOp::Unlink { model, ids } => odoo.call(model, "unlink", (ids,)).await?,
+        };
+        Ok(response.result)
+    }
+}
+
+/// File-backed queue of `create`/`write`/`unlink` operations, for clients on flaky links.
+///
+/// Operations go through `submit`: they are sent right away when possible, and recorded
+/// (one JSON line each) when the server is unreachable, or when earlier operations are still
+/// waiting, so that `replay` applies everything in order once connectivity returns. Delivery
+/// is at least once: an operation cut off by a timeout during `replay` is sent again.
+///
+/// ```ignore
+/// let mut queue = OfflineQueue::open("/var/lib/app/odoo-queue.jsonl")?;
+/// queue.replay(&odoo).await.ok();
+/// queue.submit(&odoo, QueuedOp::create("stock.move", values)).await?;
+/// ```
+#[derive(Debug)]
+pub struct OfflineQueue {
+    path: PathBuf,
+    ops: VecDeque<QueuedOp>,
+}
+
+impl OfflineQueue {
+    /// Opens the queue stored at `path`, creating it if missing.
+    pub fn open(path: impl AsRef<Path>) -> Result<OfflineQueue, Error> {
+        let path = path.as_ref().to_path_buf();
+        let ops = match fs::read_to_string(&path) {
+            Ok(data) => data
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    serde_json::from_str(line)
+                        .map_err(|e| Error::Request(format!("{}: {}", path.display(), e)))
+                })
+                .collect::<Result<VecDeque<QueuedOp>, Error>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => VecDeque::new(),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+        Ok(OfflineQueue { path, ops })
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &QueuedOp> {
+        self.ops.iter()
+    }
+
+    /// Records `op` durably without sending it.
+    pub fn push(&mut self, op: QueuedOp) -> Result<(), Error> {
+        let mut line = serde_json::to_string(&op).map_err(|e| Error::Request(e.to_string()))?;
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| io_error(&self.path, e))?;
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|e| io_error(&self.path, e))?;
+        self.ops.push_back(op);
+        Ok(())
+    }
+
+    /// Drops the oldest operation, e.g. one the server keeps rejecting, and returns it.
+    pub fn pop_front(&mut self) -> Result<Option<QueuedOp>, Error> {
+        let op = self.ops.pop_front();
+        self.save()?;
+        Ok(op)
+    }
+
+    /// Sends the queued operations in order, removing each one once applied. Stops at the
+    /// first error, leaving that operation at the front of the queue.
+    pub async fn replay(&mut self, odoo: &Odoo) -> Result<usize, Error> {
+        let mut replayed = 0;
+        while let Some(op) = self.ops.front() {
+            op.send(odoo).await?;
+            self.ops.pop_front();
+            self.save()?;
+            replayed += 1;
+        }
+        Ok(replayed)
+    }
+
+    /// Sends `op` after any queued operations, or queues it if the server is unreachable.
+    /// Returns the result, or `None` when the operation was queued.
+    ///
+    /// `op` is only queued when it certainly wasn't sent (see `Error::is_unsent`): a timeout
+    /// may strike after Odoo committed the write, so it is returned as an error instead, for
+    /// the caller to check before retrying. Replayed operations are delivered at least once
+    /// all the same: one interrupted that way stays at the front of the queue.
+    pub async fn submit(&mut self, odoo: &Odoo, op: QueuedOp) -> Result<Option<Value>, Error> {
+        if let Err(e) = self.replay(odoo).await {
+            return match e.is_unreachable() {
+                true => self.push(op).map(|_| None),
+                false => Err(e),
+            };
+        }
+        match op.send(odoo).await {
+            Ok(result) => Ok(Some(result)),
+            Err(e) if e.is_unsent() => {
+                self.push(op)?;
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // rewritten through a temporary file so a crash never leaves a truncated queue
+    fn save(&self) -> Result<(), Error> {
+        let tmp = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp).map_err(|e| io_error(&tmp, e))?;
+        for op in &self.ops {
+            let line = serde_json::to_string(op).map_err(|e| Error::Request(e.to_string()))?;
+            writeln!(file, "{}", line).map_err(|e| io_error(&tmp, e))?;
+        }
+        file.sync_all().map_err(|e| io_error(&tmp, e))?;
+        fs::rename(&tmp, &self.path).map_err(|e| io_error(&self.path, e))
+    }
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> Error {
+    Error::Request(format!("{}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map, Value};
+
+    use crate::queue::{OfflineQueue, QueuedOp};
+
+    fn values(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_queue_is_persisted() {
+        let path = std::env::temp_dir().join(format!("odoors-queue-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut queue = OfflineQueue::open(&path).unwrap();
+        assert!(queue.is_empty());
+        queue
+            .push(QueuedOp::create(
+                "res.partner",
+                values(json!({"name": "A"})),
+            ))
+            .unwrap();
+        queue
+            .push(QueuedOp::write(
+                "res.partner",
+                &[7],
+                values(json!({"name": "B"})),
+            ))
+            .unwrap();
+        queue.push(QueuedOp::unlink("res.partner", &[8])).unwrap();
+
+        let mut queue = OfflineQueue::open(&path).unwrap();
+        assert_eq!(queue.len(), 3);
+        assert_eq!(
+            queue.pop_front().unwrap(),
+            Some(QueuedOp::create(
+                "res.partner",
+                values(json!({"name": "A"}))
+            ))
+        );
+        let queue = OfflineQueue::open(&path).unwrap();
+        assert_eq!(
+            queue.pending().collect::<Vec<_>>(),
+            vec![
+                &QueuedOp::write("res.partner", &[7], values(json!({"name": "B"}))),
+                &QueuedOp::unlink("res.partner", &[8]),
+            ]
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_replay() {
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let id = server.insert("res.partner", json!({"name": "Old"}));
+        let path = std::env::temp_dir().join(format!("odoors-replay-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut queue = OfflineQueue::open(&path).unwrap();
+        queue
+            .push(QueuedOp::create(
+                "res.partner",
+                values(json!({"name": "New"})),
+            ))
+            .unwrap();
+        queue
+            .push(QueuedOp::write(
+                "res.partner",
+                &[id],
+                values(json!({"name": "Renamed"})),
+            ))
+            .unwrap();
+        let result = queue
+            .submit(&odoo, QueuedOp::unlink("res.partner", &[id]))
+            .await
+            .unwrap();
+        assert_eq!(result, Some(json!(true)));
+        assert!(queue.is_empty());
+        assert!(OfflineQueue::open(&path).unwrap().is_empty());
+
+        let records = server.records("res.partner");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["name"], "New");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_submit_queues_unsent_calls_only() {
+        use std::time::Duration;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        use crate::odoo::Odoo;
+
+        // answers the login, then holds every request without answering
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut connection, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 4096];
+            let _ = connection.read(&mut buffer).await;
+            let body = r#"{"jsonrpc": "2.0", "id": null, "result": 2}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            connection.write_all(response.as_bytes()).await.unwrap();
+            drop(connection);
+            let mut held = vec![];
+            while let Ok((connection, _)) = listener.accept().await {
+                held.push(connection);
+            }
+        });
+        let mut odoo = Odoo::new(&url, "db");
+        odoo.login("admin", "admin").await.unwrap();
+
+        let path = std::env::temp_dir().join(format!("odoors-unsent-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut queue = OfflineQueue::open(&path).unwrap();
+
+        // the server may have committed a write it didn't answer in time
+        let op = QueuedOp::create("res.partner", values(json!({"name": "A"})));
+        let timed_out = odoo.with_timeout(Duration::from_millis(100));
+        assert!(queue.submit(&timed_out, op).await.is_err());
+        assert!(queue.is_empty());
+
+        // nothing listens anymore: the call is never sent
+        server.abort();
+        let _ = server.await;
+        let op = QueuedOp::create("res.partner", values(json!({"name": "B"})));
+        assert_eq!(queue.submit(&odoo, op).await.unwrap(), None);
+        assert_eq!(queue.len(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::command::Command;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct InvoiceLine {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<u32>,
+    pub name: String,
+    pub quantity: f64,
+    pub price_unit: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<u32>,
+    /// Replaces the product's default taxes when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax_ids: Option<Command>,
+}
+
+impl InvoiceLine {
+    pub fn new(name: &str, quantity: f64, price_unit: f64) -> InvoiceLine {
+        InvoiceLine {
+            product_id: None,
+            name: name.to_string(),
+            quantity,
+            price_unit,
+            account_id: None,
+            tax_ids: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PostedInvoice {
+    pub id: u32,
+    pub name: String,
+}
+
+impl Odoo {
+    /// Creates a draft customer invoice (`account.move` of type `out_invoice`).
+    pub async fn create_invoice(
+        &self,
+        partner_id: u32,
+        lines: &[InvoiceLine],
+        journal_id: Option<u32>,
+    ) -> Result<u32, Error> {
+        let lines: Vec<Command> = lines
+            .iter()
+            .map(|line| serde_json::to_value(line).map(Command::Create))
+            .collect::<Result<_, _>>()
+            .map_err(|e| Error::Request(e.to_string()))?;
+        let mut values = json!({
+            "move_type": "out_invoice",
+            "partner_id": partner_id,
+            "invoice_line_ids": lines,
+        });
+        if let Some(journal_id) = journal_id {
+            values["journal_id"] = Value::from(journal_id);
+        }
+        Ok(self
+            .call("account.move", "create", vec![values])
+            .await?
+            .result)
+    }
+
+    /// Posts a draft invoice and returns it with the number assigned on posting.
+    pub async fn post_invoice(&self, id: u32) -> Result<PostedInvoice, Error> {
+        let _: Value = self
+            .call("account.move", "action_post", ([id],))
+            .await?
+            .result;
+        let invoices: Vec<PostedInvoice> = self
+            .call("account.move", "read", ([id], ["name"]))
+            .await?
+            .result;
+        invoices
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("account.move {} not found", id)))
+    }
+
+    /// Downloads the invoice PDF through the report controller; requires `web_login`.
+    pub async fn invoice_pdf(&self, id: u32) -> Result<Vec<u8>, Error> {
+        self.report_pdf("account.report_invoice", &[id]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::command::Command;
+    use crate::recipes::InvoiceLine;
+
+    #[test]
+    fn test_invoice_line_values() {
+        let mut line = InvoiceLine::new("Consulting", 2.0, 150.0);
+        line.tax_ids = Some(Command::Set(vec![]));
+        assert_eq!(
+            serde_json::to_value(&line).unwrap(),
+            json!({"name": "Consulting", "quantity": 2.0, "price_unit": 150.0, "tax_ids": [6, 0, []]})
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! High-level flows built on the RPC primitives, for the integrations most connectors need.
+
+mod invoice;
+mod partner;
+mod payment;
+mod sale;
+mod stock;
+
+pub use invoice::{InvoiceLine, PostedInvoice};
+pub use partner::{normalize_email, normalize_vat, PartnerKey};
+pub use payment::{PaymentOptions, RegisteredPayment};
+pub use sale::{SaleOrder, SaleOrderLine};
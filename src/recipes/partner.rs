@@ -0,0 +1,148 @@
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// How a partner is matched by `find_or_create_partner`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartnerKey {
+    /// VAT number, with or without country prefix; `country` is an ISO code like `ES`.
+    Vat {
+        vat: String,
+        country: Option<String>,
+    },
+    Email(String),
+    Ref(String),
+}
+
+/// Uppercases and strips separators: `" es-b 12.345.678 "` becomes `"ESB12345678"`.
+pub fn normalize_vat(vat: &str) -> String {
+    vat.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// The spellings a VAT number may be stored with: with and without country prefix.
+fn vat_candidates(vat: &str, country: Option<&str>) -> Vec<String> {
+    let vat = normalize_vat(vat);
+    let mut candidates = vec![vat.clone()];
+    let prefix = country.map(|country| country.trim().to_ascii_uppercase());
+    match prefix {
+        Some(prefix) if vat.starts_with(&prefix) => {
+            candidates.push(vat[prefix.len()..].to_string())
+        }
+        Some(prefix) => candidates.push(format!("{}{}", prefix, vat)),
+        None if vat.len() > 2 && vat[..2].chars().all(|c| c.is_ascii_alphabetic()) => {
+            candidates.push(vat[2..].to_string())
+        }
+        None => {}
+    }
+    candidates
+}
+
+impl Odoo {
+    pub async fn find_partner_by_vat(
+        &self,
+        vat: &str,
+        country: Option<&str>,
+    ) -> Result<Option<u32>, Error> {
+        let candidates = vat_candidates(vat, country);
+        let mut domain: Vec<Value> = vec![];
+        for candidate in &candidates[1..] {
+            domain.push(json!("|"));
+            domain.push(json!(["vat", "=ilike", candidate]));
+        }
+        domain.push(json!(["vat", "=ilike", candidates[0]]));
+        self.find_partner(domain).await
+    }
+
+    pub async fn find_partner_by_email(&self, email: &str) -> Result<Option<u32>, Error> {
+        self.find_partner(vec![json!(["email", "=ilike", normalize_email(email)])])
+            .await
+    }
+
+    pub async fn find_partner_by_ref(&self, reference: &str) -> Result<Option<u32>, Error> {
+        self.find_partner(vec![json!(["ref", "=", reference.trim()])])
+            .await
+    }
+
+    /// Returns the matching partner, creating it from `values` (plus the normalized key)
+    /// when none exists. The flag tells whether it was created.
+    pub async fn find_or_create_partner(
+        &self,
+        key: &PartnerKey,
+        values: Map<String, Value>,
+    ) -> Result<(u32, bool), Error> {
+        let (found, field, value) = match key {
+            PartnerKey::Vat { vat, country } => (
+                self.find_partner_by_vat(vat, country.as_deref()).await?,
+                "vat",
+                normalize_vat(vat),
+            ),
+            PartnerKey::Email(email) => (
+                self.find_partner_by_email(email).await?,
+                "email",
+                normalize_email(email),
+            ),
+            PartnerKey::Ref(reference) => (
+                self.find_partner_by_ref(reference).await?,
+                "ref",
+                reference.trim().to_string(),
+            ),
+        };
+        if let Some(id) = found {
+            return Ok((id, false));
+        }
+        let mut values = values;
+        values
+            .entry(field.to_string())
+            .or_insert(Value::String(value));
+        let id: u32 = self
+            .call("res.partner", "create", vec![values])
+            .await?
+            .result;
+        Ok((id, true))
+    }
+
+    async fn find_partner(&self, domain: Vec<Value>) -> Result<Option<u32>, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("limit".to_string(), Value::from(1));
+        let ids: Vec<u32> = self
+            .call_kw("res.partner", "search", (domain,), kwargs)
+            .await?
+            .result;
+        Ok(ids.into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::recipes::partner::{normalize_email, normalize_vat, vat_candidates};
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize_vat(" es-b 12.345.678 "), "ESB12345678");
+        assert_eq!(normalize_email("  Info@Example.COM "), "info@example.com");
+    }
+
+    #[test]
+    fn test_vat_candidates() {
+        assert_eq!(
+            vat_candidates("B12345678", Some("es")),
+            vec!["B12345678", "ESB12345678"]
+        );
+        assert_eq!(
+            vat_candidates("ES B12345678", Some("ES")),
+            vec!["ESB12345678", "B12345678"]
+        );
+        assert_eq!(
+            vat_candidates("FR12345678901", None),
+            vec!["FR12345678901", "12345678901"]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::action::Action;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// Options of `account.payment.register`; unset values keep the wizard's defaults
+/// (full residual amount, default bank journal, today).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaymentOptions {
+    /// Amount for a partial payment.
+    pub amount: Option<f64>,
+    pub journal_id: Option<u32>,
+    /// `YYYY-MM-DD`
+    pub payment_date: Option<String>,
+    pub communication: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegisteredPayment {
+    pub payment_ids: Vec<u32>,
+    pub move_ids: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+struct PaymentMove {
+    move_id: (u32, String),
+}
+
+impl Odoo {
+    /// Registers a payment against posted invoices through `account.payment.register`.
+    pub async fn register_payment(
+        &self,
+        invoice_ids: &[u32],
+        options: PaymentOptions,
+    ) -> Result<RegisteredPayment, Error> {
+        let mut context = Map::new();
+        context.insert("active_model".to_string(), Value::from("account.move"));
+        context.insert("active_ids".to_string(), Value::from(invoice_ids));
+
+        let mut values = Map::new();
+        if let Some(amount) = options.amount {
+            values.insert("amount".to_string(), Value::from(amount));
+        }
+        if let Some(journal_id) = options.journal_id {
+            values.insert("journal_id".to_string(), Value::from(journal_id));
+        }
+        if let Some(payment_date) = options.payment_date {
+            values.insert("payment_date".to_string(), Value::from(payment_date));
+        }
+        if let Some(communication) = options.communication {
+            values.insert("communication".to_string(), Value::from(communication));
+        }
+
+        let wizard = self
+            .open_wizard("account.payment.register", values, context)
+            .await?;
+        let payment_ids = match wizard.call(self, "action_create_payments").await? {
+            Action::Window(window) => match window.res_id {
+                Some(id) => vec![id],
+                None => domain_ids(&window.domain),
+            },
+            _ => vec![],
+        };
+
+        let payments: Vec<PaymentMove> = self
+            .call("account.payment", "read", (&payment_ids, ["move_id"]))
+            .await?
+            .result;
+        Ok(RegisteredPayment {
+            payment_ids,
+            move_ids: payments
+                .into_iter()
+                .map(|payment| payment.move_id.0)
+                .collect(),
+        })
+    }
+}
+
+// ids of an `[("id", "in", [...])]` domain
+fn domain_ids(domain: &Value) -> Vec<u32> {
+    domain
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|leaf| match leaf.as_array()?.as_slice() {
+            [field, _, Value::Array(ids)] if field == "id" => Some(ids),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|id| id.as_u64().map(|id| id as u32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_register_payment() {
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+        use crate::recipes::PaymentOptions;
+
+        let server = MockServer::start().await.unwrap();
+        let payments: Vec<u32> = (1..=2)
+            .map(|n| {
+                server.insert(
+                    "account.payment",
+                    json!({"move_id": [10 + n, format!("BNK1/2024/0000{}", n)]}),
+                )
+            })
+            .collect();
+        let model = "account.payment.register";
+        server.on_call(model, "default_get", |_, kwargs| {
+            assert_eq!(kwargs["context"]["active_model"], "account.move");
+            Ok(json!({"journal_id": 6, "payment_date": "2024-05-01"}))
+        });
+        let domain = json!([["id", "in", payments]]);
+        server.on_call(model, "action_create_payments", move |_, kwargs| {
+            assert_eq!(kwargs["context"]["active_ids"], json!([7, 8]));
+            Ok(json!({
+                "type": "ir.actions.act_window",
+                "res_model": "account.payment",
+                "views": [[false, "tree"], [false, "form"]],
+                "domain": domain,
+            }))
+        });
+        let odoo = server.odoo().await.unwrap();
+
+        let options = PaymentOptions {
+            amount: Some(50.0),
+            communication: Some(String::from("INV/2024/00007")),
+            ..Default::default()
+        };
+        let registered = odoo.register_payment(&[7, 8], options).await.unwrap();
+        assert_eq!(registered.payment_ids, payments);
+        assert_eq!(registered.move_ids, vec![11, 12]);
+
+        let wizard = &server.records(model)[0];
+        assert_eq!(wizard["amount"], 50.0);
+        assert_eq!(wizard["journal_id"], 6);
+        assert_eq!(wizard["communication"], "INV/2024/00007");
+    }
+}
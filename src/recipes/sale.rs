@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::command::Command;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SaleOrderLine {
+    pub product_id: u32,
+    pub product_uom_qty: f64,
+    /// Overrides the pricelist price when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_unit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl SaleOrderLine {
+    pub fn new(product_id: u32, product_uom_qty: f64) -> SaleOrderLine {
+        SaleOrderLine {
+            product_id,
+            product_uom_qty,
+            price_unit: None,
+            name: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SaleOrder {
+    pub id: u32,
+    pub name: String,
+    pub state: String,
+    pub amount_total: f64,
+    #[serde(default)]
+    pub picking_ids: Vec<u32>,
+    #[serde(default)]
+    pub invoice_ids: Vec<u32>,
+}
+
+impl Odoo {
+    /// Creates a quotation with its order lines and confirms it.
+    pub async fn create_sale_order(
+        &self,
+        partner_id: u32,
+        lines: &[SaleOrderLine],
+    ) -> Result<SaleOrder, Error> {
+        let lines: Vec<Command> = lines
+            .iter()
+            .map(|line| serde_json::to_value(line).map(Command::Create))
+            .collect::<Result<_, _>>()
+            .map_err(|e| Error::Request(e.to_string()))?;
+        let id: u32 = self
+            .call(
+                "sale.order",
+                "create",
+                vec![json!({ "partner_id": partner_id, "order_line": lines })],
+            )
+            .await?
+            .result;
+        self.confirm_sale_order(id).await
+    }
+
+    /// Confirms a quotation and returns the order with its pickings and invoices.
+    pub async fn confirm_sale_order(&self, id: u32) -> Result<SaleOrder, Error> {
+        let _: Value = self
+            .call("sale.order", "action_confirm", ([id],))
+            .await?
+            .result;
+        self.read_sale_order(id).await
+    }
+
+    pub async fn read_sale_order(&self, id: u32) -> Result<SaleOrder, Error> {
+        let orders: Vec<SaleOrder> = self
+            .call(
+                "sale.order",
+                "read",
+                (
+                    [id],
+                    [
+                        "name",
+                        "state",
+                        "amount_total",
+                        "picking_ids",
+                        "invoice_ids",
+                    ],
+                ),
+            )
+            .await?
+            .result;
+        orders
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("sale.order {} not found", id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_create_sale_order() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+        use crate::recipes::SaleOrderLine;
+
+        let server = MockServer::start().await.unwrap();
+        server.on_call("sale.order", "create", |args, _| {
+            assert_eq!(
+                args[0],
+                json!({
+                    "partner_id": 3,
+                    "order_line": [
+                        [0, 0, {"product_id": 10, "product_uom_qty": 2.0}],
+                        [0, 0, {"product_id": 11, "product_uom_qty": 1.0, "price_unit": 9.5}],
+                    ],
+                })
+            );
+            Ok(json!(42))
+        });
+        let confirmed = Arc::new(AtomicBool::new(false));
+        let flag = confirmed.clone();
+        server.on_call("sale.order", "action_confirm", move |args, _| {
+            assert_eq!(args[0], json!([42]));
+            flag.store(true, Ordering::SeqCst);
+            Ok(json!(true))
+        });
+        let flag = confirmed.clone();
+        server.on_call("sale.order", "read", move |args, _| {
+            if args[0] != json!([42]) {
+                return Ok(json!([]));
+            }
+            let state = if flag.load(Ordering::SeqCst) {
+                "sale"
+            } else {
+                "draft"
+            };
+            Ok(json!([{
+                "id": 42,
+                "name": "S00042",
+                "state": state,
+                "amount_total": 29.5,
+                "picking_ids": [5],
+                "invoice_ids": [],
+            }]))
+        });
+        let odoo = server.odoo().await.unwrap();
+
+        let mut line = SaleOrderLine::new(11, 1.0);
+        line.price_unit = Some(9.5);
+        let order = odoo
+            .create_sale_order(3, &[SaleOrderLine::new(10, 2.0), line])
+            .await
+            .unwrap();
+        assert!(confirmed.load(Ordering::SeqCst));
+        assert_eq!((order.id, order.state.as_str()), (42, "sale"));
+        assert_eq!(order.picking_ids, vec![5]);
+        assert!(odoo.read_sale_order(43).await.is_err());
+    }
+}
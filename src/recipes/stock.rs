@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::action::Action;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+// immediate transfer + backorder, with one spare round for custom confirmations
+const MAX_WIZARD_STEPS: usize = 3;
+
+#[derive(Deserialize)]
+struct Move {
+    id: u32,
+    product_id: (u32, String),
+    product_uom_qty: f64,
+}
+
+impl Odoo {
+    /// Sets done quantities on a picking's moves and validates it, going through the
+    /// immediate transfer and backorder wizards when the server asks for them.
+    ///
+    /// `quantities` maps product ids to done quantities; products missing from it (or all
+    /// of them when `None`) are done for their full demand. Returns the backorder this
+    /// validation created, if any.
+    pub async fn validate_picking(
+        &self,
+        picking_id: u32,
+        quantities: Option<&HashMap<u32, f64>>,
+        create_backorder: bool,
+    ) -> Result<Option<u32>, Error> {
+        self.set_picking_quantities(picking_id, quantities).await?;
+        // earlier validations may have left backorders already
+        let existing = self.backorders(picking_id).await?;
+
+        let mut action = self
+            .call_button("stock.picking", "button_validate", &[picking_id], None)
+            .await?;
+        let mut steps = 0;
+        while let Action::Window(window) = action {
+            if steps == MAX_WIZARD_STEPS {
+                return Err(Error::Request(format!(
+                    "Picking {} still asks for {} after {} wizard steps",
+                    picking_id, window.res_model, MAX_WIZARD_STEPS
+                )));
+            }
+            steps += 1;
+            let method = match window.res_model.as_str() {
+                "stock.immediate.transfer" => "process",
+                "stock.backorder.confirmation" if create_backorder => "process",
+                "stock.backorder.confirmation" => "process_cancel_backorder",
+                model => {
+                    return Err(Error::Request(format!(
+                        "Unsupported wizard {} while validating picking {}",
+                        model, picking_id
+                    )))
+                }
+            };
+            let wizard = self.open_wizard_from_action(&window, Map::new()).await?;
+            action = wizard.call(self, method).await?;
+        }
+
+        let backorders = self.backorders(picking_id).await?;
+        Ok(backorders
+            .into_iter()
+            .filter(|backorder| !existing.contains(backorder))
+            .max())
+    }
+
+    async fn backorders(&self, picking_id: u32) -> Result<Vec<u32>, Error> {
+        Ok(self
+            .call(
+                "stock.picking",
+                "search",
+                ([("backorder_id", "=", picking_id)],),
+            )
+            .await?
+            .result)
+    }
+
+    async fn set_picking_quantities(
+        &self,
+        picking_id: u32,
+        quantities: Option<&HashMap<u32, f64>>,
+    ) -> Result<(), Error> {
+        let moves: Vec<Move> = self
+            .call(
+                "stock.move",
+                "search_read",
+                (
+                    [("picking_id", "=", picking_id)],
+                    ["product_id", "product_uom_qty"],
+                ),
+            )
+            .await?
+            .result;
+        // Odoo 17 replaced `quantity_done` with `quantity` plus the `picked` flag
+        let legacy = self
+            .fields_get("stock.move")
+            .await?
+            .contains_key("quantity_done");
+        for stock_move in moves {
+            let quantity = quantities
+                .and_then(|quantities| quantities.get(&stock_move.product_id.0))
+                .copied()
+                .unwrap_or(stock_move.product_uom_qty);
+            let values = if legacy {
+                json!({ "quantity_done": quantity })
+            } else {
+                json!({ "quantity": quantity, "picked": true })
+            };
+            let _: Value = self
+                .call("stock.move", "write", ([stock_move.id], values))
+                .await?
+                .result;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_validate_picking() {
+        use std::collections::HashMap;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let picking = server.insert("stock.picking", json!({"name": "WH/OUT/00001"}));
+        for (product, demand) in [(10, 2.0), (11, 3.0)] {
+            server.insert(
+                "stock.move",
+                json!({
+                    "picking_id": picking,
+                    "product_id": [product, format!("Product {}", product)],
+                    "product_uom_qty": demand,
+                    "quantity_done": 0.0,
+                }),
+            );
+        }
+        // an older backorder, then the one the validation creates
+        let old_backorder = server.insert(
+            "stock.picking",
+            json!({"name": "WH/OUT/00002", "backorder_id": picking}),
+        );
+        let backorder = old_backorder + 1;
+        let processed = Arc::new(AtomicBool::new(false));
+        let created = processed.clone();
+        server.on_call("stock.picking", "search", move |args, _| {
+            assert_eq!(args[0], json!([["backorder_id", "=", picking]]));
+            match created.load(Ordering::SeqCst) {
+                true => Ok(json!([old_backorder, backorder])),
+                false => Ok(json!([old_backorder])),
+            }
+        });
+
+        let wizard = |model: &str| {
+            json!({
+                "type": "ir.actions.act_window",
+                "res_model": model,
+                "views": [[false, "form"]],
+                "target": "new",
+                "context": {"button_validate_picking_ids": [picking]},
+            })
+        };
+        let immediate = wizard("stock.immediate.transfer");
+        server.on_call("stock.picking", "button_validate", move |args, _| {
+            assert_eq!(args[0], json!([picking]));
+            Ok(immediate.clone())
+        });
+        let confirmation = wizard("stock.backorder.confirmation");
+        server.on_call("stock.immediate.transfer", "process", move |_, kwargs| {
+            assert_eq!(
+                kwargs["context"]["button_validate_picking_ids"],
+                json!([picking])
+            );
+            Ok(confirmation.clone())
+        });
+        server.on_call("stock.backorder.confirmation", "process", move |_, _| {
+            processed.store(true, Ordering::SeqCst);
+            Ok(Value::Bool(true))
+        });
+        for model in ["stock.immediate.transfer", "stock.backorder.confirmation"] {
+            server.on_call(model, "default_get", |_, _| Ok(json!({})));
+        }
+        let odoo = server.odoo().await.unwrap();
+
+        let quantities = HashMap::from([(10, 1.0)]);
+        let created = odoo
+            .validate_picking(picking, Some(&quantities), true)
+            .await
+            .unwrap();
+        assert_eq!(created, Some(backorder));
+        let done: Vec<Value> = server
+            .records("stock.move")
+            .iter()
+            .map(|stock_move| stock_move["quantity_done"].clone())
+            .collect();
+        assert_eq!(done, vec![json!(1.0), json!(3.0)]);
+        assert_eq!(server.records("stock.backorder.confirmation").len(), 1);
+
+        // a wizard that keeps coming back
+        let again = wizard("stock.immediate.transfer");
+        server.on_call("stock.immediate.transfer", "process", move |_, _| {
+            Ok(again.clone())
+        });
+        let error = odoo
+            .validate_picking(picking, None, true)
+            .await
+            .unwrap_err();
+        assert!(error.message().contains("after 3 wizard steps"));
+    }
+}
@@ -0,0 +1,133 @@
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+
+use crate::api::{RpcError, RpcErrorData};
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+impl Odoo {
+    /// Renders a QWeb report (`ir.actions.report.report_name`, e.g. `sale.report_saleorder`)
+    /// of `ids` as PDF through the report controller; requires `web_login`.
+    pub async fn report_pdf(&self, report_name: &str, ids: &[u32]) -> Result<Vec<u8>, Error> {
+        self.download_report(&report_route("pdf", report_name, ids), "application/pdf")
+            .await
+    }
+
+    /// Like `report_pdf`, returning the report's HTML as rendered before the PDF conversion,
+    /// e.g. to embed it in a page.
+    pub async fn report_html(&self, report_name: &str, ids: &[u32]) -> Result<String, Error> {
+        let body = self
+            .download_report(&report_route("html", report_name, ids), "text/html")
+            .await?;
+        String::from_utf8(body).map_err(|e| Error::Decode {
+            path: None,
+            message: e.to_string(),
+        })
+    }
+
+    async fn download_report(&self, route: &str, content_type: &str) -> Result<Vec<u8>, Error> {
+        let _call = self.begin_call().await?;
+        let resp = self.execute(self.http(reqwest::Method::GET, route)).await?;
+        // without a web session the report route sends the login page instead, followed or not
+        let location = resp
+            .headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok());
+        if is_login(resp.url().path()) || location.is_some_and(is_login) {
+            return Err(session_expired());
+        }
+        if !resp.status().is_success() {
+            return Err(Error::Request(format!(
+                "Report download failed: {}",
+                resp.status()
+            )));
+        }
+        let received = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|received| received.to_str().ok())
+            .unwrap_or_default();
+        if !received.starts_with(content_type) {
+            return Err(Error::Request(format!(
+                "Report download returned {:?} instead of {}",
+                received, content_type
+            )));
+        }
+        self.read_body(resp).await
+    }
+}
+
+fn report_route(converter: &str, report_name: &str, ids: &[u32]) -> String {
+    let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+    format!("report/{}/{}/{}", converter, report_name, ids.join(","))
+}
+
+fn is_login(path: &str) -> bool {
+    path.split('?')
+        .next()
+        .unwrap_or_default()
+        .ends_with("/web/login")
+}
+
+// what the JSON routes answer in the same case
+fn session_expired() -> Error {
+    Error::Rpc(RpcError {
+        code: 100,
+        message: String::from("Odoo Session Expired"),
+        data: Some(RpcErrorData {
+            name: String::from("odoo.http.SessionExpiredException"),
+            message: String::from("Report routes require a web session, see web_login"),
+            debug: None,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::report_route;
+
+    #[test]
+    fn test_report_route() {
+        assert_eq!(
+            report_route("html", "sale.report_saleorder", &[3, 7]),
+            "report/html/sale.report_saleorder/3,7"
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_report_session() {
+        use crate::builder::RedirectPolicy;
+        use crate::error::Error;
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+
+        fn is_session_expired(result: Result<Vec<u8>, Error>) -> bool {
+            matches!(result, Err(Error::Rpc(error))
+                if error.data.as_ref().unwrap().name == "odoo.http.SessionExpiredException")
+        }
+
+        let server = MockServer::start().await.unwrap();
+        let mut odoo = server.odoo().await.unwrap();
+        // the redirect to the login page is followed by default, and its HTML isn't the report
+        assert!(is_session_expired(
+            odoo.report_pdf("sale.report_saleorder", &[3]).await
+        ));
+        assert!(odoo
+            .report_html("sale.report_saleorder", &[3])
+            .await
+            .is_err());
+        let unfollowed = Odoo::builder(server.url(), "mock")
+            .redirect_policy(RedirectPolicy::None)
+            .build()
+            .unwrap();
+        assert!(is_session_expired(
+            unfollowed.report_pdf("sale.report_saleorder", &[3]).await
+        ));
+
+        odoo.web_login("admin", "admin").await.unwrap();
+        let pdf = odoo.report_pdf("sale.report_saleorder", &[3, 7]).await;
+        assert_eq!(pdf.unwrap(), b"%PDF-1.4 sale.report_saleorder/3,7");
+        let html = odoo.report_html("sale.report_saleorder", &[3]).await;
+        assert!(html.unwrap().contains("sale.report_saleorder/3"));
+    }
+}
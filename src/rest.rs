@@ -0,0 +1,145 @@
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::odoo::{decode, Odoo};
+
+/// Client for REST services exposed with OCA's `base_rest`, returned by `Odoo::rest`.
+///
+/// Requests carry the client's web session (services with `auth="user"`), or an
+/// `API-KEY` header with `with_api_key` (services using `auth_api_key`).
+///
+/// ```ignore
+/// let api = odoo.rest("/api/v1/private");
+/// let partner: Partner = api.get("partner/7", &()).await?;
+/// let created: Partner = api.post("partner/create", &json!({"name": "Azure"})).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RestClient {
+    odoo: Odoo,
+    root: String,
+    api_key: Option<String>,
+}
+
+/// Error body returned by `base_rest` for failed requests.
+#[derive(Deserialize, Debug)]
+struct RestError {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+impl Odoo {
+    /// Targets the `base_rest` services under `root_path` (the collection's `_root_path`).
+    pub fn rest(&self, root_path: &str) -> RestClient {
+        RestClient {
+            odoo: self.clone(),
+            root: root_path.trim_matches('/').to_string(),
+            api_key: None,
+        }
+    }
+}
+
+impl RestClient {
+    pub fn with_api_key(mut self, api_key: &str) -> RestClient {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// `GET`s `path`, with `params` as query string.
+    pub async fn get<T: Serialize, U: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &T,
+    ) -> Result<U, Error> {
+        let builder = self.request(Method::GET, path).query(params);
+        self.send(builder).await
+    }
+
+    pub async fn post<T: Serialize, U: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<U, Error> {
+        let builder = self.request(Method::POST, path).json(body);
+        self.send(builder).await
+    }
+
+    pub async fn put<T: Serialize, U: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<U, Error> {
+        let builder = self.request(Method::PUT, path).json(body);
+        self.send(builder).await
+    }
+
+    pub async fn delete<U: DeserializeOwned>(&self, path: &str) -> Result<U, Error> {
+        let builder = self.request(Method::DELETE, path);
+        self.send(builder).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> reqwest::RequestBuilder {
+        let path = format!("{}/{}", self.root, path.trim_start_matches('/'));
+        let builder = self.odoo.http(method, &path);
+        match &self.api_key {
+            Some(api_key) => builder.header("API-KEY", api_key),
+            None => builder,
+        }
+    }
+
+    async fn send<U: DeserializeOwned>(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<U, Error> {
+        let _call = self.odoo.begin_call().await?;
+        let resp = self.odoo.execute(builder).await?;
+        let status = resp.status();
+        let body = self.odoo.read_body(resp).await?;
+        if status.is_success() {
+            // services returning nothing answer with an empty body
+            return decode(if body.is_empty() { b"null" } else { &body });
+        }
+        Err(rest_error(status, &body))
+    }
+}
+
+fn rest_error(status: reqwest::StatusCode, body: &[u8]) -> Error {
+    let message = match serde_json::from_slice::<RestError>(body) {
+        Ok(error) if !error.description.is_empty() => error.description,
+        Ok(error) if !error.name.is_empty() => error.name,
+        _ => String::from_utf8_lossy(body).trim().to_string(),
+    };
+    match status {
+        reqwest::StatusCode::NOT_FOUND => Error::NotFound(message),
+        status => Error::Request(format!("{}: {}", status, message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+
+    use crate::error::Error;
+    use crate::rest::rest_error;
+
+    #[test]
+    fn test_rest_error() {
+        let body = br#"{"code": 400, "name": "Bad Request", "description": "<p>Missing name</p>"}"#;
+        assert_eq!(
+            rest_error(StatusCode::BAD_REQUEST, body).to_string(),
+            "400 Bad Request: <p>Missing name</p>"
+        );
+        let body = br#"{"code": 404, "name": "Not Found"}"#;
+        assert!(matches!(
+            rest_error(StatusCode::NOT_FOUND, body),
+            Error::NotFound(message) if message == "Not Found"
+        ));
+        assert_eq!(
+            rest_error(StatusCode::BAD_GATEWAY, b"upstream down\n").to_string(),
+            "502 Bad Gateway: upstream down"
+        );
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+/// Attributes requested from `fields_get`.
+pub(crate) const FIELD_ATTRIBUTES: [&str; 8] = [
+    "string",
+    "type",
+    "relation",
+    "required",
+    "readonly",
+    "store",
+    "selection",
+    "help",
+];
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldInfo {
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub string: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub relation: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub readonly: bool,
+    #[serde(default = "default_store")]
+    pub store: bool,
+    #[serde(default, deserialize_with = "deserialize_selection")]
+    pub selection: Vec<(Value, String)>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub help: Option<String>,
+}
+
+pub type Fields = HashMap<String, FieldInfo>;
+
+/// A view architecture with the metadata of the fields it can display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct View {
+    pub id: Option<u32>,
+    pub model: String,
+    pub view_type: String,
+    pub arch: String,
+    pub fields: Fields,
+}
+
+#[derive(Deserialize)]
+struct RawView {
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    id: Option<u32>,
+    arch: String,
+    #[serde(default)]
+    fields: Option<Fields>,
+}
+
+/// How a field differs between two instances, as reported by `Odoo::diff_schema`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldDiff {
+    OnlyLeft,
+    OnlyRight,
+    Type {
+        left: String,
+        right: String,
+    },
+    /// Selection keys present on one side only.
+    Selection {
+        only_left: Vec<Value>,
+        only_right: Vec<Value>,
+    },
+    Required {
+        left: bool,
+        right: bool,
+    },
+}
+
+/// Field differences per model; models without differences are left out.
+pub type SchemaDiff = BTreeMap<String, BTreeMap<String, Vec<FieldDiff>>>;
+
+fn default_store() -> bool {
+    true
+}
+
+// dynamic selections come back as a method name or `false` instead of a list
+fn deserialize_selection<'de, D>(data: D) -> Result<Vec<(Value, String)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(data)? {
+        Value::Array(items) => items
+            .into_iter()
+            .filter_map(|item| serde_json::from_value(item).ok())
+            .collect(),
+        _ => vec![],
+    })
+}
+
+impl Odoo {
+    /// Returns the model's field metadata, cached per client after the first call.
+    pub async fn fields_get(&self, model: &str) -> Result<Arc<Fields>, Error> {
+        if let Some(fields) = self.fields_cache.lock().unwrap().get(model) {
+            return Ok(fields.clone());
+        }
+        // not through `call`, whose field rules and response validation need `fields_get`
+        let args = (Vec::<&str>::new(), FIELD_ATTRIBUTES);
+        let fields: Fields = self
+            .dispatch_object(model, "fields_get", args, None)
+            .await?
+            .result;
+        let fields = Arc::new(fields);
+        self.fields_cache
+            .lock()
+            .unwrap()
+            .insert(model.to_string(), fields.clone());
+        Ok(fields)
+    }
+
+    /// Fetches a view (`view_id` or the default one of `view_type`) with field metadata,
+    /// using `get_view` (Odoo 16+) or falling back to `fields_view_get` on older servers.
+    pub async fn get_view(
+        &self,
+        model: &str,
+        view_id: Option<u32>,
+        view_type: &str,
+    ) -> Result<View, Error> {
+        let view_id = view_id.map_or(Value::Bool(false), Value::from);
+        let raw: RawView = match self.call(model, "get_view", (&view_id, view_type)).await {
+            Ok(response) => response.result,
+            Err(e) if e.message().contains("get_view") => {
+                self.call(model, "fields_view_get", (&view_id, view_type))
+                    .await?
+                    .result
+            }
+            Err(e) => return Err(e),
+        };
+        let fields = match raw.fields {
+            Some(fields) => fields,
+            None => (*self.fields_get(model).await?).clone(),
+        };
+        Ok(View {
+            id: raw.id,
+            model: model.to_string(),
+            view_type: view_type.to_string(),
+            arch: raw.arch,
+            fields,
+        })
+    }
+
+    /// Compares the fields of `models` on this instance (left) and `other` (right), e.g.
+    /// staging against production before a sync.
+    pub async fn diff_schema(&self, other: &Odoo, models: &[&str]) -> Result<SchemaDiff, Error> {
+        let mut diff = SchemaDiff::new();
+        for model in models {
+            let left = self.fields_get(model).await?;
+            let right = other.fields_get(model).await?;
+            let fields = diff_fields(&left, &right);
+            if !fields.is_empty() {
+                diff.insert(model.to_string(), fields);
+            }
+        }
+        Ok(diff)
+    }
+
+    /// Checks field names and domain field paths (`partner_id.country_id.code`) against
+    /// `fields_get`, following relations.
+    pub async fn validate_fields(
+        &self,
+        model: &str,
+        fields: &[&str],
+        domain: &Value,
+    ) -> Result<(), Error> {
+        let mut paths: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+        domain_paths(domain, &mut paths);
+        for path in paths {
+            self.validate_path(model, &path).await?;
+        }
+        Ok(())
+    }
+
+    /// `validate_fields` on the field list and domain of a `read`, `search_read`, `search` or
+    /// `search_count` call, given positionally or as keywords; other methods aren't checked.
+    pub(crate) async fn validate_call(
+        &self,
+        model: &str,
+        method: &str,
+        args: &Value,
+        kwargs: &Option<Map<String, Value>>,
+    ) -> Result<(), Error> {
+        let arg = |index: usize, name: &str| {
+            args.get(index)
+                .or_else(|| kwargs.as_ref()?.get(name))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let (domain, fields) = match method {
+            "read" => (Value::Null, arg(1, "fields")),
+            "search_read" => (arg(0, "domain"), arg(1, "fields")),
+            "search" | "search_count" => (arg(0, "domain"), Value::Null),
+            _ => return Ok(()),
+        };
+        let fields: Vec<&str> = match &fields {
+            Value::Array(fields) => fields.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        self.validate_fields(model, &fields, &domain).await
+    }
+
+    async fn validate_path(&self, model: &str, path: &str) -> Result<(), Error> {
+        let mut current = model.to_string();
+        let mut segments = path.split('.').peekable();
+        while let Some(segment) = segments.next() {
+            if segment == "id" {
+                return Ok(());
+            }
+            let fields = self.fields_get(&current).await?;
+            let field = match fields.get(segment) {
+                Some(field) => field,
+                None => {
+                    let mut message = format!("Unknown field \"{}\" on {}", segment, current);
+                    if let Some(suggestion) = closest(segment, fields.keys()) {
+                        message.push_str(&format!(" (did you mean \"{}\"?)", suggestion));
+                    }
+                    return Err(Error::InvalidField(message));
+                }
+            };
+            if segments.peek().is_some() {
+                current = field.relation.clone().ok_or_else(|| {
+                    Error::InvalidField(format!(
+                        "Field \"{}\" on {} is not relational, cannot follow \"{}\"",
+                        segment, current, path
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Differences between two `fields_get` results, keyed by field name.
+pub fn diff_fields(left: &Fields, right: &Fields) -> BTreeMap<String, Vec<FieldDiff>> {
+    let mut diff: BTreeMap<String, Vec<FieldDiff>> = BTreeMap::new();
+    for (name, l) in left {
+        let r = match right.get(name) {
+            Some(r) => r,
+            None => {
+                diff.insert(name.clone(), vec![FieldDiff::OnlyLeft]);
+                continue;
+            }
+        };
+        let mut changes = vec![];
+        if l.field_type != r.field_type {
+            changes.push(FieldDiff::Type {
+                left: l.field_type.clone(),
+                right: r.field_type.clone(),
+            });
+        }
+        let keys = |info: &FieldInfo| -> Vec<Value> {
+            info.selection.iter().map(|(key, _)| key.clone()).collect()
+        };
+        let (l_keys, r_keys) = (keys(l), keys(r));
+        let only_left: Vec<Value> = l_keys
+            .iter()
+            .filter(|k| !r_keys.contains(k))
+            .cloned()
+            .collect();
+        let only_right: Vec<Value> = r_keys
+            .iter()
+            .filter(|k| !l_keys.contains(k))
+            .cloned()
+            .collect();
+        if !only_left.is_empty() || !only_right.is_empty() {
+            changes.push(FieldDiff::Selection {
+                only_left,
+                only_right,
+            });
+        }
+        if l.required != r.required {
+            changes.push(FieldDiff::Required {
+                left: l.required,
+                right: r.required,
+            });
+        }
+        if !changes.is_empty() {
+            diff.insert(name.clone(), changes);
+        }
+    }
+    for name in right.keys().filter(|name| !left.contains_key(*name)) {
+        diff.insert(name.clone(), vec![FieldDiff::OnlyRight]);
+    }
+    diff
+}
+
+/// Collects the left-hand field paths of every `(path, operator, value)` leaf of a domain.
+pub(crate) fn domain_paths(domain: &Value, paths: &mut Vec<String>) {
+    if let Value::Array(items) = domain {
+        match items.as_slice() {
+            [Value::String(path), Value::String(_), _] => paths.push(path.clone()),
+            _ => items.iter().for_each(|item| domain_paths(item, paths)),
+        }
+    }
+}
+
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a String>) -> Option<&'a String> {
+    candidates
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(current)
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::schema::{closest, diff_fields, domain_paths, FieldDiff, Fields};
+
+    #[test]
+    fn test_domain_paths() {
+        let domain = json!([
+            "|",
+            ["partner_id.country_id.code", "=", "ES"],
+            ["id", "in", [1, 2, 3]],
+            [1, "=", 1]
+        ]);
+        let mut paths = vec![];
+        domain_paths(&domain, &mut paths);
+        assert_eq!(paths, vec!["partner_id.country_id.code", "id"]);
+    }
+
+    #[test]
+    fn test_closest() {
+        let names = [String::from("default_code"), String::from("name")];
+        assert_eq!(
+            closest("defautl_code", names.iter()).unwrap(),
+            "default_code"
+        );
+        assert_eq!(closest("barcode", names.iter()), None);
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let left: Fields = serde_json::from_value(json!({
+            "name": {"type": "char", "required": true},
+            "state": {"type": "selection", "selection": [["draft", "Draft"], ["done", "Done"]]},
+            "x_legacy": {"type": "char"},
+            "amount": {"type": "float"},
+        }))
+        .unwrap();
+        let right: Fields = serde_json::from_value(json!({
+            "name": {"type": "char", "required": false},
+            "state": {"type": "selection", "selection": [["draft", "Draft"], ["sent", "Sent"]]},
+            "x_new": {"type": "char"},
+            "amount": {"type": "monetary"},
+        }))
+        .unwrap();
+        let diff = diff_fields(&left, &right);
+        assert_eq!(diff.len(), 5);
+        assert_eq!(
+            diff["name"],
+            vec![FieldDiff::Required {
+                left: true,
+                right: false
+            }]
+        );
+        assert_eq!(
+            diff["state"],
+            vec![FieldDiff::Selection {
+                only_left: vec![json!("done")],
+                only_right: vec![json!("sent")],
+            }]
+        );
+        assert_eq!(diff["x_legacy"], vec![FieldDiff::OnlyLeft]);
+        assert_eq!(diff["x_new"], vec![FieldDiff::OnlyRight]);
+        assert_eq!(
+            diff["amount"],
+            vec![FieldDiff::Type {
+                left: "float".to_string(),
+                right: "monetary".to_string()
+            }]
+        );
+        assert!(diff_fields(&left, &left).is_empty());
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_get_view() {
+        use crate::mock::MockServer;
+
+        // older servers only have fields_view_get
+        let server = MockServer::start().await.unwrap();
+        server.insert("res.partner", json!({"name": "Azure"}));
+        server.on_call("res.partner", "fields_view_get", |args, _| {
+            assert_eq!(args[0], false);
+            Ok(json!({"arch": "<form><field name=\"name\"/></form>", "view_id": 12}))
+        });
+        let odoo = server.odoo().await.unwrap();
+        let view = odoo.get_view("res.partner", None, "form").await.unwrap();
+        assert_eq!(view.view_type, "form");
+        assert!(view.arch.starts_with("<form>"));
+        assert!(view.fields.contains_key("name"));
+
+        let server = MockServer::start().await.unwrap();
+        server.on_call("res.partner", "get_view", |args, _| {
+            assert_eq!(args, [json!(12), json!("tree")]);
+            Ok(json!({
+                "id": 12,
+                "arch": "<tree><field name=\"name\"/></tree>",
+                "fields": {"name": {"type": "char", "string": "Name"}},
+            }))
+        });
+        let odoo = server.odoo().await.unwrap();
+        let view = odoo
+            .get_view("res.partner", Some(12), "tree")
+            .await
+            .unwrap();
+        assert_eq!(view.id, Some(12));
+        assert_eq!(view.fields["name"].field_type, "char");
+    }
+}
@@ -0,0 +1,95 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use serde::Serialize;
+use serde_json::Value;
+use tower_service::Service;
+
+use crate::api::Response;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// A single `execute_kw` call, as accepted by the `tower::Service` implementation of `Odoo`.
+#[derive(Debug, Clone)]
+pub struct Call<T> {
+    pub model: String,
+    pub method: String,
+    pub args: T,
+}
+
+impl<T> Call<T> {
+    pub fn new(model: &str, method: &str, args: T) -> Call<T> {
+        Call {
+            model: model.to_string(),
+            method: method.to_string(),
+            args,
+        }
+    }
+}
+
+impl<T> Service<Call<T>> for Odoo
+where
+    T: Serialize + Send + Sync + 'static,
+{
+    type Response = Response<Value>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Call<T>) -> Self::Future {
+        let odoo = self.clone();
+        Box::pin(async move {
+            odoo.call(&request.model, &request.method, request.args)
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_service() {
+        use std::future::poll_fn;
+
+        use serde_json::{json, Value};
+        use tower_service::Service;
+
+        use crate::error::Error;
+        use crate::mock::MockServer;
+        use crate::service::Call;
+
+        let server = MockServer::start().await.unwrap();
+        let id = server.insert("res.partner", json!({"name": "Azure"}));
+        let mut odoo = server.odoo().await.unwrap();
+
+        poll_fn(|cx| Service::<Call<Value>>::poll_ready(&mut odoo, cx))
+            .await
+            .unwrap();
+        let response = Service::call(
+            &mut odoo,
+            Call::new("res.partner", "read", json!([[id], ["name"]])),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.result, json!([{"id": id, "name": "Azure"}]));
+
+        // server faults come back as the service error
+        server.on_call("res.partner", "action_archive", |_, _| {
+            Err("nope".to_string())
+        });
+        poll_fn(|cx| Service::<Call<Value>>::poll_ready(&mut odoo, cx))
+            .await
+            .unwrap();
+        let result = Service::call(
+            &mut odoo,
+            Call::new("res.partner", "action_archive", json!([[id]])),
+        )
+        .await;
+        assert!(matches!(result, Err(Error::User(_))));
+    }
+}
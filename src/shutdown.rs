@@ -0,0 +1,211 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::queue::OfflineQueue;
+
+/// What `Odoo::shutdown` did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Requests still running when the timeout expired.
+    pub abandoned: usize,
+    /// Queued offline operations sent before closing.
+    pub flushed: usize,
+}
+
+/// Shutdown state shared by all clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct Lifecycle {
+    closed: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    closing: Notify,
+}
+
+pub(crate) struct InFlight(Arc<Lifecycle>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Counts a request in flight, unless the client is shut down and `draining` is false.
+    pub(crate) fn enter(self: &Arc<Self>, draining: bool) -> Result<InFlight, Error> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlight(self.clone());
+        self.check(draining)?;
+        Ok(guard)
+    }
+
+    /// Fails once the client is shut down, unless `draining`; retries check it before
+    /// sending again.
+    pub(crate) fn check(&self, draining: bool) -> Result<(), Error> {
+        if self.closed.load(Ordering::SeqCst) && !draining {
+            return Err(Error::NotSent(String::from("Client is shut down")));
+        }
+        Ok(())
+    }
+
+    /// Resolves once the client is shut down.
+    pub(crate) async fn closed(&self) {
+        loop {
+            let closing = self.closing.notified();
+            if self.closed.load(Ordering::SeqCst) {
+                return;
+            }
+            closing.await;
+        }
+    }
+
+    async fn idle(&self) {
+        loop {
+            let idle = self.idle.notified();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+impl Odoo {
+    /// Stops the client and all its clones from sending new requests, cancels pending bus
+    /// polls, waits up to `timeout` for the requests in flight, then sends what `queue`
+    /// holds. Later calls fail as unreachable, so `OfflineQueue::submit` queues them.
+    pub async fn shutdown(
+        &self,
+        timeout: Duration,
+        queue: Option<&mut OfflineQueue>,
+    ) -> Result<ShutdownReport, Error> {
+        let lifecycle = &self.lifecycle;
+        lifecycle.closed.store(true, Ordering::SeqCst);
+        lifecycle.closing.notify_waiters();
+
+        let _ = tokio::time::timeout(timeout, lifecycle.idle()).await;
+        let abandoned = lifecycle.in_flight.load(Ordering::SeqCst);
+
+        let flushed = match queue {
+            Some(queue) => {
+                let mut odoo = self.clone();
+                odoo.draining = true;
+                queue.replay(&odoo).await?
+            }
+            None => 0,
+        };
+        Ok(ShutdownReport { abandoned, flushed })
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.lifecycle.closed.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_shutdown() {
+        use std::time::Duration;
+
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+        use crate::queue::{OfflineQueue, QueuedOp};
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let path =
+            std::env::temp_dir().join(format!("odoors-shutdown-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut queue = OfflineQueue::open(&path).unwrap();
+        queue
+            .push(QueuedOp::create(
+                "res.partner",
+                json!({"name": "Queued"}).as_object().cloned().unwrap(),
+            ))
+            .unwrap();
+
+        let poller = odoo.clone();
+        let poll = tokio::spawn(async move {
+            poller
+                .wait_notification(&["jobs"], 0, Duration::from_secs(10), |_| true)
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let report = odoo
+            .shutdown(Duration::from_secs(1), Some(&mut queue))
+            .await
+            .unwrap();
+        assert_eq!(report.abandoned, 0);
+        assert_eq!(report.flushed, 1);
+        assert!(queue.is_empty());
+        assert_eq!(server.records("res.partner")[0]["name"], "Queued");
+        assert!(poll.await.unwrap().is_err());
+
+        let clone = odoo.clone();
+        assert!(clone.is_shut_down());
+        let error = clone
+            .call::<_, Vec<u32>>("res.partner", "search", (json!([]),))
+            .await
+            .unwrap_err();
+        assert!(error.is_unreachable());
+        let queued = queue
+            .submit(&clone, QueuedOp::unlink("res.partner", &[1]))
+            .await
+            .unwrap();
+        assert_eq!(queued, None);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_shutdown_waits_for_bodies() {
+        use std::convert::Infallible;
+        use std::net::SocketAddr;
+        use std::time::Duration;
+
+        use hyper::service::{make_service_fn, service_fn};
+        use hyper::{Body, Response, Server};
+
+        use crate::odoo::Odoo;
+
+        // answers with the headers at once and the body 200ms later
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|_| async {
+                let (mut sender, body) = Body::channel();
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    let body =
+                        r#"{"jsonrpc": "2.0", "id": 1, "result": {"server_version": "16.0"}}"#;
+                    let _ = sender.send_data(body.into()).await;
+                });
+                let response = Response::builder()
+                    .header("Content-Type", "application/json")
+                    .body(body);
+                Ok::<_, Infallible>(response.unwrap())
+            }))
+        });
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+
+        let odoo = Odoo::new(&url, "demo");
+        let caller = odoo.clone();
+        let call = tokio::spawn(async move { caller.call_service("common", "version", ()).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let report = odoo.shutdown(Duration::from_secs(2), None).await.unwrap();
+        assert_eq!(report.abandoned, 0);
+        assert!(call.is_finished());
+        assert_eq!(call.await.unwrap().unwrap()["server_version"], "16.0");
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// Field values of a set of records at a point in time, taken by `Odoo::snapshot`.
+///
+/// Snapshots serialize to JSON, so they can be stored and diffed later against the live server.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub model: String,
+    pub fields: Vec<String>,
+    pub records: BTreeMap<u32, Map<String, Value>>,
+}
+
+/// Differences between a snapshot and a later state of the same records.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Changeset {
+    /// Records that no longer exist.
+    pub removed: Vec<u32>,
+    /// Records with at least one changed field.
+    pub changed: BTreeMap<u32, BTreeMap<String, FieldChange>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub before: Value,
+    pub after: Value,
+}
+
+impl Changeset {
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Snapshot {
+    /// Compares with a newer snapshot of the same records.
+    pub fn compare(&self, newer: &Snapshot) -> Changeset {
+        let mut changeset = Changeset::default();
+        for (id, before) in &self.records {
+            let after = match newer.records.get(id) {
+                Some(after) => after,
+                None => {
+                    changeset.removed.push(*id);
+                    continue;
+                }
+            };
+            let changes: BTreeMap<String, FieldChange> = before
+                .iter()
+                .filter(|(field, value)| after.get(*field).is_some_and(|after| after != *value))
+                .map(|(field, value)| {
+                    let change = FieldChange {
+                        before: value.clone(),
+                        after: after[field].clone(),
+                    };
+                    (field.clone(), change)
+                })
+                .collect();
+            if !changes.is_empty() {
+                changeset.changed.insert(*id, changes);
+            }
+        }
+        changeset
+    }
+
+    /// Reads the same records and fields again and compares them with this snapshot.
+    pub async fn diff(&self, odoo: &Odoo) -> Result<Changeset, Error> {
+        let ids: Vec<u32> = self.records.keys().copied().collect();
+        let fields: Vec<&str> = self.fields.iter().map(String::as_str).collect();
+        let current = odoo.snapshot(&self.model, &ids, &fields).await?;
+        Ok(self.compare(&current))
+    }
+}
+
+impl Odoo {
+    /// Reads `fields` of `ids` (all fields if empty), including archived records.
+    /// Ids that don't exist are left out of the snapshot.
+    pub async fn snapshot(
+        &self,
+        model: &str,
+        ids: &[u32],
+        fields: &[&str],
+    ) -> Result<Snapshot, Error> {
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(fields));
+        kwargs.insert("context".to_string(), json!({"active_test": false}));
+        let records: Vec<Map<String, Value>> = self
+            .call_kw(model, "search_read", (vec![("id", "in", ids)],), kwargs)
+            .await?
+            .result;
+        let records = records
+            .into_iter()
+            .filter_map(|mut record| {
+                let id = record.remove("id")?.as_u64()? as u32;
+                Some((id, record))
+            })
+            .collect();
+        Ok(Snapshot {
+            model: model.to_string(),
+            fields: fields.iter().map(|f| f.to_string()).collect(),
+            records,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::{json, Map, Value};
+
+    use crate::snapshot::Snapshot;
+
+    fn snapshot(records: Value) -> Snapshot {
+        let records: BTreeMap<u32, Map<String, Value>> = serde_json::from_value(records).unwrap();
+        Snapshot {
+            model: "res.partner".to_string(),
+            fields: vec!["name".to_string(), "email".to_string()],
+            records,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_compare() {
+        let before = snapshot(json!({
+            "1": {"name": "Azure", "email": false},
+            "2": {"name": "Deco", "email": "deco@example.com"},
+            "3": {"name": "Gemini", "email": false},
+        }));
+        let after = snapshot(json!({
+            "1": {"name": "Azure", "email": "azure@example.com"},
+            "2": {"name": "Deco", "email": "deco@example.com"},
+        }));
+
+        let changeset = before.compare(&after);
+        assert_eq!(changeset.removed, vec![3]);
+        assert_eq!(changeset.changed.len(), 1);
+        let change = &changeset.changed[&1]["email"];
+        assert_eq!(change.before, json!(false));
+        assert_eq!(change.after, json!("azure@example.com"));
+        assert!(before.compare(&before).is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+use std::cell::RefCell;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// What typed results do with fields their type doesn't declare.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownFields {
+    /// Skips them, like serde does.
+    #[default]
+    Ignore,
+    /// Fails with `Error::Decode`, e.g. for test suites to catch model drift.
+    Deny,
+}
+
+impl Odoo {
+    /// Sets how the results of model calls treat fields missing from the target type,
+    /// without `#[serde(deny_unknown_fields)]` on each struct. Note that `read` and
+    /// `search_read` always return `id`.
+    pub fn with_unknown_fields(mut self, unknown_fields: UnknownFields) -> Odoo {
+        self.unknown_fields = unknown_fields;
+        self
+    }
+
+    /// Decodes a typed result per `with_unknown_fields`; every decode of records goes
+    /// through here.
+    pub(crate) fn decode_value<U: DeserializeOwned>(&self, value: Value) -> Result<U, Error> {
+        match self.unknown_fields {
+            UnknownFields::Ignore => from_value(value),
+            UnknownFields::Deny => from_value_strict(&value),
+        }
+    }
+}
+
+/// Deserializes `value`, with the path of the failing field in `path-to-error` mode.
+pub(crate) fn from_value<U: DeserializeOwned>(value: Value) -> Result<U, Error> {
+    #[cfg(feature = "path-to-error")]
+    let result = serde_path_to_error::deserialize(value).map_err(path_error);
+    #[cfg(not(feature = "path-to-error"))]
+    let result = serde_json::from_value(value).map_err(|e| Error::Decode {
+        path: None,
+        message: e.to_string(),
+    });
+    result
+}
+
+#[cfg(feature = "path-to-error")]
+fn path_error(error: serde_path_to_error::Error<serde_json::Error>) -> Error {
+    Error::Decode {
+        path: Some(error.path().to_string()),
+        message: error.inner().to_string(),
+    }
+}
+
+/// Deserializes `value`, failing on the fields `U` ignores.
+pub(crate) fn from_value_strict<U: DeserializeOwned>(value: &Value) -> Result<U, Error> {
+    let ignored = RefCell::new(vec![]);
+    let tracking = Tracking {
+        value,
+        path: String::new(),
+        ignored: &ignored,
+    };
+    #[cfg(feature = "path-to-error")]
+    let result = serde_path_to_error::deserialize(tracking).map_err(path_error)?;
+    #[cfg(not(feature = "path-to-error"))]
+    let result = U::deserialize(tracking).map_err(|e| Error::Decode {
+        path: None,
+        message: e.to_string(),
+    })?;
+    let ignored = ignored.into_inner();
+    match ignored.first() {
+        Some(path) => Err(Error::Decode {
+            path: Some(path.clone()),
+            message: match ignored.len() {
+                1 => String::from("Unknown field"),
+                n => format!("{} unknown fields, first one", n),
+            },
+        }),
+        None => Ok(result),
+    }
+}
+
+/// A `Value` deserializer recording the paths of the values the target type ignores.
+struct Tracking<'de, 'a> {
+    value: &'de Value,
+    path: String,
+    ignored: &'a RefCell<Vec<String>>,
+}
+
+impl<'de, 'a> Tracking<'de, 'a> {
+    fn child(&self, value: &'de Value, path: String) -> Tracking<'de, 'a> {
+        Tracking {
+            value,
+            path,
+            ignored: self.ignored,
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for Tracking<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(map) => visitor.visit_map(TrackingMap {
+                entries: map.iter(),
+                value: None,
+                parent: &self,
+            }),
+            Value::Array(values) => visitor.visit_seq(TrackingSeq {
+                values: values.iter().enumerate(),
+                parent: &self,
+            }),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.ignored.borrow_mut().push(self.path);
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct TrackingMap<'de, 'a, 'p> {
+    entries: serde_json::map::Iter<'de>,
+    value: Option<(&'de String, &'de Value)>,
+    parent: &'p Tracking<'de, 'a>,
+}
+
+impl<'de> MapAccess<'de> for TrackingMap<'de, '_, '_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                seed.deserialize(BorrowedStrDeserializer::new(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        let path = match self.parent.path.as_str() {
+            "" => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        };
+        seed.deserialize(self.parent.child(value, path))
+    }
+}
+
+struct TrackingSeq<'de, 'a, 'p> {
+    values: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+    parent: &'p Tracking<'de, 'a>,
+}
+
+impl<'de> SeqAccess<'de> for TrackingSeq<'de, '_, '_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.values.next() {
+            Some((index, value)) => {
+                let path = format!("{}[{}]", self.parent.path, index);
+                seed.deserialize(self.parent.child(value, path)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use crate::error::Error;
+    use crate::odoo::deserialize_odoo_optional;
+    use crate::strict::from_value_strict;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Partner {
+        id: u32,
+        name: String,
+        #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+        email: Option<String>,
+        tags: Vec<Tag>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Tag {
+        name: String,
+    }
+
+    #[test]
+    fn test_from_value_strict() {
+        let records = json!([
+            {"id": 1, "name": "Azure", "email": false, "tags": [{"name": "VIP"}]},
+        ]);
+        let partners: Vec<Partner> = from_value_strict(&records).unwrap();
+        assert_eq!(partners[0].email, None);
+
+        let records = json!([
+            {"id": 1, "name": "Azure", "email": false, "tags": []},
+            {"id": 2, "name": "Deco", "email": "deco@example.com", "tags": [{"name": "VIP", "color": 3}], "phone": "555"},
+        ]);
+        let error = from_value_strict::<Vec<Partner>>(&records).unwrap_err();
+        assert!(matches!(&error, Error::Decode { path: Some(path), .. } if path == "[1].phone"));
+        assert_eq!(
+            error.to_string(),
+            "2 unknown fields, first one at [1].phone"
+        );
+
+        // untyped values keep everything
+        let values: Vec<Value> = from_value_strict(&records).unwrap();
+        assert_eq!(values[1]["phone"], "555");
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_unknown_fields() {
+        use crate::mock::MockServer;
+        use crate::strict::UnknownFields;
+
+        #[derive(Deserialize)]
+        struct Named {
+            #[allow(dead_code)]
+            name: String,
+        }
+
+        let server = MockServer::start().await.unwrap();
+        server.insert("res.partner", json!({"name": "Azure", "ref": "P001"}));
+        let odoo = server.odoo().await.unwrap();
+
+        let records: Vec<Named> = odoo
+            .read("res.partner", &[1], &["name", "ref"])
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+
+        let odoo = odoo.with_unknown_fields(UnknownFields::Deny);
+        let error = odoo
+            .read::<Named>("res.partner", &[1], &["name", "ref"])
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, Error::Decode { path: Some(path), .. } if path == "[0].id"));
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+/// The value of a translatable field in one language.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FieldTranslation {
+    pub lang: String,
+    /// The source text (the `en_US` value, or the term for `translate=html_translate` fields).
+    pub source: String,
+    /// The translation, `None` when missing.
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    pub value: Option<String>,
+}
+
+/// Values of a record per field, then per language, returned by `read_translated`.
+pub type Translated = BTreeMap<String, BTreeMap<String, Value>>;
+
+#[derive(Deserialize)]
+struct IrTranslation {
+    lang: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    src: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    value: Option<String>,
+}
+
+impl Odoo {
+    /// Translations of `field` of record `id`, through `get_field_translations` on Odoo 16+
+    /// and `ir.translation` before.
+    pub async fn field_translations(
+        &self,
+        model: &str,
+        id: u32,
+        field: &str,
+    ) -> Result<Vec<FieldTranslation>, Error> {
+        // returns `(translations, context)`
+        let result: Result<(Vec<FieldTranslation>, Value), Error> = self
+            .call(model, "get_field_translations", ([id], field))
+            .await
+            .map(|response| response.result);
+        match result {
+            Ok((translations, _)) => Ok(translations),
+            Err(e) if e.message().contains("get_field_translations") => {
+                let mut kwargs = Map::new();
+                kwargs.insert("fields".to_string(), json!(["lang", "src", "value"]));
+                kwargs.insert("order".to_string(), Value::from("lang"));
+                let translations: Vec<IrTranslation> = self
+                    .call_kw(
+                        "ir.translation",
+                        "search_read",
+                        (json!([
+                            ["name", "=", format!("{},{}", model, field)],
+                            ["res_id", "=", id],
+                        ]),),
+                        kwargs,
+                    )
+                    .await?
+                    .result;
+                Ok(translations
+                    .into_iter()
+                    .map(|translation| FieldTranslation {
+                        lang: translation.lang,
+                        source: translation.src.unwrap_or_default(),
+                        value: translation.value.filter(|value| !value.is_empty()),
+                    })
+                    .collect())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sets the value of `field` of record `id` per language (`{"fr_FR": "Bureau"}`),
+    /// through `update_field_translations` on Odoo 16+ and writes in each language before.
+    pub async fn update_field_translations(
+        &self,
+        model: &str,
+        id: u32,
+        field: &str,
+        translations: &BTreeMap<String, String>,
+    ) -> Result<(), Error> {
+        let result = self
+            .call::<_, Value>(
+                model,
+                "update_field_translations",
+                ([id], field, translations),
+            )
+            .await;
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if e.message().contains("update_field_translations") => {
+                for (lang, value) in translations {
+                    let mut kwargs = Map::new();
+                    kwargs.insert("context".to_string(), json!({ "lang": lang }));
+                    let _: bool = self
+                        .call_kw(model, "write", ([id], json!({ field: value })), kwargs)
+                        .await?
+                        .result;
+                }
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reads `fields` of `ids` once per language in `langs` and gathers the values per
+    /// record, field and language.
+    pub async fn read_translated(
+        &self,
+        model: &str,
+        ids: &[u32],
+        fields: &[&str],
+        langs: &[&str],
+    ) -> Result<BTreeMap<u32, Translated>, Error> {
+        let mut translated: BTreeMap<u32, Translated> = BTreeMap::new();
+        for lang in langs {
+            let mut kwargs = Map::new();
+            kwargs.insert("fields".to_string(), json!(fields));
+            kwargs.insert("context".to_string(), json!({ "lang": lang }));
+            let records: Vec<Map<String, Value>> =
+                self.call_kw(model, "read", (ids,), kwargs).await?.result;
+            for record in records {
+                let id = record.get("id").and_then(Value::as_u64).unwrap_or_default() as u32;
+                let values = translated.entry(id).or_default();
+                for (field, value) in record {
+                    if field != "id" {
+                        values
+                            .entry(field)
+                            .or_default()
+                            .insert(lang.to_string(), value);
+                    }
+                }
+            }
+        }
+        Ok(translated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_field_translations() {
+        use std::collections::BTreeMap;
+
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+        use crate::translation::FieldTranslation;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let id = server.insert("product.template", json!({"name": "Desk"}));
+
+        // before Odoo 16
+        server.insert(
+            "ir.translation",
+            json!({"name": "product.template,name", "res_id": id, "lang": "fr_FR", "src": "Desk", "value": "Bureau"}),
+        );
+        server.insert(
+            "ir.translation",
+            json!({"name": "product.template,name", "res_id": id, "lang": "es_ES", "src": "Desk", "value": ""}),
+        );
+        let translations = odoo
+            .field_translations("product.template", id, "name")
+            .await
+            .unwrap();
+        assert_eq!(
+            translations,
+            vec![
+                FieldTranslation {
+                    lang: "es_ES".to_string(),
+                    source: "Desk".to_string(),
+                    value: None
+                },
+                FieldTranslation {
+                    lang: "fr_FR".to_string(),
+                    source: "Desk".to_string(),
+                    value: Some("Bureau".to_string())
+                },
+            ]
+        );
+
+        let updates = BTreeMap::from([("es_ES".to_string(), "Escritorio".to_string())]);
+        odoo.update_field_translations("product.template", id, "name", &updates)
+            .await
+            .unwrap();
+        assert_eq!(server.records("product.template")[0]["name"], "Escritorio");
+
+        // Odoo 16+
+        server.on_call("product.template", "get_field_translations", |args, _| {
+            assert_eq!(args[1], "name");
+            Ok(json!([[{"lang": "fr_FR", "source": "Desk", "value": "Bureau"}], {}]))
+        });
+        server.on_call(
+            "product.template",
+            "update_field_translations",
+            |args, _| {
+                assert_eq!(args[2], json!({"es_ES": "Escritorio"}));
+                Ok(json!(true))
+            },
+        );
+        let translations = odoo
+            .field_translations("product.template", id, "name")
+            .await
+            .unwrap();
+        assert_eq!(translations[0].value.as_deref(), Some("Bureau"));
+        odoo.update_field_translations("product.template", id, "name", &updates)
+            .await
+            .unwrap();
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_read_translated() {
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        server.on_call("product.template", "read", |args, kwargs| {
+            let name = match kwargs["context"]["lang"].as_str() {
+                Some("fr_FR") => "Bureau",
+                _ => "Desk",
+            };
+            assert_eq!(args[0], json!([5]));
+            Ok(json!([{"id": 5, "name": name, "list_price": 120.0}]))
+        });
+
+        let translated = odoo
+            .read_translated(
+                "product.template",
+                &[5],
+                &["name", "list_price"],
+                &["en_US", "fr_FR"],
+            )
+            .await
+            .unwrap();
+        let name = &translated[&5]["name"];
+        assert_eq!(name["en_US"], "Desk");
+        assert_eq!(name["fr_FR"], "Bureau");
+        assert_eq!(translated[&5]["list_price"]["fr_FR"], Value::from(120.0));
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::schema::{FieldInfo, Fields};
+
+/// A value returned by `read`/`search_read` that doesn't match the type `fields_get` gives
+/// for its field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    pub model: String,
+    pub field: String,
+    /// The field type, e.g. `char`.
+    pub expected: String,
+    pub value: Value,
+    /// Index of the record in the result.
+    pub record: usize,
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}: expected {}, got {}",
+            self.model, self.field, self.expected, self.value
+        )
+    }
+}
+
+/// What `Odoo::with_response_validation` does with a `TypeMismatch`.
+#[derive(Clone)]
+pub enum MismatchPolicy {
+    /// Fails the call with `Error::Decode`.
+    Error,
+    /// Hands the mismatches to a hook and returns the result anyway.
+    Warn(Arc<dyn Fn(&TypeMismatch) + Send + Sync>),
+}
+
+impl MismatchPolicy {
+    pub fn warn<F>(hook: F) -> MismatchPolicy
+    where
+        F: Fn(&TypeMismatch) + Send + Sync + 'static,
+    {
+        MismatchPolicy::Warn(Arc::new(hook))
+    }
+}
+
+impl fmt::Debug for MismatchPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MismatchPolicy::Error => f.write_str("Error"),
+            MismatchPolicy::Warn(_) => f.write_str("Warn"),
+        }
+    }
+}
+
+impl Odoo {
+    /// Debug aid: cross-checks the values returned by `read`/`search_read` against the field
+    /// types of `fields_get`, catching server customizations that break typed models.
+    /// `false` stands for an empty value of any non-numeric field, unless it is required.
+    pub fn with_response_validation(mut self, policy: MismatchPolicy) -> Odoo {
+        self.response_validation = Some(policy);
+        self
+    }
+
+    pub(crate) fn validates_response(&self, method: &str) -> bool {
+        self.response_validation.is_some() && matches!(method, "read" | "search_read")
+    }
+
+    pub(crate) async fn validate_response(&self, model: &str, result: &Value) -> Result<(), Error> {
+        let policy = match &self.response_validation {
+            Some(policy) => policy,
+            None => return Ok(()),
+        };
+        let fields = self.fields_get(model).await?;
+        for mismatch in type_mismatches(model, &fields, result) {
+            match policy {
+                MismatchPolicy::Error => {
+                    return Err(Error::Decode {
+                        path: Some(format!("[{}].{}", mismatch.record, mismatch.field)),
+                        message: mismatch.to_string(),
+                    })
+                }
+                MismatchPolicy::Warn(hook) => hook(&mismatch),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn type_mismatches(model: &str, fields: &Fields, records: &Value) -> Vec<TypeMismatch> {
+    let records = match records {
+        Value::Array(records) => records,
+        _ => return vec![],
+    };
+    let mut mismatches = vec![];
+    for (index, record) in records.iter().enumerate() {
+        let record = match record {
+            Value::Object(record) => record,
+            _ => continue,
+        };
+        for (name, value) in record {
+            match fields.get(name) {
+                Some(field) if !matches_type(field, value) => mismatches.push(TypeMismatch {
+                    model: model.to_string(),
+                    field: name.to_string(),
+                    expected: field.field_type.clone(),
+                    value: value.clone(),
+                    record: index,
+                }),
+                _ => {}
+            }
+        }
+    }
+    mismatches
+}
+
+fn matches_type(field: &FieldInfo, value: &Value) -> bool {
+    let empty = value == &Value::Bool(false) && !field.required;
+    match field.field_type.as_str() {
+        "boolean" => value.is_boolean(),
+        "integer" | "many2one_reference" => value.is_i64() || value.is_u64() || empty,
+        "float" | "monetary" => value.is_number(),
+        "char" | "text" | "html" | "date" | "datetime" | "binary" | "reference" => {
+            value.is_string() || empty
+        }
+        // keys are strings, or integers for some custom selections
+        "selection" => value.is_string() || value.is_i64() || empty,
+        "many2one" => match value {
+            Value::Array(pair) => pair.len() == 2 && pair[0].is_u64(),
+            value => value.is_u64() || empty,
+        },
+        "one2many" | "many2many" => value
+            .as_array()
+            .is_some_and(|ids| ids.iter().all(Value::is_u64)),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::schema::Fields;
+    use crate::typecheck::type_mismatches;
+
+    #[test]
+    fn test_type_mismatches() {
+        let fields: Fields = serde_json::from_value(json!({
+            "name": {"type": "char", "required": true},
+            "email": {"type": "char"},
+            "parent_id": {"type": "many2one", "relation": "res.partner"},
+            "category_id": {"type": "many2many", "relation": "res.partner.category"},
+            "credit_limit": {"type": "float"},
+            "active": {"type": "boolean"},
+        }))
+        .unwrap();
+        let records = json!([
+            {"id": 1, "name": "Azure", "email": false, "parent_id": [3, "Acme"], "category_id": [1, 2], "credit_limit": 0.0, "active": true},
+            {"id": 2, "name": false, "email": 42, "parent_id": "Acme", "category_id": [], "credit_limit": "0", "active": true},
+        ]);
+        let mismatches = type_mismatches("res.partner", &fields, &records);
+        let fields: Vec<&str> = mismatches.iter().map(|m| m.field.as_str()).collect();
+        assert_eq!(fields, vec!["credit_limit", "email", "name", "parent_id"]);
+        assert!(mismatches.iter().all(|m| m.record == 1));
+        assert_eq!(
+            mismatches[2].to_string(),
+            "res.partner.name: expected char, got false"
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_response_validation() {
+        use std::sync::{Arc, Mutex};
+
+        use serde_json::Value;
+
+        use crate::error::Error;
+        use crate::mock::MockServer;
+        use crate::typecheck::{MismatchPolicy, TypeMismatch};
+
+        let server = MockServer::start().await.unwrap();
+        server.insert("res.partner", json!({"name": "Azure", "ref": "P001"}));
+        server.on_call("res.partner", "fields_get", |_, _| {
+            Ok(json!({"name": {"type": "char"}, "ref": {"type": "integer"}}))
+        });
+
+        let warnings: Arc<Mutex<Vec<TypeMismatch>>> = Arc::default();
+        let sink = warnings.clone();
+        let odoo = server.odoo().await.unwrap();
+        let warned = odoo
+            .clone()
+            .with_response_validation(MismatchPolicy::warn(move |m| {
+                sink.lock().unwrap().push(m.clone())
+            }));
+        let records: Vec<Value> = warned
+            .read("res.partner", &[1], &["name", "ref"])
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(warnings.lock().unwrap()[0].field, "ref");
+
+        let strict = odoo.with_response_validation(MismatchPolicy::Error);
+        let error = strict
+            .read::<Value>("res.partner", &[1], &["name", "ref"])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Decode { path: Some(path), .. } if path == "[0].ref"));
+    }
+}
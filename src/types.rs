@@ -0,0 +1,392 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, serialize_odoo_nullable, Odoo};
+
+/// An optional field value following Odoo's convention that `false` means empty.
+///
+/// Deserializes `false`/`null` to `None` and serializes `None` back to `false`, so it can
+/// be used in typed models without a `deserialize_with` attribute on every field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct OdooOption<T>(pub Option<T>);
+
+impl<T> OdooOption<T> {
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for OdooOption<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Option<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for OdooOption<T> {
+    fn deref_mut(&mut self) -> &mut Option<T> {
+        &mut self.0
+    }
+}
+
+impl<T> From<Option<T>> for OdooOption<T> {
+    fn from(value: Option<T>) -> OdooOption<T> {
+        OdooOption(value)
+    }
+}
+
+impl<T> From<OdooOption<T>> for Option<T> {
+    fn from(value: OdooOption<T>) -> Option<T> {
+        value.0
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for OdooOption<T> {
+    fn deserialize<D: Deserializer<'de>>(data: D) -> Result<OdooOption<T>, D::Error> {
+        deserialize_odoo_optional(data).map(OdooOption)
+    }
+}
+
+impl<T: Serialize> Serialize for OdooOption<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_odoo_nullable(&self.0, serializer)
+    }
+}
+
+/// A typed model, tying a struct to the Odoo model it is read from.
+pub trait Model: DeserializeOwned {
+    /// Technical name, e.g. `res.partner`.
+    const MODEL: &'static str;
+    /// Fields to read, all of them when empty.
+    const FIELDS: &'static [&'static str] = &[];
+}
+
+/// A many2one link to a `T`, loaded on demand with `fetch`.
+///
+/// Deserializes from `[id, display_name]` or a plain id (`load=""`), and serializes to the
+/// id for writes. Use `Relation<T>` for links that may be empty.
+pub struct Ref<T> {
+    pub id: u32,
+    /// The display name, when read along with the id.
+    pub name: Option<String>,
+    model: PhantomData<fn() -> T>,
+}
+
+impl<T> Ref<T> {
+    pub fn new(id: u32) -> Ref<T> {
+        Ref {
+            id,
+            name: None,
+            model: PhantomData,
+        }
+    }
+}
+
+impl<T: Model> Ref<T> {
+    /// Reads the linked record.
+    pub async fn fetch(&self, odoo: &Odoo) -> Result<T, Error> {
+        odoo.read(T::MODEL, &[self.id], T::FIELDS)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("{}({}) does not exist", T::MODEL, self.id)))
+    }
+}
+
+impl<T> Clone for Ref<T> {
+    fn clone(&self) -> Ref<T> {
+        Ref {
+            id: self.id,
+            name: self.name.clone(),
+            model: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for Ref<T> {
+    fn eq(&self, other: &Ref<T>) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Ref<T> {}
+
+impl<T> fmt::Debug for Ref<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ref")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Ref<T> {
+    fn deserialize<D: Deserializer<'de>>(data: D) -> Result<Ref<T>, D::Error> {
+        let (id, name) = match Value::deserialize(data)? {
+            Value::Array(pair) => (
+                pair.first().and_then(Value::as_u64),
+                pair.get(1).and_then(Value::as_str).map(str::to_string),
+            ),
+            value => (value.as_u64(), None),
+        };
+        let id = id.ok_or_else(|| D::Error::custom("expected a many2one id"))?;
+        Ok(Ref {
+            id: id as u32,
+            name,
+            model: PhantomData,
+        })
+    }
+}
+
+impl<T> Serialize for Ref<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.id)
+    }
+}
+
+/// An optional many2one link to a `T`: `false` when empty, `[id, display_name]` or a plain
+/// id otherwise, serialized to `false` or the id for writes. Needs no `deserialize_with`,
+/// and `T` may be left out when the linked model isn't typed.
+pub struct Relation<T = ()>(Option<Ref<T>>);
+
+impl<T> Relation<T> {
+    pub fn new(id: u32) -> Relation<T> {
+        Relation(Some(Ref::new(id)))
+    }
+
+    /// An empty link, written as `false`.
+    pub fn none() -> Relation<T> {
+        Relation(None)
+    }
+
+    pub fn id(&self) -> Option<u32> {
+        self.0.as_ref().map(|link| link.id)
+    }
+
+    /// The display name, when read along with the id.
+    pub fn name(&self) -> Option<&str> {
+        self.0.as_ref().and_then(|link| link.name.as_deref())
+    }
+
+    pub fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn as_ref(&self) -> Option<&Ref<T>> {
+        self.0.as_ref()
+    }
+
+    pub fn into_inner(self) -> Option<Ref<T>> {
+        self.0
+    }
+}
+
+impl<T: Model> Relation<T> {
+    /// Reads the linked record, `None` when the link is empty.
+    pub async fn fetch(&self, odoo: &Odoo) -> Result<Option<T>, Error> {
+        match &self.0 {
+            Some(link) => link.fetch(odoo).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T> Default for Relation<T> {
+    fn default() -> Relation<T> {
+        Relation(None)
+    }
+}
+
+impl<T> Clone for Relation<T> {
+    fn clone(&self) -> Relation<T> {
+        Relation(self.0.clone())
+    }
+}
+
+impl<T> PartialEq for Relation<T> {
+    fn eq(&self, other: &Relation<T>) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Relation<T> {}
+
+impl<T> fmt::Debug for Relation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Some(link) => f
+                .debug_struct("Relation")
+                .field("id", &link.id)
+                .field("name", &link.name)
+                .finish(),
+            None => f.write_str("Relation(false)"),
+        }
+    }
+}
+
+impl<T> From<Ref<T>> for Relation<T> {
+    fn from(link: Ref<T>) -> Relation<T> {
+        Relation(Some(link))
+    }
+}
+
+impl<T> From<Option<u32>> for Relation<T> {
+    fn from(id: Option<u32>) -> Relation<T> {
+        Relation(id.map(Ref::new))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Relation<T> {
+    fn deserialize<D: Deserializer<'de>>(data: D) -> Result<Relation<T>, D::Error> {
+        match Value::deserialize(data)? {
+            Value::Bool(false) | Value::Null => Ok(Relation(None)),
+            value => Ref::deserialize(value)
+                .map(|link| Relation(Some(link)))
+                .map_err(D::Error::custom),
+        }
+    }
+}
+
+impl<T> Serialize for Relation<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Some(link) => link.serialize(serializer),
+            None => serializer.serialize_bool(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use crate::types::{Model, OdooOption};
+
+    #[derive(Serialize, Deserialize)]
+    struct Partner {
+        name: String,
+        email: OdooOption<String>,
+    }
+
+    impl Model for Partner {
+        const MODEL: &'static str = "res.partner";
+        const FIELDS: &'static [&'static str] = &["name", "email"];
+    }
+
+    #[test]
+    fn test_odoo_option_round_trip() {
+        let partner: Partner =
+            serde_json::from_value(json!({"name": "Azure", "email": false})).unwrap();
+        assert_eq!(*partner.email, None);
+        assert_eq!(
+            serde_json::to_value(&partner).unwrap(),
+            json!({"name": "Azure", "email": false})
+        );
+
+        let partner: Partner =
+            serde_json::from_value(json!({"name": "Azure", "email": "a@b.c"})).unwrap();
+        assert_eq!(partner.email.as_deref(), Some("a@b.c"));
+        assert_eq!(serde_json::to_value(&partner).unwrap()["email"], "a@b.c");
+    }
+
+    #[test]
+    fn test_ref() {
+        use crate::types::Ref;
+
+        #[derive(Deserialize, Serialize)]
+        struct Order {
+            partner_id: Ref<Partner>,
+            user_id: OdooOption<Ref<Partner>>,
+        }
+
+        let order: Order = serde_json::from_value(json!({
+            "partner_id": [7, "Azure"],
+            "user_id": false,
+        }))
+        .unwrap();
+        assert_eq!(order.partner_id.id, 7);
+        assert_eq!(order.partner_id.name.as_deref(), Some("Azure"));
+        assert_eq!(*order.user_id, None);
+        assert_eq!(
+            serde_json::to_value(&order).unwrap(),
+            json!({"partner_id": 7, "user_id": false})
+        );
+
+        let order: Order = serde_json::from_value(json!({"partner_id": 7, "user_id": 2})).unwrap();
+        assert_eq!(order.partner_id, Ref::new(7));
+        assert_eq!(order.user_id.as_ref().map(|user| user.id), Some(2));
+    }
+
+    #[test]
+    fn test_relation() {
+        use crate::types::Relation;
+
+        #[derive(Deserialize, Serialize)]
+        struct Order {
+            partner_id: Relation<Partner>,
+            user_id: Relation,
+            #[serde(default)]
+            team_id: Relation,
+        }
+
+        let order: Order = serde_json::from_value(json!({
+            "partner_id": [7, "Azure"],
+            "user_id": false,
+        }))
+        .unwrap();
+        assert_eq!(order.partner_id.id(), Some(7));
+        assert_eq!(order.partner_id.name(), Some("Azure"));
+        assert!(order.user_id.is_none());
+        assert!(order.team_id.is_none());
+        assert_eq!(
+            serde_json::to_value(&order).unwrap(),
+            json!({"partner_id": 7, "user_id": false, "team_id": false})
+        );
+
+        let order: Order =
+            serde_json::from_value(json!({"partner_id": 7, "user_id": 2, "team_id": null}))
+                .unwrap();
+        assert_eq!(order.partner_id, Relation::new(7));
+        assert_eq!(order.user_id.id(), Some(2));
+        assert!(serde_json::from_value::<Relation>(json!("Azure")).is_err());
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_ref_fetch() {
+        use crate::error::Error;
+        use crate::mock::MockServer;
+        use crate::types::{Ref, Relation};
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let id = server.insert("res.partner", json!({"name": "Azure", "email": false}));
+
+        let partner = Ref::<Partner>::new(id).fetch(&odoo).await.unwrap();
+        assert_eq!(partner.name, "Azure");
+        assert!(matches!(
+            Ref::<Partner>::new(id + 1).fetch(&odoo).await,
+            Err(Error::NotFound(_))
+        ));
+
+        let link: Relation<Partner> = Relation::new(id);
+        assert_eq!(link.fetch(&odoo).await.unwrap().unwrap().name, "Azure");
+        assert!(Relation::<Partner>::none()
+            .fetch(&odoo)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
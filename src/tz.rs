@@ -0,0 +1,89 @@
+use chrono::{NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+/// Format of datetime values over RPC, always in UTC.
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// A user's timezone, converting between the UTC datetimes Odoo stores and the wall-clock
+/// times the user sees in the web client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserTz(pub Tz);
+
+#[derive(Deserialize)]
+struct UserContext {
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    tz: Option<String>,
+}
+
+impl Odoo {
+    /// The timezone of the logged-in user (`res.users.context_get`), UTC when unset.
+    pub async fn user_tz(&self) -> Result<UserTz, Error> {
+        let context: UserContext = self.call("res.users", "context_get", ()).await?.result;
+        match context.tz {
+            Some(tz) => UserTz::parse(&tz),
+            None => Ok(UserTz(Tz::UTC)),
+        }
+    }
+}
+
+impl UserTz {
+    /// Parses an IANA name such as `Europe/Madrid`.
+    pub fn parse(name: &str) -> Result<UserTz, Error> {
+        name.parse()
+            .map(UserTz)
+            .map_err(|e| Error::InvalidField(format!("Unknown timezone {}: {}", name, e)))
+    }
+
+    /// The wall-clock time of a stored datetime (`2024-05-01 08:00:00`).
+    pub fn to_local(&self, stored: &str) -> Result<NaiveDateTime, Error> {
+        let utc = NaiveDateTime::parse_from_str(stored, DATETIME_FORMAT)
+            .map_err(|e| Error::InvalidField(format!("Invalid datetime {}: {}", stored, e)))?;
+        Ok(self.0.from_utc_datetime(&utc).naive_local())
+    }
+
+    /// The value to store for a wall-clock time. When clocks go back, the earlier of the
+    /// two instants is used; times skipped when clocks go forward are an error.
+    pub fn to_stored(&self, local: &NaiveDateTime) -> Result<String, Error> {
+        let datetime = self
+            .0
+            .from_local_datetime(local)
+            .earliest()
+            .ok_or_else(|| {
+                Error::InvalidField(format!("{} does not exist in {}", local, self.0.name()))
+            })?;
+        Ok(datetime.naive_utc().format(DATETIME_FORMAT).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::tz::UserTz;
+
+    #[test]
+    fn test_user_tz() {
+        let tz = UserTz::parse("Europe/Madrid").unwrap();
+        let local = tz.to_local("2024-07-01 08:00:00").unwrap();
+        assert_eq!(local.to_string(), "2024-07-01 10:00:00");
+        assert_eq!(tz.to_stored(&local).unwrap(), "2024-07-01 08:00:00");
+
+        let winter = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap();
+        assert_eq!(tz.to_stored(&winter).unwrap(), "2024-01-15 08:30:00");
+
+        // 02:30 is skipped on the last Sunday of March
+        let skipped = NaiveDate::from_ymd_opt(2024, 3, 31)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        assert!(tz.to_stored(&skipped).is_err());
+        assert!(UserTz::parse("Mars/Olympus").is_err());
+    }
+}
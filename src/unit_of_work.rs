@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::values::Values;
+
+/// A record to be created by a `UnitOfWork`, standing for its id until it is flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PendingId(usize);
+
+/// One batched call of a `UnitOfWork` flush.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    /// A single `create` of several records, which only link to records created before.
+    Create {
+        model: String,
+        records: Vec<PendingId>,
+    },
+    /// A single `write` of the same values to several records.
+    Write {
+        model: String,
+        ids: Vec<u32>,
+        values: Map<String, Value>,
+    },
+    Unlink {
+        model: String,
+        ids: Vec<u32>,
+    },
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Step::Create { model, records } => write!(f, "create {} x{}", model, records.len()),
+            Step::Write { model, ids, values } => {
+                let fields: Vec<&str> = values.keys().map(String::as_str).collect();
+                write!(f, "write {} {:?}: {}", model, ids, fields.join(", "))
+            }
+            Step::Unlink { model, ids } => write!(f, "unlink {} {:?}", model, ids),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PendingCreate {
+    model: String,
+    values: Map<String, Value>,
+    /// Many2one fields pointing to other pending records.
+    links: Vec<(String, PendingId)>,
+}
+
+/// Collects creates, writes and unlinks across models and sends them in as few calls as
+/// possible: creates first, by model and in dependency order, then writes grouped by
+/// identical values, then unlinks.
+///
+/// ```ignore
+/// let mut work = UnitOfWork::new();
+/// let company = work.create("res.partner", Values::new().set("name", "Azure").set("is_company", true));
+/// let contact = work.create("res.partner", Values::new().set("name", "Joel"));
+/// work.link(contact, "parent_id", company);
+/// work.unlink("res.partner", &[42]);
+/// println!("{}", work.dry_run()?);
+/// let ids = work.flush(&odoo).await?;
+/// ```
+///
+/// Steps already sent are not rolled back if a later one fails.
+#[derive(Debug, Clone, Default)]
+pub struct UnitOfWork {
+    creates: Vec<PendingCreate>,
+    writes: Vec<(String, Vec<u32>, Map<String, Value>)>,
+    unlinks: Vec<(String, Vec<u32>)>,
+}
+
+impl UnitOfWork {
+    pub fn new() -> UnitOfWork {
+        UnitOfWork::default()
+    }
+
+    pub fn create(&mut self, model: &str, values: impl Into<Values>) -> PendingId {
+        self.creates.push(PendingCreate {
+            model: model.to_string(),
+            values: values.into().into_map(),
+            links: vec![],
+        });
+        PendingId(self.creates.len() - 1)
+    }
+
+    /// Sets many2one `field` of pending `record` to the pending `target`, so `target` is
+    /// created first.
+    pub fn link(&mut self, record: PendingId, field: &str, target: PendingId) {
+        if let Some(create) = self.creates.get_mut(record.0) {
+            create.links.push((field.to_string(), target));
+        }
+    }
+
+    pub fn write(&mut self, model: &str, ids: &[u32], values: impl Into<Values>) {
+        self.writes
+            .push((model.to_string(), ids.to_vec(), values.into().into_map()));
+    }
+
+    pub fn unlink(&mut self, model: &str, ids: &[u32]) {
+        self.unlinks.push((model.to_string(), ids.to_vec()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty() && self.writes.is_empty() && self.unlinks.is_empty()
+    }
+
+    /// The calls `flush` would send, in order.
+    pub fn plan(&self) -> Result<Vec<Step>, Error> {
+        let mut steps = vec![];
+
+        // a record's level is one more than the highest level of the records it links to
+        let mut levels: Vec<Option<usize>> = vec![None; self.creates.len()];
+        let mut remaining = self.creates.len();
+        while remaining > 0 {
+            let mut progress = false;
+            for (index, create) in self.creates.iter().enumerate() {
+                if levels[index].is_some() {
+                    continue;
+                }
+                let mut level = Some(0);
+                for (_, target) in &create.links {
+                    let target = levels.get(target.0).ok_or_else(|| {
+                        Error::Request(format!("Unknown pending record {}", target.0))
+                    })?;
+                    level = match (level, target) {
+                        (Some(level), Some(target)) => Some(level.max(target + 1)),
+                        _ => None,
+                    };
+                }
+                if level.is_some() {
+                    levels[index] = level;
+                    remaining -= 1;
+                    progress = true;
+                }
+            }
+            if !progress {
+                return Err(Error::Request(String::from(
+                    "Pending records link to each other in a cycle",
+                )));
+            }
+        }
+        let depth = levels.iter().flatten().max().map_or(0, |level| level + 1);
+        for level in 0..depth {
+            let mut batches: Vec<(String, Vec<PendingId>)> = vec![];
+            for (index, create) in self.creates.iter().enumerate() {
+                if levels[index] != Some(level) {
+                    continue;
+                }
+                match batches.iter_mut().find(|(model, _)| model == &create.model) {
+                    Some((_, records)) => records.push(PendingId(index)),
+                    None => batches.push((create.model.clone(), vec![PendingId(index)])),
+                }
+            }
+            steps.extend(
+                batches
+                    .into_iter()
+                    .map(|(model, records)| Step::Create { model, records }),
+            );
+        }
+
+        let mut writes: Vec<(String, Vec<u32>, Map<String, Value>)> = vec![];
+        for (model, ids, values) in &self.writes {
+            match writes
+                .iter_mut()
+                .find(|(m, _, v)| m == model && v == values)
+            {
+                Some((_, batch, _)) => merge_ids(batch, ids),
+                None => writes.push((model.clone(), ids.clone(), values.clone())),
+            }
+        }
+        steps.extend(writes.into_iter().map(|(model, ids, values)| Step::Write {
+            model,
+            ids,
+            values,
+        }));
+
+        let mut unlinks: Vec<(String, Vec<u32>)> = vec![];
+        for (model, ids) in &self.unlinks {
+            match unlinks.iter_mut().find(|(m, _)| m == model) {
+                Some((_, batch)) => merge_ids(batch, ids),
+                None => unlinks.push((model.clone(), ids.clone())),
+            }
+        }
+        steps.extend(
+            unlinks
+                .into_iter()
+                .map(|(model, ids)| Step::Unlink { model, ids }),
+        );
+        Ok(steps)
+    }
+
+    /// The plan as text, one call per line, without sending anything.
+    pub fn dry_run(&self) -> Result<String, Error> {
+        Ok(self
+            .plan()?
+            .iter()
+            .map(|step| format!("{}\n", step))
+            .collect())
+    }
+
+    /// Sends the pending operations and returns the ids of the created records.
+    pub async fn flush(self, odoo: &Odoo) -> Result<HashMap<PendingId, u32>, Error> {
+        let mut created: HashMap<PendingId, u32> = HashMap::new();
+        for step in self.plan()? {
+            match step {
+                Step::Create { model, records } => {
+                    let vals_list: Vec<Map<String, Value>> = records
+                        .iter()
+                        .map(|record| {
+                            let create = &self.creates[record.0];
+                            let mut values = create.values.clone();
+                            for (field, target) in &create.links {
+                                values.insert(field.clone(), Value::from(created[target]));
+                            }
+                            values
+                        })
+                        .collect();
+                    let ids: Vec<u32> = odoo.call(&model, "create", (vals_list,)).await?.result;
+                    created.extend(records.into_iter().zip(ids));
+                }
+                Step::Write { model, ids, values } => {
+                    let _: bool = odoo.call(&model, "write", (ids, values)).await?.result;
+                }
+                Step::Unlink { model, ids } => {
+                    let _: bool = odoo.call(&model, "unlink", (ids,)).await?.result;
+                }
+            }
+        }
+        Ok(created)
+    }
+}
+
+fn merge_ids(batch: &mut Vec<u32>, ids: &[u32]) {
+    for id in ids {
+        if !batch.contains(id) {
+            batch.push(*id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit_of_work::UnitOfWork;
+    use crate::values::Values;
+
+    #[test]
+    fn test_plan() {
+        let mut work = UnitOfWork::new();
+        let line = work.create("sale.order.line", Values::new().set("name", "Desk"));
+        let order = work.create("sale.order", Values::new().set("name", "S1"));
+        let partner = work.create("res.partner", Values::new().set("name", "Azure"));
+        work.create("res.partner", Values::new().set("name", "Deco"));
+        work.link(line, "order_id", order);
+        work.link(order, "partner_id", partner);
+        work.write("res.partner", &[1], Values::new().set("active", false));
+        work.write("res.partner", &[2, 1], Values::new().set("active", false));
+        work.write("res.partner", &[3], Values::new().set("name", "Gemini"));
+        work.unlink("crm.tag", &[4]);
+        work.unlink("crm.tag", &[5]);
+
+        assert_eq!(
+            work.dry_run().unwrap(),
+            "create res.partner x2\n\
+             create sale.order x1\n\
+             create sale.order.line x1\n\
+             write res.partner [1, 2]: active\n\
+             write res.partner [3]: name\n\
+             unlink crm.tag [4, 5]\n"
+        );
+
+        work.link(partner, "parent_id", line);
+        assert!(work.plan().is_err());
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_flush() {
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let old = server.insert("res.partner", json!({"name": "Old"}));
+
+        let mut work = UnitOfWork::new();
+        let contact = work.create("res.partner", Values::new().set("name", "Joel"));
+        let company = work.create("res.partner", Values::new().set("name", "Azure"));
+        work.link(contact, "parent_id", company);
+        work.unlink("res.partner", &[old]);
+        let created = work.flush(&odoo).await.unwrap();
+
+        let records = server.records("res.partner");
+        assert_eq!(records.len(), 2);
+        let joel = records.iter().find(|r| r["name"] == "Joel").unwrap();
+        assert_eq!(joel["id"], created[&contact]);
+        assert_eq!(joel["parent_id"], created[&company]);
+    }
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use hyper::header::HOST;
+use hyper::Body;
+use reqwest::ResponseBuilderExt;
+use tokio::net::UnixStream;
+
+use crate::error::Error;
+
+/// Sends `request` over the Unix socket at `path`, one connection per request.
+///
+/// Only buffered bodies (JSON, forms) are supported; streaming multipart uploads are not.
+pub(crate) async fn send(
+    path: &Path,
+    request: reqwest::Request,
+) -> Result<reqwest::Response, Error> {
+    let body = match request.body() {
+        Some(body) => body.as_bytes().map(<[u8]>::to_vec).ok_or_else(|| {
+            Error::Request(String::from(
+                "Streaming request bodies can't be sent over a Unix socket",
+            ))
+        })?,
+        None => vec![],
+    };
+    let url = request.url().clone();
+    let path_and_query = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let mut builder = hyper::Request::builder()
+        .method(request.method().clone())
+        .uri(path_and_query)
+        .header(HOST, url.host_str().unwrap_or("localhost"));
+    for (name, value) in request.headers() {
+        builder = builder.header(name, value);
+    }
+    let hyper_request = builder
+        .body(Body::from(body))
+        .map_err(|e| Error::Request(e.to_string()))?;
+
+    let exchange = async {
+        let stream = UnixStream::connect(path)
+            .await
+            .map_err(|e| Error::NotSent(format!("{}: {}", path.display(), e)))?;
+        let (mut sender, connection) = hyper::client::conn::handshake(stream)
+            .await
+            .map_err(|e| Error::NotSent(e.to_string()))?;
+        tokio::spawn(connection);
+        sender
+            .send_request(hyper_request)
+            .await
+            .map_err(|e| Error::Request(e.to_string()))
+    };
+    let response = match request.timeout() {
+        Some(timeout) => tokio::time::timeout(*timeout, exchange)
+            .await
+            .map_err(|_| Error::DeadlineExceeded)??,
+        None => exchange.await?,
+    };
+    // keeps the request URL, which reqwest reports as the final one
+    let (parts, body) = response.into_parts();
+    let mut response = hyper::Response::builder()
+        .status(parts.status)
+        .version(parts.version)
+        .url(url)
+        .body(body)
+        .map_err(|e| Error::Request(e.to_string()))?;
+    *response.headers_mut() = parts.headers;
+    Ok(reqwest::Response::from(response))
+}
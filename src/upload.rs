@@ -0,0 +1,313 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Map, Value};
+use sha1_smol::Sha1;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::checksum::sha1_hex;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+const DEFAULT_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Where `Odoo::upload_chunked` sends the chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChunkTarget {
+    /// One `ir.attachment` per chunk, named `<name>.001`, `<name>.002`, ..., joined back
+    /// with `Odoo::download_parts`. Works on any server.
+    Parts,
+    /// A JSON route of a custom module assembling the file server-side. It gets each chunk
+    /// as `{upload_id, index, count, name, mimetype, res_model, res_id, data}` (base64
+    /// `data`, 0-based `index`) and answers the last one with the attachment's id.
+    Controller(String),
+}
+
+/// State of an upload after each chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Bytes of the file sent so far.
+    pub sent: u64,
+    pub total: u64,
+    /// Chunks sent so far.
+    pub chunks: usize,
+    pub chunk_count: usize,
+}
+
+/// The result of `Odoo::upload_chunked`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedFile {
+    /// One attachment per chunk with `ChunkTarget::Parts`, the assembled one with
+    /// `ChunkTarget::Controller`.
+    pub ids: Vec<u32>,
+    /// SHA-1 hex digest of the whole file.
+    pub sha1: String,
+}
+
+/// Settings of `Odoo::upload_chunked`.
+#[derive(Clone)]
+pub struct ChunkedUpload {
+    target: ChunkTarget,
+    chunk_size: usize,
+    progress: Option<Arc<dyn Fn(UploadProgress) + Send + Sync>>,
+}
+
+impl ChunkedUpload {
+    /// Uploads in chunks of 16 MiB, well under what a JSON-RPC body can carry once encoded.
+    pub fn new(target: ChunkTarget) -> ChunkedUpload {
+        ChunkedUpload {
+            target,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            progress: None,
+        }
+    }
+
+    /// Size of the chunks before base64 encoding.
+    pub fn chunk_size(mut self, chunk_size: usize) -> ChunkedUpload {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn on_progress<F>(mut self, progress: F) -> ChunkedUpload
+    where
+        F: Fn(UploadProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+}
+
+impl fmt::Debug for ChunkedUpload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedUpload")
+            .field("target", &self.target)
+            .field("chunk_size", &self.chunk_size)
+            .finish()
+    }
+}
+
+impl Odoo {
+    /// Attaches the file at `path` to `model`/`id` in chunks, for files too large for a
+    /// single base64 JSON body (over ~100MB). Each created attachment is checked against its
+    /// `checksum`, failing with `Error::ChecksumMismatch` on corruption.
+    pub async fn upload_chunked(
+        &self,
+        model: &str,
+        id: u32,
+        path: impl AsRef<Path>,
+        mimetype: &str,
+        upload: ChunkedUpload,
+    ) -> Result<UploadedFile, Error> {
+        let path = path.as_ref();
+        let read_error =
+            |e: std::io::Error| Error::Request(format!("Can't read {}: {}", path.display(), e));
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut file = tokio::fs::File::open(path).await.map_err(read_error)?;
+        let total = file.metadata().await.map_err(read_error)?.len();
+        let chunk_count = chunk_count(total, upload.chunk_size);
+        let upload_id = format!("{:016x}", rand::random::<u64>());
+
+        let mut ids = vec![];
+        let mut hasher = Sha1::new();
+        let mut progress = UploadProgress {
+            sent: 0,
+            total,
+            chunks: 0,
+            chunk_count,
+        };
+        let mut chunk = vec![0; upload.chunk_size];
+        for index in 0..chunk_count {
+            let size = ((total - progress.sent) as usize).min(upload.chunk_size);
+            file.read_exact(&mut chunk[..size])
+                .await
+                .map_err(read_error)?;
+            hasher.update(&chunk[..size]);
+            let data = STANDARD.encode(&chunk[..size]);
+            match &upload.target {
+                ChunkTarget::Parts => {
+                    let values = json!({
+                        "name": part_name(&name, index, chunk_count),
+                        "mimetype": mimetype,
+                        "res_model": model,
+                        "res_id": id,
+                        "datas": data,
+                    });
+                    let part: u32 = self
+                        .call("ir.attachment", "create", vec![values])
+                        .await?
+                        .result;
+                    self.verify_checksum(part, &sha1_hex(&chunk[..size]))
+                        .await?;
+                    ids.push(part);
+                }
+                ChunkTarget::Controller(route) => {
+                    let params = json!({
+                        "upload_id": upload_id,
+                        "index": index,
+                        "count": chunk_count,
+                        "name": name,
+                        "mimetype": mimetype,
+                        "res_model": model,
+                        "res_id": id,
+                        "data": data,
+                    });
+                    let result: Value = self.call_controller(route, params).await?;
+                    if index + 1 == chunk_count {
+                        let attachment = result.as_u64().ok_or_else(|| {
+                            Error::Request(format!("Unexpected upload response: {}", result))
+                        })?;
+                        ids.push(attachment as u32);
+                    }
+                }
+            }
+            progress.sent += size as u64;
+            progress.chunks += 1;
+            if let Some(hook) = &upload.progress {
+                hook(progress);
+            }
+        }
+        let sha1 = hasher.digest().to_string();
+        if let (ChunkTarget::Controller(_), Some(attachment)) = (&upload.target, ids.first()) {
+            self.verify_checksum(*attachment, &sha1).await?;
+        }
+        Ok(UploadedFile { ids, sha1 })
+    }
+
+    /// Joins the parts of a `ChunkTarget::Parts` upload (`UploadedFile::ids`, in order) into
+    /// the file at `path`, checking each against its `checksum`, and returns the SHA-1 hex
+    /// digest of the whole file. Parts are read over JSON-RPC, so no web session is needed.
+    pub async fn download_parts(
+        &self,
+        ids: &[u32],
+        path: impl AsRef<Path>,
+    ) -> Result<String, Error> {
+        let path = path.as_ref();
+        let write_error =
+            |e: std::io::Error| Error::Request(format!("Can't write {}: {}", path.display(), e));
+        let mut file = tokio::fs::File::create(path).await.map_err(write_error)?;
+        let mut hasher = Sha1::new();
+        for &part in ids {
+            let records: Vec<Map<String, Value>> = self
+                .call("ir.attachment", "read", ([part], ["datas", "checksum"]))
+                .await?
+                .result;
+            let record = records.first().ok_or_else(|| {
+                Error::NotFound(format!("ir.attachment({}) does not exist", part))
+            })?;
+            let content = match record.get("datas").and_then(Value::as_str) {
+                Some(datas) => STANDARD.decode(datas).map_err(|e| Error::Decode {
+                    path: None,
+                    message: e.to_string(),
+                })?,
+                None => vec![],
+            };
+            let digest = sha1_hex(&content);
+            match record.get("checksum").and_then(Value::as_str) {
+                Some(checksum) if checksum != digest => {
+                    return Err(Error::ChecksumMismatch {
+                        attachment_id: part,
+                        expected: checksum.to_string(),
+                        actual: digest,
+                    })
+                }
+                _ => {}
+            }
+            hasher.update(&content);
+            file.write_all(&content).await.map_err(write_error)?;
+        }
+        file.flush().await.map_err(write_error)?;
+        Ok(hasher.digest().to_string())
+    }
+}
+
+// an empty file still takes one chunk
+fn chunk_count(total: u64, chunk_size: usize) -> usize {
+    (total.div_ceil(chunk_size as u64) as usize).max(1)
+}
+
+fn part_name(name: &str, index: usize, count: usize) -> String {
+    let width = count.to_string().len().max(3);
+    format!("{}.{:0width$}", name, index + 1, width = width)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::upload::{chunk_count, part_name};
+
+    #[test]
+    fn test_chunks() {
+        assert_eq!(chunk_count(0, 10), 1);
+        assert_eq!(chunk_count(10, 10), 1);
+        assert_eq!(chunk_count(11, 10), 2);
+        assert_eq!(part_name("backup.zip", 0, 12), "backup.zip.001");
+        assert_eq!(part_name("backup.zip", 1233, 1500), "backup.zip.1234");
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_upload_chunked() {
+        use std::sync::{Arc, Mutex};
+
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+        use serde_json::json;
+
+        use crate::checksum::sha1_hex;
+        use crate::mock::MockServer;
+        use crate::upload::{ChunkTarget, ChunkedUpload, UploadProgress};
+
+        let server = MockServer::start().await.unwrap();
+        let partner = server.insert("res.partner", json!({"name": "Azure"}));
+        let odoo = server.odoo().await.unwrap();
+
+        let content: Vec<u8> = (0..2500).map(|i| (i % 251) as u8).collect();
+        let path = std::env::temp_dir().join(format!("upload-{}.bin", std::process::id()));
+        std::fs::write(&path, &content).unwrap();
+
+        let updates: Arc<Mutex<Vec<UploadProgress>>> = Arc::default();
+        let sink = updates.clone();
+        let upload = ChunkedUpload::new(ChunkTarget::Parts)
+            .chunk_size(1000)
+            .on_progress(move |progress| sink.lock().unwrap().push(progress));
+        let uploaded = odoo
+            .upload_chunked("res.partner", partner, &path, "application/zip", upload)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(uploaded.ids.len(), 3);
+        assert_eq!(uploaded.sha1, sha1_hex(&content));
+
+        let parts = server.records("ir.attachment");
+        let mut joined = vec![];
+        for part in &parts {
+            assert_eq!(part["res_id"], partner);
+            joined.extend(STANDARD.decode(part["datas"].as_str().unwrap()).unwrap());
+        }
+        assert_eq!(joined, content);
+        assert!(parts[2]["name"].as_str().unwrap().ends_with(".bin.003"));
+
+        let joined_path = path.with_extension("joined");
+        let sha1 = odoo
+            .download_parts(&uploaded.ids, &joined_path)
+            .await
+            .unwrap();
+        assert_eq!(sha1, uploaded.sha1);
+        assert_eq!(std::fs::read(&joined_path).unwrap(), content);
+        let mut reversed = uploaded.ids.clone();
+        reversed.reverse();
+        let sha1 = odoo.download_parts(&reversed, &joined_path).await.unwrap();
+        assert_ne!(sha1, uploaded.sha1);
+        std::fs::remove_file(&joined_path).unwrap();
+
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 3);
+        assert_eq!((updates[0].sent, updates[0].total), (1000, 2500));
+        assert_eq!((updates[2].sent, updates[2].chunks), (2500, 3));
+    }
+}
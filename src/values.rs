@@ -0,0 +1,283 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::command::Command;
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::types::Model;
+
+/// Builder for `create`/`write` payloads:
+/// `Values::new().set("name", "Test").set_m2o("partner_id", 7).set_o2m("line_ids", commands)`.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Values(Map<String, Value>);
+
+impl Values {
+    pub fn new() -> Values {
+        Values(Map::new())
+    }
+
+    pub fn set(mut self, field: &str, value: impl Into<Value>) -> Values {
+        self.0.insert(field.to_string(), value.into());
+        self
+    }
+
+    /// Sets `field` to `False`, Odoo's empty value for any field type.
+    pub fn unset(self, field: &str) -> Values {
+        self.set(field, false)
+    }
+
+    pub fn set_m2o(self, field: &str, id: u32) -> Values {
+        self.set(field, id)
+    }
+
+    /// Sets a one2many or many2many field with x2many commands.
+    pub fn set_o2m(self, field: &str, commands: Vec<Command>) -> Values {
+        let commands = commands
+            .iter()
+            .map(|command| serde_json::to_value(command).unwrap_or_default())
+            .collect::<Vec<Value>>();
+        self.set(field, commands)
+    }
+
+    /// Replaces the records linked by a many2many field.
+    pub fn set_m2m(self, field: &str, ids: &[u32]) -> Values {
+        self.set_o2m(field, vec![Command::Set(ids.to_vec())])
+    }
+
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.0.get(field)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_map(self) -> Map<String, Value> {
+        self.0
+    }
+}
+
+impl From<Map<String, Value>> for Values {
+    fn from(values: Map<String, Value>) -> Values {
+        Values(values)
+    }
+}
+
+impl From<Values> for Map<String, Value> {
+    fn from(values: Values) -> Map<String, Value> {
+        values.0
+    }
+}
+
+impl From<Values> for Value {
+    fn from(values: Values) -> Value {
+        Value::Object(values.0)
+    }
+}
+
+/// A record read from the server that remembers its original values, so `save` only writes
+/// the fields actually modified.
+///
+/// ```ignore
+/// let mut partner = Tracked::new(partner)?;
+/// partner.email = Some("new@example.com".to_string());
+/// partner.save(&odoo, "res.partner").await?; // writes {"email": ...} only
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tracked<T> {
+    record: T,
+    original: Map<String, Value>,
+}
+
+impl<T: Serialize> Tracked<T> {
+    pub fn new(record: T) -> Result<Tracked<T>, Error> {
+        let original = to_map(&record)?;
+        Ok(Tracked { record, original })
+    }
+
+    /// Fields whose serialized value differs from the original one.
+    pub fn changes(&self) -> Result<Values, Error> {
+        let mut changes = Values::new();
+        for (field, value) in to_map(&self.record)? {
+            if field != "id" && self.original.get(&field) != Some(&value) {
+                changes = changes.set(&field, value);
+            }
+        }
+        Ok(changes)
+    }
+
+    pub fn is_dirty(&self) -> Result<bool, Error> {
+        Ok(!self.changes()?.is_empty())
+    }
+
+    /// Writes the changed fields, if any, and makes the current values the new baseline.
+    /// Returns whether a `write` was sent.
+    pub async fn save(&mut self, odoo: &Odoo, model: &str) -> Result<bool, Error> {
+        let changes = self.changes()?;
+        if changes.is_empty() {
+            return Ok(false);
+        }
+        let id = self
+            .original
+            .get("id")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| Error::Request(String::from("Tracked record has no id")))?;
+        let _: bool = odoo.call(model, "write", (vec![id], changes)).await?.result;
+        self.original = to_map(&self.record)?;
+        Ok(true)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.record
+    }
+}
+
+impl<T: Model + Serialize> Tracked<T> {
+    /// Reads record `id` of a typed model (which must serialize its `id`) for tracking.
+    pub async fn fetch(odoo: &Odoo, id: u32) -> Result<Tracked<T>, Error> {
+        let record: T = odoo
+            .read(T::MODEL, &[id], T::FIELDS)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("{}({}) does not exist", T::MODEL, id)))?;
+        Tracked::new(record)
+    }
+
+    /// `save` to the model's own table.
+    pub async fn save_model(&mut self, odoo: &Odoo) -> Result<bool, Error> {
+        self.save(odoo, T::MODEL).await
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.record
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.record
+    }
+}
+
+fn to_map<T: Serialize>(record: &T) -> Result<Map<String, Value>, Error> {
+    match serde_json::to_value(record) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(Error::Request(String::from(
+            "Tracked records must serialize to a map",
+        ))),
+        Err(e) => Err(Error::Request(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_json::json;
+
+    use crate::command::Command;
+    use crate::values::{Tracked, Values};
+
+    #[test]
+    fn test_values_builder() {
+        let values = Values::new()
+            .set("name", "Test")
+            .set("amount", 12.5)
+            .set_m2o("partner_id", 7)
+            .unset("user_id")
+            .set_m2m("tag_ids", &[1, 2])
+            .set_o2m(
+                "line_ids",
+                vec![Command::Create(json!({"name": "Line"})), Command::Delete(3)],
+            );
+        assert_eq!(
+            serde_json::to_value(&values).unwrap(),
+            json!({
+                "name": "Test",
+                "amount": 12.5,
+                "partner_id": 7,
+                "user_id": false,
+                "tag_ids": [[6, 0, [1, 2]]],
+                "line_ids": [[0, 0, {"name": "Line"}], [2, 3, 0]],
+            })
+        );
+    }
+
+    #[derive(Serialize)]
+    struct Partner {
+        id: u32,
+        name: String,
+        email: Option<String>,
+    }
+
+    #[test]
+    fn test_tracked_changes() {
+        let mut partner = Tracked::new(Partner {
+            id: 7,
+            name: "Azure".to_string(),
+            email: None,
+        })
+        .unwrap();
+        assert!(!partner.is_dirty().unwrap());
+
+        partner.email = Some("azure@example.com".to_string());
+        partner.name = "Azure".to_string();
+        assert_eq!(
+            serde_json::to_value(partner.changes().unwrap()).unwrap(),
+            json!({"email": "azure@example.com"})
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_tracked_save_model() {
+        use serde::Deserialize;
+
+        use crate::mock::MockServer;
+        use crate::types::{Model, OdooOption};
+
+        #[derive(Serialize, Deserialize)]
+        struct Contact {
+            id: u32,
+            name: String,
+            email: OdooOption<String>,
+        }
+
+        impl Model for Contact {
+            const MODEL: &'static str = "res.partner";
+            const FIELDS: &'static [&'static str] = &["name", "email"];
+        }
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+        let id = server.insert("res.partner", json!({"name": "Azure", "email": false}));
+
+        let mut partner = Tracked::<Contact>::fetch(&odoo, id).await.unwrap();
+        assert!(!partner.save_model(&odoo).await.unwrap());
+
+        // a concurrent edit of another field survives the save
+        let _: bool = odoo
+            .call(
+                "res.partner",
+                "write",
+                ([id], json!({"name": "Azure Interior"})),
+            )
+            .await
+            .unwrap()
+            .result;
+        *partner.email = Some("azure@example.com".to_string());
+        assert!(partner.save_model(&odoo).await.unwrap());
+        assert!(!partner.is_dirty().unwrap());
+
+        let record = &server.records("res.partner")[0];
+        assert_eq!(record["name"], "Azure Interior");
+        assert_eq!(record["email"], "azure@example.com");
+    }
+}
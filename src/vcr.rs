@@ -0,0 +1,417 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use reqwest::header::{HeaderName, HeaderValue, SET_COOKIE};
+use reqwest::ResponseBuilderExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::dump::redact;
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+const SCRUBBED: &str = "***";
+
+/// Whether a `Cassette` talks to the server or plays back what it recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Sends requests and appends the exchanges, saved with `Cassette::save`.
+    Record,
+    /// Answers requests from the cassette without any network access.
+    Replay,
+}
+
+/// Recorded HTTP exchanges, set on a client with `Odoo::with_cassette` to run
+/// integration tests against a live server once and replay them in CI:
+///
+/// ```no_run
+/// # async fn run() -> Result<(), async_odoors::error::Error> {
+/// use async_odoors::odoo::Odoo;
+/// use async_odoors::vcr::Cassette;
+///
+/// // records on the first run, replays once the file exists
+/// let cassette = Cassette::open("tests/cassettes/partners.json")?.scrub("my-api-key");
+/// let mut odoo = Odoo::new("http://localhost:8069", "demo").with_cassette(cassette.clone());
+/// odoo.login("admin", "my-api-key").await?;
+/// cassette.save()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Logins, passwords (including the database master password), session cookies and the
+/// `scrub`bed secrets never reach the file. Requests are matched on method, path and body
+/// (ignoring the JSON-RPC id), each exchange being replayed once, in order.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    secrets: Vec<String>,
+    interactions: Arc<Mutex<Vec<(Interaction, bool)>>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Interaction {
+    method: String,
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_body: Option<Body>,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Body,
+}
+
+// tagged, since any text would read back as JSON: `{"json": ...}`, `{"text": "..."}` or
+// `{"base64": "..."}` for binary content
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Body {
+    Json(Value),
+    Text(String),
+    Base64(#[serde(with = "base64_bytes")] Vec<u8>),
+}
+
+mod base64_bytes {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        STANDARD.decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Cassette {
+    pub fn record(path: impl AsRef<Path>) -> Cassette {
+        Cassette {
+            path: path.as_ref().to_path_buf(),
+            mode: Mode::Record,
+            secrets: vec![],
+            interactions: Arc::default(),
+        }
+    }
+
+    pub fn replay(path: impl AsRef<Path>) -> Result<Cassette, Error> {
+        let path = path.as_ref();
+        let content = std::fs::read(path)
+            .map_err(|e| Error::Request(format!("Can't read {}: {}", path.display(), e)))?;
+        let interactions: Vec<Interaction> =
+            serde_json::from_slice(&content).map_err(|e| Error::Decode {
+                path: None,
+                message: e.to_string(),
+            })?;
+        Ok(Cassette {
+            path: path.to_path_buf(),
+            mode: Mode::Replay,
+            secrets: vec![],
+            interactions: Arc::new(Mutex::new(
+                interactions.into_iter().map(|i| (i, false)).collect(),
+            )),
+        })
+    }
+
+    /// Replays `path` if it exists, records it otherwise.
+    pub fn open(path: impl AsRef<Path>) -> Result<Cassette, Error> {
+        match path.as_ref().exists() {
+            true => Cassette::replay(path),
+            false => Ok(Cassette::record(path)),
+        }
+    }
+
+    /// Replaces `secret` with `***` wherever it appears in recorded requests and responses.
+    pub fn scrub(mut self, secret: &str) -> Cassette {
+        if !secret.is_empty() {
+            self.secrets.push(secret.to_string());
+        }
+        self
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Writes the recorded exchanges; does nothing when replaying.
+    pub fn save(&self) -> Result<(), Error> {
+        if self.mode == Mode::Replay {
+            return Ok(());
+        }
+        let interactions: Vec<Interaction> = self
+            .interactions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(interaction, _)| interaction.clone())
+            .collect();
+        let content =
+            serde_json::to_vec_pretty(&interactions).map_err(|e| Error::Request(e.to_string()))?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Error::Request(format!("Can't create {}: {}", parent.display(), e)))?;
+        }
+        std::fs::write(&self.path, content)
+            .map_err(|e| Error::Request(format!("Can't write {}: {}", self.path.display(), e)))
+    }
+
+    pub(crate) async fn execute(
+        &self,
+        odoo: &Odoo,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, Error> {
+        let method = request.method().to_string();
+        let path = match request.url().query() {
+            Some(query) => format!("{}?{}", request.url().path(), query),
+            None => request.url().path().to_string(),
+        };
+        let request_body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| self.request_body(body));
+
+        if self.mode == Mode::Replay {
+            let mut interactions = self.interactions.lock().unwrap();
+            let (interaction, used) = interactions
+                .iter_mut()
+                .find(|(i, used)| {
+                    !used && i.method == method && i.path == path && i.request_body == request_body
+                })
+                .ok_or_else(|| {
+                    Error::Request(format!(
+                        "No recorded interaction for {} {} in {}",
+                        method,
+                        path,
+                        self.path.display()
+                    ))
+                })?;
+            *used = true;
+            return response(interaction, request.url().clone());
+        }
+
+        let url = request.url().clone();
+        let resp = odoo.transport(request).await?;
+        let status = resp.status();
+        let raw_headers = resp.headers().clone();
+        let headers = raw_headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value.to_str().ok()?;
+                let value = match name == SET_COOKIE {
+                    true => scrub_cookie(value),
+                    false => self.scrub_text(value),
+                };
+                Some((name.to_string(), value))
+            })
+            .collect();
+        let body = odoo.read_body(resp).await?;
+        let interaction = Interaction {
+            method,
+            path,
+            request_body,
+            status: status.as_u16(),
+            headers,
+            body: self.body(&body),
+        };
+        self.interactions.lock().unwrap().push((interaction, false));
+
+        // the caller gets the unscrubbed exchange
+        let mut resp = http::Response::builder()
+            .status(status)
+            .url(url)
+            .body(body)
+            .map_err(|e| Error::Request(e.to_string()))?;
+        *resp.headers_mut() = raw_headers;
+        Ok(reqwest::Response::from(resp))
+    }
+
+    // the JSON-RPC id is random, credentials are always scrubbed
+    fn request_body(&self, body: &[u8]) -> Body {
+        match self.body(body) {
+            Body::Json(mut json) => {
+                if let Value::Object(json) = &mut json {
+                    json.remove("id");
+                }
+                redact(&mut json);
+                Body::Json(json)
+            }
+            body => body,
+        }
+    }
+
+    fn body(&self, body: &[u8]) -> Body {
+        match std::str::from_utf8(body) {
+            Ok(text) => {
+                let text = self.scrub_text(text);
+                match serde_json::from_str(&text) {
+                    Ok(json) => Body::Json(json),
+                    Err(_) => Body::Text(text),
+                }
+            }
+            Err(_) => Body::Base64(body.to_vec()),
+        }
+    }
+
+    fn scrub_text(&self, text: &str) -> String {
+        self.secrets.iter().fold(text.to_string(), |text, secret| {
+            text.replace(secret.as_str(), SCRUBBED)
+        })
+    }
+}
+
+impl Odoo {
+    /// Records or replays every HTTP exchange through `cassette`, see `Cassette`.
+    pub fn with_cassette(mut self, cassette: Cassette) -> Odoo {
+        self.cassette = Some(cassette);
+        self
+    }
+}
+
+// `session_id=abc; Path=/` -> `session_id=***; Path=/`
+fn scrub_cookie(cookie: &str) -> String {
+    match cookie.split_once(';') {
+        Some((pair, attributes)) => format!("{};{}", scrub_cookie(pair), attributes),
+        None => match cookie.split_once('=') {
+            Some((name, _)) => format!("{}={}", name, SCRUBBED),
+            None => cookie.to_string(),
+        },
+    }
+}
+
+fn response(interaction: &Interaction, url: reqwest::Url) -> Result<reqwest::Response, Error> {
+    let body = match &interaction.body {
+        Body::Json(json) => json.to_string().into_bytes(),
+        Body::Text(text) => text.clone().into_bytes(),
+        Body::Base64(bytes) => bytes.clone(),
+    };
+    let mut builder = http::Response::builder()
+        .status(interaction.status)
+        .url(url);
+    for (name, value) in &interaction.headers {
+        // the body may have been re-encoded
+        if name == "content-length" || name == "transfer-encoding" {
+            continue;
+        }
+        let name = HeaderName::try_from(name.as_str());
+        let value = HeaderValue::try_from(value.as_str());
+        if let (Ok(name), Ok(value)) = (name, value) {
+            builder = builder.header(name, value);
+        }
+    }
+    let resp = builder
+        .body(body)
+        .map_err(|e| Error::Request(e.to_string()))?;
+    Ok(reqwest::Response::from(resp))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vcr::scrub_cookie;
+
+    #[test]
+    fn test_scrub_cookie() {
+        assert_eq!(
+            scrub_cookie("session_id=abc123; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Path=/"),
+            "session_id=***; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Path=/"
+        );
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_record_replay() {
+        use serde_json::{json, Value};
+
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+        use crate::vcr::{Cassette, Mode};
+
+        let path = std::env::temp_dir().join(format!("cassette-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let server = MockServer::start().await.unwrap();
+        server.insert("res.partner", json!({"name": "Azure Interior"}));
+        server.add_user("joel", "s3cret");
+        let cassette = Cassette::open(&path).unwrap().scrub("Azure");
+        assert_eq!(cassette.mode(), Mode::Record);
+        let mut odoo = Odoo::new(server.url(), "mock").with_cassette(cassette.clone());
+        odoo.login("joel", "s3cret").await.unwrap();
+        let records: Vec<Value> = odoo
+            .call("res.partner", "search_read", (json!([]), ["name"]))
+            .await
+            .unwrap()
+            .result;
+        assert_eq!(records[0]["name"], "Azure Interior");
+        cassette.save().unwrap();
+
+        let recorded = std::fs::read_to_string(&path).unwrap();
+        assert!(!recorded.contains("Azure"));
+        assert!(!recorded.contains("s3cret"));
+
+        // no server needed anymore
+        drop(server);
+        let cassette = Cassette::open(&path).unwrap();
+        assert_eq!(cassette.mode(), Mode::Replay);
+        let mut odoo = Odoo::new("http://127.0.0.1:1", "mock").with_cassette(cassette);
+        odoo.login("joel", "s3cret").await.unwrap();
+        let records: Vec<Value> = odoo
+            .call("res.partner", "search_read", (json!([]), ["name"]))
+            .await
+            .unwrap()
+            .result;
+        assert_eq!(records[0]["name"], "*** Interior");
+        // each exchange replays once
+        assert!(odoo
+            .call::<_, Vec<Value>>("res.partner", "search_read", (json!([]), ["name"]))
+            .await
+            .is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_replay_raw_bodies() {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+        use serde_json::json;
+
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+        use crate::vcr::Cassette;
+
+        let path = std::env::temp_dir().join(format!("cassette-raw-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let html = "<html>\"quoted\" [1, 2]</html>";
+        let png = b"\x89PNG\r\n\x1a\n\x00\xff";
+        let server = MockServer::start().await.unwrap();
+        let text = server.insert(
+            "ir.attachment",
+            json!({"name": "page.html", "datas": STANDARD.encode(html)}),
+        );
+        let image = server.insert(
+            "ir.attachment",
+            json!({"name": "logo.png", "datas": STANDARD.encode(png)}),
+        );
+        let cassette = Cassette::open(&path).unwrap();
+        let mut odoo = Odoo::new(server.url(), "mock").with_cassette(cassette.clone());
+        odoo.web_login("admin", "admin").await.unwrap();
+        assert_eq!(
+            odoo.download_attachment(text).await.unwrap(),
+            html.as_bytes()
+        );
+        assert_eq!(odoo.download_attachment(image).await.unwrap(), png);
+        cassette.save().unwrap();
+        drop(server);
+
+        let cassette = Cassette::open(&path).unwrap();
+        let mut odoo = Odoo::new("http://127.0.0.1:1", "mock").with_cassette(cassette);
+        odoo.web_login("admin", "admin").await.unwrap();
+        assert_eq!(
+            odoo.download_attachment(text).await.unwrap(),
+            html.as_bytes()
+        );
+        assert_eq!(odoo.download_attachment(image).await.unwrap(), png);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,271 @@
+#[cfg(feature = "multipart")]
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::{deserialize_odoo_optional, Odoo};
+
+/// The web session's state, from `/web/session/get_session_info`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionInfo {
+    /// `None` when the session isn't logged in.
+    pub uid: Option<u32>,
+    pub db: String,
+    pub username: Option<String>,
+    pub name: Option<String>,
+    pub partner_id: Option<u32>,
+    pub is_admin: bool,
+    pub user_context: Map<String, Value>,
+    pub server_version: String,
+    pub server_version_info: Vec<Value>,
+    pub company_id: Option<u32>,
+    pub allowed_company_ids: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+struct RawSessionInfo {
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    uid: Option<u32>,
+    #[serde(default)]
+    db: String,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    username: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    partner_id: Option<u32>,
+    #[serde(default)]
+    is_admin: bool,
+    #[serde(default)]
+    user_context: Map<String, Value>,
+    #[serde(default)]
+    server_version: String,
+    #[serde(default)]
+    server_version_info: Vec<Value>,
+    // before Odoo 13
+    #[serde(default, deserialize_with = "deserialize_odoo_optional")]
+    company_id: Option<u32>,
+    #[serde(default)]
+    user_companies: Value,
+}
+
+impl From<RawSessionInfo> for SessionInfo {
+    fn from(raw: RawSessionInfo) -> SessionInfo {
+        // companies are `[id, name]` pairs up to Odoo 14, ids and `{id: {...}}` since 15
+        let company_id = |value: &Value| match value {
+            Value::Array(pair) => pair.first().and_then(Value::as_u64),
+            value => value.as_u64(),
+        };
+        let companies = &raw.user_companies;
+        let allowed_company_ids = match companies.get("allowed_companies") {
+            Some(Value::Array(companies)) => companies.iter().filter_map(company_id).collect(),
+            Some(Value::Object(companies)) => companies
+                .keys()
+                .filter_map(|id| id.parse::<u64>().ok())
+                .collect(),
+            _ => vec![],
+        };
+        SessionInfo {
+            uid: raw.uid,
+            db: raw.db,
+            username: raw.username,
+            name: raw.name,
+            partner_id: raw.partner_id,
+            is_admin: raw.is_admin,
+            user_context: raw.user_context,
+            server_version: raw.server_version,
+            server_version_info: raw.server_version_info,
+            company_id: companies
+                .get("current_company")
+                .and_then(company_id)
+                .map(|id| id as u32)
+                .or(raw.company_id),
+            allowed_company_ids: allowed_company_ids
+                .into_iter()
+                .map(|id| id as u32)
+                .collect(),
+        }
+    }
+}
+
+/// A file sent as one part of a `multipart/form-data` request.
+#[cfg(feature = "multipart")]
+#[derive(Debug, Clone)]
+pub struct UploadFile {
+    pub field_name: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl Odoo {
+    /// Fetches the web session's uid, companies, context and server version in one call;
+    /// needs a session from `web_login`.
+    pub async fn session_info(&self) -> Result<SessionInfo, Error> {
+        let raw: RawSessionInfo = self
+            .call_controller("web/session/get_session_info", json!({}))
+            .await?;
+        Ok(raw.into())
+    }
+
+    /// Fetches the CSRF token required by `type="http"` routes for the current web session.
+    pub async fn csrf_token(&self) -> Result<String, Error> {
+        let _call = self.begin_call().await?;
+        let html = self
+            .execute(self.http(reqwest::Method::GET, "web"))
+            .await?
+            .text()
+            .await
+            .map_err(|e| Error::Request(e.to_string()))?;
+        extract_csrf_token(&html)
+            .ok_or_else(|| Error::Request(String::from("CSRF token not found")))
+    }
+
+    /// Posts a `multipart/form-data` body to a web route, adding the session's CSRF token,
+    /// and returns the raw response body.
+    #[cfg(feature = "multipart")]
+    pub async fn post_multipart(
+        &self,
+        route: &str,
+        fields: &[(&str, &str)],
+        files: Vec<UploadFile>,
+    ) -> Result<String, Error> {
+        let mut form = Form::new().text("csrf_token", self.csrf_token().await?);
+        for (name, value) in fields {
+            form = form.text(name.to_string(), value.to_string());
+        }
+        for file in files {
+            let part = Part::bytes(file.data)
+                .file_name(file.file_name)
+                .mime_str(&file.mime_type)
+                .map_err(|e| Error::Request(e.to_string()))?;
+            form = form.part(file.field_name, part);
+        }
+
+        let _call = self.begin_call().await?;
+        let resp = self
+            .execute(self.http(reqwest::Method::POST, route).multipart(form))
+            .await?;
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| Error::Request(e.to_string()))?;
+        if !status.is_success() {
+            return Err(Error::Request(format!("{}: {}", status, body)));
+        }
+        Ok(body)
+    }
+
+    /// Uploads a file through `/web/binary/upload_attachment` and returns the created
+    /// attachments' metadata (`id`, `filename`, `mimetype`, ...).
+    #[cfg(feature = "multipart")]
+    pub async fn upload_attachment(
+        &self,
+        model: &str,
+        id: u32,
+        file_name: &str,
+        mime_type: &str,
+        data: Vec<u8>,
+    ) -> Result<Vec<Value>, Error> {
+        let id = id.to_string();
+        let file = UploadFile {
+            field_name: String::from("ufile"),
+            file_name: file_name.to_string(),
+            mime_type: mime_type.to_string(),
+            data,
+        };
+        let body = self
+            .post_multipart(
+                "web/binary/upload_attachment",
+                &[("model", model), ("id", &id)],
+                vec![file],
+            )
+            .await?;
+        serde_json::from_str(&body)
+            .map_err(|_| Error::Request(format!("Unexpected upload response: {}", body)))
+    }
+}
+
+pub(crate) fn extract_csrf_token(html: &str) -> Option<String> {
+    let start = html.find("csrf_token")? + "csrf_token".len();
+    let rest = &html[start..];
+    let rest = &rest[rest.find([':', '='])?..];
+    let rest = &rest[rest.find(['"', '\''])? + 1..];
+    let end = rest.find(['"', '\''])?;
+    let token = &rest[..end];
+    if token.is_empty() {
+        None
+    } else {
+        Some(token.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::web::{extract_csrf_token, RawSessionInfo, SessionInfo};
+
+    #[test]
+    fn test_session_info() {
+        let raw: RawSessionInfo = serde_json::from_value(json!({
+            "uid": 2,
+            "db": "prod",
+            "username": "admin",
+            "name": "Mitchell Admin",
+            "partner_id": 3,
+            "is_admin": true,
+            "user_context": {"lang": "en_US", "tz": "Europe/Brussels", "uid": 2},
+            "server_version": "14.0",
+            "server_version_info": [14, 0, 0, "final", 0, ""],
+            "user_companies": {
+                "current_company": [1, "YourCompany"],
+                "allowed_companies": [[1, "YourCompany"], [3, "Subsidiary"]],
+            },
+        }))
+        .unwrap();
+        let info = SessionInfo::from(raw);
+        assert_eq!(info.uid, Some(2));
+        assert_eq!(info.company_id, Some(1));
+        assert_eq!(info.allowed_company_ids, vec![1, 3]);
+        assert_eq!(info.user_context["tz"], "Europe/Brussels");
+
+        let raw: RawSessionInfo = serde_json::from_value(json!({
+            "uid": false,
+            "db": "prod",
+            "user_companies": false,
+            "company_id": 4,
+        }))
+        .unwrap();
+        let info = SessionInfo::from(raw);
+        assert_eq!(info.uid, None);
+        assert_eq!(info.company_id, Some(4));
+        assert!(info.allowed_company_ids.is_empty());
+    }
+
+    #[test]
+    fn test_extract_csrf_token() {
+        let html = r#"<script>var odoo = { csrf_token: "a1b2c3o", debug: "" };</script>"#;
+        assert_eq!(extract_csrf_token(html).unwrap(), "a1b2c3o");
+        let html = r#"<script>odoo.__session_info__ = {"csrf_token": "d4e5f6o"};</script>"#;
+        assert_eq!(extract_csrf_token(html).unwrap(), "d4e5f6o");
+        assert_eq!(extract_csrf_token("<html></html>"), None);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_web_session_info() {
+        use crate::mock::MockServer;
+        use crate::odoo::Odoo;
+
+        let server = MockServer::start().await.unwrap();
+        let mut web = Odoo::new(server.url(), "mock");
+        web.web_login("admin", "admin").await.unwrap();
+        let info = web.session_info().await.unwrap();
+        assert_eq!(info.uid, Some(2));
+        assert_eq!(info.username.as_deref(), Some("admin"));
+        assert_eq!(info.allowed_company_ids, vec![1]);
+    }
+}
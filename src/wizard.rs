@@ -0,0 +1,114 @@
+use serde_json::{Map, Value};
+
+use crate::action::{Action, WindowAction};
+use crate::error::Error;
+use crate::odoo::Odoo;
+
+/// A created `TransientModel` record together with the context it was opened with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wizard {
+    pub model: String,
+    pub id: u32,
+    pub context: Map<String, Value>,
+}
+
+impl Odoo {
+    /// Creates a wizard like the web client does: `default_get` under `context`, overridden
+    /// by `values`, then `create` with the same context.
+    pub async fn open_wizard(
+        &self,
+        model: &str,
+        values: Map<String, Value>,
+        context: Map<String, Value>,
+    ) -> Result<Wizard, Error> {
+        let fields = self.fields_get(model).await?;
+        let field_names: Vec<&String> = fields.keys().collect();
+        let mut kwargs = Map::new();
+        kwargs.insert("context".to_string(), Value::Object(context.clone()));
+
+        let mut defaults: Map<String, Value> = self
+            .call_kw(model, "default_get", (field_names,), kwargs.clone())
+            .await?
+            .result;
+        defaults.extend(values);
+        let id: u32 = self
+            .call_kw(model, "create", (defaults,), kwargs)
+            .await?
+            .result;
+        Ok(Wizard {
+            model: model.to_string(),
+            id,
+            context,
+        })
+    }
+
+    /// Opens the wizard described by an `act_window` action returned by a button (e.g.
+    /// `stock.immediate.transfer` from `button_validate`), reusing the action's context.
+    pub async fn open_wizard_from_action(
+        &self,
+        action: &WindowAction,
+        values: Map<String, Value>,
+    ) -> Result<Wizard, Error> {
+        let context = match &action.context {
+            Value::Object(context) => context.clone(),
+            _ => Map::new(),
+        };
+        self.open_wizard(&action.res_model, values, context).await
+    }
+}
+
+impl Wizard {
+    /// Calls one of the wizard's buttons (`process`, `action_create_payments`, ...) with the
+    /// wizard context and returns the follow-up action.
+    pub async fn call(&self, odoo: &Odoo, method: &str) -> Result<Action, Error> {
+        odoo.call_button(&self.model, method, &[self.id], Some(self.context.clone()))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_open_wizard_from_action() {
+        use serde_json::{json, Map, Value};
+
+        use crate::action::Action;
+        use crate::mock::MockServer;
+
+        let server = MockServer::start().await.unwrap();
+        let model = "stock.immediate.transfer";
+        server.on_call(model, "default_get", |_, kwargs| {
+            assert_eq!(kwargs["context"]["default_pick_ids"], json!([[4, 7]]));
+            Ok(json!({"pick_ids": [[4, 7]], "show_transfers": false}))
+        });
+        server.on_call(model, "process", |args, kwargs| {
+            assert_eq!(args[0], json!([1]));
+            assert_eq!(kwargs["context"]["button_validate_picking_ids"], json!([7]));
+            Ok(Value::Bool(true))
+        });
+        let odoo = server.odoo().await.unwrap();
+
+        let action = Action::from_value(json!({
+            "type": "ir.actions.act_window",
+            "res_model": model,
+            "views": [[false, "form"]],
+            "target": "new",
+            "context": {"default_pick_ids": [[4, 7]], "button_validate_picking_ids": [7]},
+        }))
+        .unwrap();
+        let action = match action {
+            Action::Window(action) => action,
+            action => panic!("unexpected action {:?}", action),
+        };
+        let mut values = Map::new();
+        values.insert("show_transfers".to_string(), Value::Bool(true));
+        let wizard = odoo.open_wizard_from_action(&action, values).await.unwrap();
+        assert_eq!(wizard.model, model);
+
+        let created = &server.records(model)[0];
+        assert_eq!(created["pick_ids"], json!([[4, 7]]));
+        assert_eq!(created["show_transfers"], true);
+        assert_eq!(wizard.call(&odoo, "process").await.unwrap(), Action::None);
+    }
+}
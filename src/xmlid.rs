@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::Error;
+use crate::odoo::Odoo;
+use crate::values::Values;
+
+const UPSERT_BATCH_SIZE: usize = 500;
+
+/// A record designated by database id or by external id (`module.name`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RecordRef {
+    Id(u32),
+    XmlId(String),
+}
+
+impl From<u32> for RecordRef {
+    fn from(id: u32) -> RecordRef {
+        RecordRef::Id(id)
+    }
+}
+
+impl From<&str> for RecordRef {
+    fn from(xmlid: &str) -> RecordRef {
+        RecordRef::XmlId(xmlid.to_string())
+    }
+}
+
+impl From<String> for RecordRef {
+    fn from(xmlid: String) -> RecordRef {
+        RecordRef::XmlId(xmlid)
+    }
+}
+
+#[derive(Deserialize)]
+struct ModelData {
+    model: String,
+    res_id: u32,
+}
+
+#[derive(Deserialize)]
+struct NamedModelData {
+    name: String,
+    res_id: u32,
+}
+
+/// Counts reported by `upsert_by_xmlid`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpsertResult {
+    pub created: usize,
+    pub updated: usize,
+}
+
+/// Splits `module.name` into its two parts; the module is mandatory.
+pub fn split_xmlid(xmlid: &str) -> Result<(&str, &str), Error> {
+    xmlid
+        .split_once('.')
+        .ok_or_else(|| Error::Request(format!("Invalid external id \"{}\"", xmlid)))
+}
+
+impl Odoo {
+    /// Resolves an external id to its `(model, id)` through `ir.model.data`.
+    pub async fn resolve_xmlid(&self, xmlid: &str) -> Result<(String, u32), Error> {
+        let (module, name) = split_xmlid(xmlid)?;
+        let mut kwargs = Map::new();
+        kwargs.insert("fields".to_string(), json!(["model", "res_id"]));
+        kwargs.insert("limit".to_string(), Value::from(1));
+        let data: Vec<ModelData> = self
+            .call_kw(
+                "ir.model.data",
+                "search_read",
+                ([["module", "=", module], ["name", "=", name]],),
+                kwargs,
+            )
+            .await?
+            .result;
+        data.into_iter()
+            .next()
+            .map(|data| (data.model, data.res_id))
+            .ok_or_else(|| Error::NotFound(format!("External id \"{}\" not found", xmlid)))
+    }
+
+    /// Returns the database id of `record`, checking that an external id belongs to `model`.
+    pub async fn resolve(&self, model: &str, record: &RecordRef) -> Result<u32, Error> {
+        match record {
+            RecordRef::Id(id) => Ok(*id),
+            RecordRef::XmlId(xmlid) => {
+                let (found, id) = self.resolve_xmlid(xmlid).await?;
+                if found != model {
+                    return Err(Error::NotFound(format!(
+                        "External id \"{}\" is a {} record, not {}",
+                        xmlid, found, model
+                    )));
+                }
+                Ok(id)
+            }
+        }
+    }
+    /// Registers `xmlid` for an existing record, as `noupdate` so module upgrades leave it alone.
+    pub async fn create_xmlid(&self, xmlid: &str, model: &str, id: u32) -> Result<(), Error> {
+        let (module, name) = split_xmlid(xmlid)?;
+        let values = json!({
+            "module": module,
+            "name": name,
+            "model": model,
+            "res_id": id,
+            "noupdate": true,
+        });
+        let _: u32 = self
+            .call("ir.model.data", "create", (values,))
+            .await?
+            .result;
+        Ok(())
+    }
+
+    /// Creates or updates `model` records keyed by external ids `module.<name>`, for ETL
+    /// imports. Missing records are created in batches along with their `ir.model.data`
+    /// entries; existing ones are written one by one.
+    pub async fn upsert_by_xmlid<V: Into<Values>>(
+        &self,
+        model: &str,
+        module: &str,
+        rows: Vec<(String, V)>,
+    ) -> Result<UpsertResult, Error> {
+        let mut result = UpsertResult::default();
+        let mut rows = rows.into_iter().peekable();
+        while rows.peek().is_some() {
+            let batch: Vec<(String, Values)> = rows
+                .by_ref()
+                .take(UPSERT_BATCH_SIZE)
+                .map(|(name, values)| (name, values.into()))
+                .collect();
+            let names: Vec<&str> = batch.iter().map(|(name, _)| name.as_str()).collect();
+            let mut kwargs = Map::new();
+            kwargs.insert("fields".to_string(), json!(["name", "res_id"]));
+            let existing: HashMap<String, u32> = self
+                .call_kw::<_, Vec<NamedModelData>>(
+                    "ir.model.data",
+                    "search_read",
+                    (json!([
+                        ["module", "=", module],
+                        ["model", "=", model],
+                        ["name", "in", names],
+                    ]),),
+                    kwargs,
+                )
+                .await?
+                .result
+                .into_iter()
+                .map(|data| (data.name, data.res_id))
+                .collect();
+
+            let mut missing = vec![];
+            for (name, values) in batch {
+                match existing.get(&name) {
+                    Some(id) => {
+                        let _: bool = self.call(model, "write", ([*id], values)).await?.result;
+                        result.updated += 1;
+                    }
+                    None => missing.push((name, values)),
+                }
+            }
+            if missing.is_empty() {
+                continue;
+            }
+            let vals_list: Vec<&Values> = missing.iter().map(|(_, values)| values).collect();
+            let ids: Vec<u32> = self.call(model, "create", (vals_list,)).await?.result;
+            let data: Vec<Value> = missing
+                .iter()
+                .zip(&ids)
+                .map(|((name, _), id)| {
+                    json!({
+                        "module": module,
+                        "name": name,
+                        "model": model,
+                        "res_id": id,
+                        "noupdate": true,
+                    })
+                })
+                .collect();
+            let _: Vec<u32> = self.call("ir.model.data", "create", (data,)).await?.result;
+            result.created += ids.len();
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_upsert_by_xmlid() {
+        use crate::mock::MockServer;
+        use crate::values::Values;
+        use crate::xmlid::UpsertResult;
+
+        let server = MockServer::start().await.unwrap();
+        let odoo = server.odoo().await.unwrap();
+
+        let rows = vec![
+            ("partner_a".to_string(), Values::new().set("name", "A")),
+            ("partner_b".to_string(), Values::new().set("name", "B")),
+        ];
+        let result = odoo
+            .upsert_by_xmlid("res.partner", "etl", rows)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            UpsertResult {
+                created: 2,
+                updated: 0
+            }
+        );
+
+        let rows = vec![
+            ("partner_b".to_string(), Values::new().set("name", "B2")),
+            ("partner_c".to_string(), Values::new().set("name", "C")),
+        ];
+        let result = odoo
+            .upsert_by_xmlid("res.partner", "etl", rows)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            UpsertResult {
+                created: 1,
+                updated: 1
+            }
+        );
+        let (_, id) = odoo.resolve_xmlid("etl.partner_b").await.unwrap();
+        let names: Vec<String> = server
+            .records("res.partner")
+            .iter()
+            .map(|record| record["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["A", "B2", "C"]);
+        assert!(server
+            .records("res.partner")
+            .iter()
+            .any(|record| record["id"] == id && record["name"] == "B2"));
+    }
+}